@@ -0,0 +1,8 @@
+CREATE TABLE game_stats (
+    game        BIGINT NOT NULL,
+    date        BIGINT NOT NULL,
+    mods        BIGINT NOT NULL,
+    subscribers BIGINT NOT NULL,
+    downloads   BIGINT NOT NULL,
+    PRIMARY KEY (game, date)
+);
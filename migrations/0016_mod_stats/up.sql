@@ -0,0 +1,11 @@
+CREATE TABLE mod_stats (
+    game                BIGINT NOT NULL,
+    mod_id              BIGINT NOT NULL,
+    date                BIGINT NOT NULL,
+    downloads           BIGINT NOT NULL,
+    subscribers         BIGINT NOT NULL,
+    rank                BIGINT NOT NULL,
+    ratings_positive    BIGINT NOT NULL,
+    ratings_negative    BIGINT NOT NULL,
+    PRIMARY KEY (game, mod_id, date)
+);
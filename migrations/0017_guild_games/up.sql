@@ -0,0 +1,8 @@
+CREATE TABLE guild_games (
+    guild   BIGINT NOT NULL,
+    game    BIGINT NOT NULL,
+    alias   TEXT NULL,
+    PRIMARY KEY (guild, game)
+);
+
+CREATE UNIQUE INDEX guild_games_alias ON guild_games (guild, alias);
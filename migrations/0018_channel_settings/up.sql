@@ -0,0 +1,5 @@
+CREATE TABLE channel_settings (
+    channel BIGINT PRIMARY KEY NOT NULL,
+    guild   BIGINT NOT NULL,
+    game    BIGINT NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE subscription_roles (
+    guild   BIGINT NOT NULL,
+    role    BIGINT NOT NULL,
+    PRIMARY KEY (guild, role)
+);
@@ -0,0 +1,10 @@
+DROP TABLE subscriptions_audit;
+
+CREATE TABLE settings_tmp (
+    guild BIGINT PRIMARY KEY NOT NULL,
+    game BIGINT NULL
+);
+
+INSERT INTO settings_tmp (guild, game) SELECT guild, game FROM settings;
+DROP TABLE settings;
+ALTER TABLE settings_tmp RENAME TO settings;
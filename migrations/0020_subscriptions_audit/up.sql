@@ -0,0 +1,15 @@
+CREATE TABLE subscriptions_audit (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    timestamp   BIGINT NOT NULL,
+    guild       BIGINT NOT NULL,
+    channel     BIGINT NOT NULL,
+    game        BIGINT NOT NULL,
+    actor       BIGINT NULL,
+    action      TEXT NOT NULL,
+    before      TEXT NULL,
+    after       TEXT NULL
+);
+
+CREATE INDEX subscriptions_audit_guild ON subscriptions_audit (guild, timestamp);
+
+ALTER TABLE settings ADD COLUMN log_channel BIGINT NULL;
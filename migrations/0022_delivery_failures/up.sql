@@ -0,0 +1,9 @@
+CREATE TABLE delivery_failures (
+    channel             BIGINT PRIMARY KEY NOT NULL,
+    guild               BIGINT NOT NULL,
+    failures            INTEGER NOT NULL DEFAULT 0,
+    permission_failures INTEGER NOT NULL DEFAULT 0,
+    last_error          TEXT NOT NULL,
+    last_failure        BIGINT NOT NULL,
+    paused              BOOLEAN NOT NULL DEFAULT 0
+);
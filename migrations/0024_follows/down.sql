@@ -0,0 +1,3 @@
+DROP TABLE follows_games;
+DROP TABLE follows_mods;
+DROP TABLE follows_users;
@@ -0,0 +1,22 @@
+CREATE TABLE follows_games (
+    user     BIGINT NOT NULL,
+    game     BIGINT NOT NULL,
+    disabled BOOLEAN NOT NULL DEFAULT 0,
+    PRIMARY KEY (user, game)
+);
+
+CREATE TABLE follows_mods (
+    user     BIGINT NOT NULL,
+    game     BIGINT NOT NULL,
+    mod_id   BIGINT NOT NULL,
+    disabled BOOLEAN NOT NULL DEFAULT 0,
+    PRIMARY KEY (user, game, mod_id)
+);
+
+CREATE TABLE follows_users (
+    user     BIGINT NOT NULL,
+    game     BIGINT NOT NULL,
+    name     TEXT NOT NULL,
+    disabled BOOLEAN NOT NULL DEFAULT 0,
+    PRIMARY KEY (user, game, name)
+);
@@ -0,0 +1,8 @@
+CREATE TABLE subscriptions_mods (
+    game         BIGINT NOT NULL,
+    channel      BIGINT NOT NULL,
+    guild        BIGINT NOT NULL,
+    mod_id       BIGINT NOT NULL,
+    paused_until BIGINT NULL,
+    PRIMARY KEY (game, channel, mod_id)
+);
@@ -0,0 +1,11 @@
+DROP TABLE notification_messages;
+
+CREATE TABLE settings_tmp (
+    guild BIGINT PRIMARY KEY NOT NULL,
+    game BIGINT NULL,
+    log_channel BIGINT NULL
+);
+
+INSERT INTO settings_tmp (guild, game, log_channel) SELECT guild, game, log_channel FROM settings;
+DROP TABLE settings;
+ALTER TABLE settings_tmp RENAME TO settings;
@@ -0,0 +1,10 @@
+CREATE TABLE notification_messages (
+    channel  BIGINT NOT NULL,
+    mod_id   BIGINT NOT NULL,
+    message  BIGINT NOT NULL,
+    versions TEXT NOT NULL,
+    created  BIGINT NOT NULL,
+    PRIMARY KEY (channel, mod_id)
+);
+
+ALTER TABLE settings ADD COLUMN edit_window BIGINT NULL;
@@ -0,0 +1,7 @@
+CREATE TABLE pending_file_changes (
+    game     BIGINT NOT NULL,
+    mod_id   BIGINT NOT NULL,
+    event    BIGINT NOT NULL,
+    uploaded BIGINT NOT NULL,
+    PRIMARY KEY (game, mod_id)
+);
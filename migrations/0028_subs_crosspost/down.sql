@@ -0,0 +1,30 @@
+CREATE TABLE subscriptions_tmp (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    game         BIGINT NOT NULL,
+    channel      BIGINT NOT NULL,
+    tags         TEXT NOT NULL DEFAULT "",
+    guild        BIGINT NOT NULL,
+    events       INTEGER NOT NULL DEFAULT 3,
+    explicit     BOOLEAN NOT NULL DEFAULT 1,
+    paused_until BIGINT NULL,
+    UNIQUE(game, channel, tags)
+);
+
+INSERT INTO subscriptions_tmp (id, game, channel, tags, guild, events, explicit, paused_until)
+    SELECT id, game, channel, tags, guild, events, explicit, paused_until FROM subscriptions;
+DROP TABLE subscriptions;
+ALTER TABLE subscriptions_tmp RENAME TO subscriptions;
+
+CREATE TABLE subscriptions_mods_tmp (
+    game         BIGINT NOT NULL,
+    channel      BIGINT NOT NULL,
+    guild        BIGINT NOT NULL,
+    mod_id       BIGINT NOT NULL,
+    paused_until BIGINT NULL,
+    PRIMARY KEY (game, channel, mod_id)
+);
+
+INSERT INTO subscriptions_mods_tmp (game, channel, guild, mod_id, paused_until)
+    SELECT game, channel, guild, mod_id, paused_until FROM subscriptions_mods;
+DROP TABLE subscriptions_mods;
+ALTER TABLE subscriptions_mods_tmp RENAME TO subscriptions_mods;
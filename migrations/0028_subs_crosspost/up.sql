@@ -0,0 +1,2 @@
+ALTER TABLE subscriptions ADD COLUMN crosspost BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE subscriptions_mods ADD COLUMN crosspost BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+CREATE TABLE notification_templates (
+    guild        BIGINT NOT NULL,
+    subscription INTEGER NOT NULL DEFAULT 0,
+    content      TEXT NULL,
+    title        TEXT NULL,
+    description  TEXT NULL,
+    PRIMARY KEY (guild, subscription)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE subscriptions_tmp (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    game         BIGINT NOT NULL,
+    channel      BIGINT NOT NULL,
+    tags         TEXT NOT NULL DEFAULT "",
+    guild        BIGINT NOT NULL,
+    events       INTEGER NOT NULL DEFAULT 3,
+    explicit     BOOLEAN NOT NULL DEFAULT 1,
+    paused_until BIGINT NULL,
+    crosspost    BOOLEAN NOT NULL DEFAULT 0,
+    UNIQUE(game, channel, tags)
+);
+
+INSERT INTO subscriptions_tmp (id, game, channel, tags, guild, events, explicit, paused_until, crosspost)
+    SELECT id, game, channel, tags, guild, events, explicit, paused_until, crosspost FROM subscriptions;
+DROP TABLE subscriptions;
+ALTER TABLE subscriptions_tmp RENAME TO subscriptions;
@@ -0,0 +1 @@
+ALTER TABLE subscriptions ADD COLUMN style INTEGER NOT NULL DEFAULT 0;
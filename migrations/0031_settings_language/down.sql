@@ -0,0 +1,10 @@
+CREATE TABLE settings_tmp (
+    guild BIGINT PRIMARY KEY NOT NULL,
+    game BIGINT NULL,
+    log_channel BIGINT NULL,
+    edit_window BIGINT NULL
+);
+
+INSERT INTO settings_tmp (guild, game, log_channel, edit_window) SELECT guild, game, log_channel, edit_window FROM settings;
+DROP TABLE settings;
+ALTER TABLE settings_tmp RENAME TO settings;
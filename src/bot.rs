@@ -14,14 +14,29 @@ use twilight_model::oauth::Application;
 use crate::commands;
 use crate::config::Config;
 use crate::db::types::GuildId;
-use crate::db::{DbPool, Settings, Subscriptions};
+use crate::db::{DbPool, Follows, Settings, Subscriptions};
 use crate::error::Error;
 use crate::metrics::Metrics;
 
+/// The events handled by the bot and the events keeping the channels and roles in the cache up
+/// to date for the permission checks.
+///
+/// Member updates require the privileged `GUILD_MEMBERS` intent, the bot's own member is fetched
+/// before its permissions are checked instead.
 pub const EVENTS: EventTypeFlags = EventTypeFlags::from_bits_retain(
     EventTypeFlags::READY.bits()
         | EventTypeFlags::GUILD_CREATE.bits()
+        | EventTypeFlags::GUILD_UPDATE.bits()
         | EventTypeFlags::GUILD_DELETE.bits()
+        | EventTypeFlags::CHANNEL_CREATE.bits()
+        | EventTypeFlags::CHANNEL_UPDATE.bits()
+        | EventTypeFlags::CHANNEL_DELETE.bits()
+        | EventTypeFlags::THREAD_CREATE.bits()
+        | EventTypeFlags::THREAD_UPDATE.bits()
+        | EventTypeFlags::THREAD_DELETE.bits()
+        | EventTypeFlags::ROLE_CREATE.bits()
+        | EventTypeFlags::ROLE_UPDATE.bits()
+        | EventTypeFlags::ROLE_DELETE.bits()
         | EventTypeFlags::INTERACTION_CREATE.bits(),
 );
 
@@ -34,6 +49,7 @@ pub struct Context {
     pub pool: DbPool,
     pub settings: Settings,
     pub subscriptions: Subscriptions,
+    pub follows: Follows,
     pub metrics: Metrics,
 }
 
@@ -77,7 +93,13 @@ pub async fn initialize(
         .collect::<Vec<_>>();
 
     let cache = InMemoryCache::builder()
-        .resource_types(ResourceType::USER_CURRENT)
+        .resource_types(
+            ResourceType::USER_CURRENT
+                | ResourceType::GUILD
+                | ResourceType::CHANNEL
+                | ResourceType::ROLE
+                | ResourceType::MEMBER,
+        )
         .build();
 
     let ctx = Context {
@@ -87,7 +109,8 @@ pub async fn initialize(
         modio,
         pool: pool.clone(),
         settings: Settings { pool: pool.clone() },
-        subscriptions: Subscriptions { pool },
+        subscriptions: Subscriptions { pool: pool.clone() },
+        follows: Follows { pool },
         metrics,
     };
 
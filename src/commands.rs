@@ -16,28 +16,35 @@ use twilight_util::builder::embed::EmbedBuilder;
 use twilight_util::builder::InteractionResponseDataBuilder;
 
 use crate::bot::Context;
-use crate::db::autocomplete::{games_by_name, games_by_name_id};
+use crate::db::autocomplete::{games_by_name, games_by_name_id, guild_games_by_name};
 use crate::db::types::{ChannelId, GuildId};
 use crate::error::Error;
+use crate::i18n;
 
 mod basic;
+mod follow;
 mod game;
 mod help;
 pub mod mods;
-mod subs;
+mod search;
+pub mod subs;
 
 fn commands() -> Vec<Command> {
     let mut cmds = Vec::new();
     cmds.extend(help::commands());
     cmds.extend(basic::commands());
+    cmds.extend(follow::commands());
     cmds.extend(game::commands());
     cmds.extend(mods::commands());
+    cmds.extend(search::commands());
     cmds.extend(subs::commands());
     cmds
 }
 
 pub async fn register(client: &InteractionClient<'_>) -> Result<(), Error> {
-    client.set_global_commands(&commands()).await?;
+    let mut commands = commands();
+    i18n::localize_commands(&mut commands);
+    client.set_global_commands(&commands).await?;
     Ok(())
 }
 
@@ -49,10 +56,14 @@ pub async fn handle_command(ctx: &Context, interaction: &Interaction, command: &
         "help" => help::help(ctx, interaction, command).await,
         "settings" => basic::settings(ctx, interaction, command).await,
         "games" => game::games(ctx, interaction, command).await,
-        "game" => game::game(ctx, interaction).await,
+        "game" => game::game(ctx, interaction, command).await,
         "mods" => mods::list(ctx, interaction, command).await,
+        "mod" => mods::mod_(ctx, interaction, command).await,
         "popular" => mods::popular(ctx, interaction, command).await,
+        "random" => mods::random(ctx, interaction, command).await,
+        "search" => search::search(ctx, interaction, command).await,
         "subs" => subs::handle_command(ctx, interaction, command).await,
+        "follow" | "unfollow" => follow::follow(ctx, interaction, command).await,
         _ => Ok(()),
     };
     if let Err(e) = res {
@@ -65,10 +76,19 @@ pub async fn handle_component(
     interaction: &Interaction,
     component: &MessageComponentInteractionData,
 ) {
-    if component.custom_id.starts_with("mods:") {
-        if let Err(e) = mods::list_component(ctx, interaction, component).await {
-            tracing::error!("{e}");
-        }
+    let res = if component.custom_id.starts_with("mods:") {
+        mods::list_component(ctx, interaction, component).await
+    } else if component.custom_id.starts_with("random:") {
+        mods::random_component(ctx, interaction, component).await
+    } else if component.custom_id.starts_with("search:") {
+        search::search_component(ctx, interaction, component).await
+    } else if component.custom_id.starts_with(subs::setup::PREFIX) {
+        subs::setup::setup_component(ctx, interaction, component).await
+    } else {
+        Ok(())
+    };
+    if let Err(e) = res {
+        tracing::error!("{e}");
     }
 }
 
@@ -286,10 +306,28 @@ async fn autocomplete_games(
     interaction: &Interaction,
     value: &str,
 ) -> Result<(), Error> {
-    let games = value.strip_prefix('@').map_or_else(
+    // Rank the games of the guild first.
+    let mut games = match interaction.guild_id() {
+        Some(guild_id) => guild_games_by_name(&ctx.pool, guild_id, value)?
+            .into_iter()
+            .map(|(id, name, alias)| match alias {
+                Some(alias) => (id, format!("{name} ({alias})")),
+                None => (id, name),
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let others = value.strip_prefix('@').map_or_else(
         || games_by_name(&ctx.pool, value),
         |value| games_by_name_id(&ctx.pool, value),
     )?;
+    for (id, name) in others {
+        if !games.iter().any(|(game_id, _)| *game_id == id) {
+            games.push((id, name));
+        }
+    }
+    games.truncate(25);
 
     let choices = games.into_iter().map(|(id, name)| CommandOptionChoice {
         name,
@@ -309,9 +347,19 @@ async fn autocomplete_games(
     Ok(())
 }
 
-async fn search_game(ctx: &Context, search: &str) -> Result<Option<Game>, Error> {
+async fn search_game(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    search: &str,
+) -> Result<Option<Game>, Error> {
     use crate::util::IntoFilter;
 
+    if let Some(guild_id) = guild_id {
+        if let Some(game_id) = ctx.settings.game_by_alias(guild_id, search)? {
+            return Ok(Some(ctx.modio.game(game_id.0).get().await?));
+        }
+    }
+
     let filter = search.into_filter();
     let game = ctx.modio.games().search(filter).first().await?;
     Ok(game)
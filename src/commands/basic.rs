@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::fmt::Write;
 
 use modio::types::games::ApiAccessOptions;
 use twilight_model::application::command::{Command, CommandType};
@@ -6,19 +7,31 @@ use twilight_model::application::interaction::application_command::{
     CommandData, CommandDataOption, CommandOptionValue,
 };
 use twilight_model::application::interaction::{Interaction, InteractionContextType};
+use twilight_model::channel::ChannelType;
 use twilight_model::guild::Permissions;
-use twilight_util::builder::command::{CommandBuilder, StringBuilder, SubCommandBuilder};
+use twilight_util::builder::command::{
+    ChannelBuilder, CommandBuilder, RoleBuilder, StringBuilder, SubCommandBuilder,
+    SubCommandGroupBuilder,
+};
 use twilight_util::builder::embed::{
     EmbedAuthorBuilder, EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder,
 };
 use twilight_util::builder::InteractionResponseDataBuilder;
 
-use super::{create_response, defer_ephemeral, update_response_content};
+use super::{
+    create_response, defer_ephemeral, search_game, update_response_content, AutocompleteExt,
+    InteractionExt, SubCommandExt,
+};
 use crate::bot::Context;
 use crate::commands::autocomplete_games;
-use crate::db::types::{GameId, GuildId};
+use crate::db::types::{ChannelId, GameId, RoleId};
+use crate::db::MAX_EDIT_WINDOW;
 use crate::error::Error;
-use crate::util::IntoFilter;
+use crate::i18n::{Locale, LOCALES};
+use crate::util;
+
+const THIRD_PARTY_DISABLED: &str =
+    ":no_entry: Third party API access is disabled for '{game}' but is required for the commands.";
 
 pub fn commands() -> Vec<Command> {
     vec![
@@ -38,6 +51,72 @@ pub fn commands() -> Vec<Command> {
                         .autocomplete(true),
                 ),
         )
+        .option(
+            SubCommandBuilder::new(
+                "channel-game",
+                "Set the default game of this channel. Omit the game to use the server default.",
+            )
+            .option(StringBuilder::new("value", "ID or search").autocomplete(true)),
+        )
+        .option(
+            SubCommandGroupBuilder::new("games", "Manage the known games of the server.")
+                .subcommands([
+                    SubCommandBuilder::new("list", "List the known games and their aliases."),
+                    SubCommandBuilder::new("add", "Add a known game with an optional alias.")
+                        .option(
+                            StringBuilder::new("game", "ID or search")
+                                .required(true)
+                                .autocomplete(true),
+                        )
+                        .option(
+                            StringBuilder::new("alias", "Short name for the game").max_length(32),
+                        ),
+                    SubCommandBuilder::new("rm", "Remove a known game.").option(
+                        StringBuilder::new("game", "ID, alias or search")
+                            .required(true)
+                            .autocomplete(true),
+                    ),
+                ]),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "log-channel",
+                "Set the channel where changes of subscriptions are announced.",
+            )
+            .option(
+                ChannelBuilder::new("channel", "Omit the channel to disable the announcements.")
+                    .channel_types([ChannelType::GuildText]),
+            ),
+        )
+        .option(
+            SubCommandBuilder::new(
+                "edit-window",
+                "Edit the previous notification of a mod for updates within the time window.",
+            )
+            .option(StringBuilder::new(
+                "duration",
+                "e.g. `30m`, `2h` or `1d`. Omit the duration to always send new notifications.",
+            )),
+        )
+        .option(
+            SubCommandBuilder::new("language", "Set the language of the notifications.").option(
+                StringBuilder::new("language", "Omit the language to use English.")
+                    .choices(LOCALES.map(|locale| (locale.name(), locale.code()))),
+            ),
+        )
+        .option(
+            SubCommandGroupBuilder::new(
+                "permissions",
+                "Manage the roles allowed to manage subscriptions.",
+            )
+            .subcommands([
+                SubCommandBuilder::new("list", "List the roles allowed to manage subscriptions."),
+                SubCommandBuilder::new("grant", "Allow a role to manage subscriptions.")
+                    .option(RoleBuilder::new("role", "Role").required(true)),
+                SubCommandBuilder::new("revoke", "Disallow a role to manage subscriptions.")
+                    .option(RoleBuilder::new("role", "Role").required(true)),
+            ]),
+        )
         .build(),
     ]
 }
@@ -107,48 +186,404 @@ pub async fn settings(
     interaction: &Interaction,
     command: &CommandData,
 ) -> Result<(), Error> {
-    let filter = match command.options.as_slice() {
-        [CommandDataOption {
-            value: CommandOptionValue::SubCommand(commands),
-            ..
-        }] => match commands.as_slice() {
-            [CommandDataOption {
-                value: CommandOptionValue::Focused(value, _),
-                ..
-            }] => {
-                return autocomplete_games(ctx, interaction, value).await;
-            }
-            [CommandDataOption {
-                value: CommandOptionValue::String(s),
-                ..
-            }] => s.into_filter(),
-            _ => unreachable!(),
-        },
-
-        _ => unreachable!(),
-    };
+    if let Some((_, value)) = command.autocomplete() {
+        return autocomplete_games(ctx, interaction, value).await;
+    }
+
+    match command.subcommand() {
+        Some(("default-game", opts)) => default_game(ctx, interaction, opts).await,
+        Some(("channel-game", opts)) => channel_game(ctx, interaction, opts).await,
+        Some(("games", opts)) => games(ctx, interaction, opts).await,
+        Some(("log-channel", opts)) => log_channel(ctx, interaction, opts).await,
+        Some(("edit-window", opts)) => edit_window(ctx, interaction, opts).await,
+        Some(("language", opts)) => language(ctx, interaction, opts).await,
+        Some(("permissions", opts)) => permissions(ctx, interaction, opts).await,
+        _ => Ok(()),
+    }
+}
+
+/// `/settings default-game <game>`
+async fn default_game(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let search = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) => Some(s),
+        _ => None,
+    });
+    let search = search.expect("required option");
 
     defer_ephemeral(ctx, interaction).await?;
 
-    let game = ctx.modio.games().search(filter).first().await?;
-    let guild_id = interaction.guild_id.expect("guild only command");
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let game = search_game(ctx, Some(guild_id), search).await?;
+    let locale = Locale::from_interaction(interaction);
 
     let content: Cow<'_, str> = if let Some(game) = game {
+        let args = [("game", game.name.as_str())];
         if game
             .api_access_options
             .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
         {
-            ctx.settings.set_game(GuildId(guild_id), GameId(game.id))?;
-            format!("Game is set to '{}'.", game.name).into()
+            ctx.settings.set_game(guild_id, GameId(game.id))?;
+            locale.format("Game is set to '{game}'.", &args).into()
         } else {
-            let msg = format!(
-                ":no_entry: Third party API access is disabled for '{}' but is required for the commands.",
-                game.name
-            );
-            msg.into()
+            locale.format(THIRD_PARTY_DISABLED, &args).into()
         }
     } else {
-        "Game not found.".into()
+        locale.tr("Game not found.").into()
+    };
+
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings channel-game [game]`
+async fn channel_game(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let search = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) => Some(s),
+        _ => None,
+    });
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let channel_id = interaction.channel_id().expect("guild only command");
+    let locale = Locale::from_interaction(interaction);
+
+    let Some(search) = search else {
+        ctx.settings.set_channel_game(guild_id, channel_id, None)?;
+        let content = locale.tr("The channel uses the default game of the server.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let content: Cow<'_, str> = match search_game(ctx, Some(guild_id), search).await? {
+        Some(game)
+            if game
+                .api_access_options
+                .contains(ApiAccessOptions::ALLOW_THIRD_PARTY) =>
+        {
+            ctx.settings
+                .set_channel_game(guild_id, channel_id, Some(GameId(game.id)))?;
+            let args = [("game", game.name.as_str())];
+            locale
+                .format("Game of the channel is set to '{game}'.", &args)
+                .into()
+        }
+        Some(game) => locale
+            .format(THIRD_PARTY_DISABLED, &[("game", &game.name)])
+            .into(),
+        None => locale.tr("Game not found.").into(),
+    };
+
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings games`
+async fn games(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    match opts.subcommand() {
+        Some(("list", _)) => games_list(ctx, interaction).await,
+        Some(("add", opts)) => games_add(ctx, interaction, opts).await,
+        Some(("rm", opts)) => games_remove(ctx, interaction, opts).await,
+        _ => Ok(()),
+    }
+}
+
+/// `/settings games list`
+async fn games_list(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let games = ctx.settings.games(guild_id)?;
+    let locale = Locale::from_interaction(interaction);
+
+    if games.is_empty() {
+        let content = locale.tr("No games added.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let mut content = String::new();
+    for (game_id, alias, name) in games {
+        _ = write!(&mut content, "`{game_id}.` ");
+        content.push_str(name.as_deref().unwrap_or(locale.tr("Unknown game")));
+        if let Some(alias) = alias {
+            _ = write!(&mut content, " | {}: `{alias}`", locale.tr("Alias"));
+        }
+        content.push('\n');
+    }
+
+    let embed = EmbedBuilder::new()
+        .title(locale.tr("Games"))
+        .description(content)
+        .build();
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+/// `/settings games add <game> [alias]`
+async fn games_add(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let locale = Locale::from_interaction(interaction);
+
+    let mut game = None;
+    let mut alias = None;
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => {
+                game = search_game(ctx, Some(guild_id), s).await?;
+
+                if game.is_none() {
+                    let content = locale.tr("Game not found.");
+                    return update_response_content(ctx, interaction, content).await;
+                }
+            }
+            CommandOptionValue::String(s) if opt.name == "alias" => {
+                alias = Some(s.trim().to_lowercase());
+            }
+            _ => {}
+        }
+    }
+    let game = game.expect("required option");
+
+    if !game
+        .api_access_options
+        .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
+    {
+        let content = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    if let Some(alias) = &alias {
+        let args = [("alias", alias.as_str())];
+        // Aliases must not be confused with game ids or name ids.
+        if alias.is_empty() || alias.starts_with('@') || alias.parse::<u64>().is_ok() {
+            let content = locale.format("Invalid alias '{alias}'.", &args);
+            return update_response_content(ctx, interaction, &content).await;
+        }
+        if let Some(other) = ctx.settings.game_by_alias(guild_id, alias)? {
+            if other.0 != game.id {
+                let content = locale.format(
+                    "The alias '{alias}' is already used for another game.",
+                    &args,
+                );
+                return update_response_content(ctx, interaction, &content).await;
+            }
+        }
+    }
+
+    ctx.settings
+        .add_game(guild_id, GameId(game.id), alias.as_deref())?;
+
+    let content = match alias {
+        Some(alias) => locale.format(
+            "Added '{game}' with the alias '{alias}'.",
+            &[("game", &game.name), ("alias", &alias)],
+        ),
+        None => locale.format("Added '{game}'.", &[("game", &game.name)]),
+    };
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings games rm <game>`
+async fn games_remove(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let guild_id = interaction.guild_id().expect("guild only command");
+
+    let search = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) if opt.name == "game" => Some(s),
+        _ => None,
+    });
+    let search = search.expect("required option");
+    let locale = Locale::from_interaction(interaction);
+
+    let Some(game) = search_game(ctx, Some(guild_id), search).await? else {
+        let content = locale.tr("Game not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let args = [("game", game.name.as_str())];
+    let content = if ctx.settings.remove_game(guild_id, GameId(game.id))? {
+        locale.format("Removed '{game}'.", &args)
+    } else {
+        locale.format("'{game}' is not a known game of this server.", &args)
+    };
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings log-channel [channel]`
+async fn log_channel(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let channel_id = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::Channel(id) => Some(ChannelId(*id)),
+        _ => None,
+    });
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().expect("guild only command");
+    ctx.settings.set_log_channel(guild_id, channel_id)?;
+
+    let locale = Locale::from_interaction(interaction);
+    let content = match channel_id {
+        Some(channel_id) => locale.format(
+            "Changes of subscriptions are announced in {channel}.",
+            &[("channel", &format!("<#{channel_id}>"))],
+        ),
+        None => locale
+            .tr("Changes of subscriptions are no longer announced.")
+            .to_owned(),
+    };
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings edit-window [duration]`
+async fn edit_window(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let duration = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) => Some(s.trim()),
+        _ => None,
+    });
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let locale = Locale::from_interaction(interaction);
+
+    let Some(duration) = duration else {
+        ctx.settings.set_edit_window(guild_id, None)?;
+        let content = locale.tr("Every mod update is sent as a new notification.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    let args = [("duration", duration)];
+
+    let seconds = util::parse_duration(duration)
+        .and_then(|d| i64::try_from(d.as_secs()).ok())
+        .filter(|secs| (1..=MAX_EDIT_WINDOW).contains(secs));
+    let Some(seconds) = seconds else {
+        let content = locale.format(
+            "Invalid duration `{duration}`, use e.g. `30m`, `2h` or `1d` up to one week.",
+            &args,
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    };
+
+    ctx.settings.set_edit_window(guild_id, Some(seconds))?;
+
+    let content = locale.format(
+        "Mod updates within `{duration}` edit the previous notification of the mod instead of sending a new one.",
+        &args,
+    );
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings language [language]`
+async fn language(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let language = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) => Locale::from_code(s),
+        _ => None,
+    });
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().expect("guild only command");
+    ctx.settings.set_language(guild_id, language)?;
+
+    let locale = Locale::from_interaction(interaction);
+    let language = language.unwrap_or_default();
+    let content = locale.format(
+        "Notifications are sent in {language}.",
+        &[("language", language.name())],
+    );
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/settings permissions`
+async fn permissions(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().expect("guild only command");
+    let locale = Locale::from_interaction(interaction);
+
+    let role = |opts: &[CommandDataOption]| {
+        opts.iter().find_map(|opt| match &opt.value {
+            CommandOptionValue::Role(id) => Some(RoleId(*id)),
+            _ => None,
+        })
+    };
+
+    let content = match opts.subcommand() {
+        Some(("list", _)) => {
+            let roles = ctx.settings.roles(guild_id)?;
+            if roles.is_empty() {
+                locale
+                    .tr("Only members with the `Administrator` or `Manage Channels` permission can manage subscriptions.")
+                    .to_owned()
+            } else {
+                let mut content = locale
+                    .tr("Roles allowed to manage subscriptions:")
+                    .to_owned();
+                content.push('\n');
+                for role in roles {
+                    _ = writeln!(&mut content, "<@&{role}>");
+                }
+                content
+            }
+        }
+        Some(("grant", opts)) => {
+            let role = role(opts).expect("required option");
+            ctx.settings.grant_role(guild_id, role)?;
+            let mention = format!("<@&{role}>");
+            let args = [("role", mention.as_str())];
+            locale.format("{role} is allowed to manage subscriptions.", &args)
+        }
+        Some(("revoke", opts)) => {
+            let role = role(opts).expect("required option");
+            let mention = format!("<@&{role}>");
+            let args = [("role", mention.as_str())];
+            if ctx.settings.revoke_role(guild_id, role)? {
+                locale.format(
+                    "{role} is no longer allowed to manage subscriptions.",
+                    &args,
+                )
+            } else {
+                locale.format("{role} was not allowed to manage subscriptions.", &args)
+            }
+        }
+        _ => return Ok(()),
     };
 
     update_response_content(ctx, interaction, &content).await
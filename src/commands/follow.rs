@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use modio::filter::prelude::*;
+use modio::types::games::ApiAccessOptions;
+use twilight_model::application::command::{Command, CommandType};
+use twilight_model::application::interaction::application_command::{
+    CommandData, CommandDataOption, CommandOptionValue,
+};
+use twilight_model::application::interaction::{Interaction, InteractionContextType};
+use twilight_util::builder::command::{CommandBuilder, StringBuilder, SubCommandBuilder};
+
+use super::{
+    autocomplete_games, defer_ephemeral, search_game, update_response_content,
+    update_response_from_content, AutocompleteExt, InteractionExt, SubCommandExt,
+};
+use crate::bot::Context;
+use crate::db::types::{GameId, ModId, UserId};
+use crate::db::Follow;
+use crate::error::Error;
+use crate::i18n::Locale;
+use crate::util::{ContentBuilder, IntoFilter};
+
+pub fn commands() -> Vec<Command> {
+    let game = || {
+        StringBuilder::new("game", "ID or search")
+            .required(true)
+            .autocomplete(true)
+    };
+    let subcommands = |verb: &str| {
+        [
+            SubCommandBuilder::new("game", format!("{verb} new and updated mods of a game."))
+                .option(game()),
+            SubCommandBuilder::new("mod", format!("{verb} the updates of a mod."))
+                .option(game())
+                .option(StringBuilder::new("mod", "ID or search").required(true)),
+            SubCommandBuilder::new("user", format!("{verb} the mods of a user."))
+                .option(game())
+                .option(StringBuilder::new("name", "Username").required(true)),
+        ]
+    };
+
+    let mut follow = CommandBuilder::new(
+        "follow",
+        "Get notified by direct message about mod updates.",
+        CommandType::ChatInput,
+    )
+    .contexts([InteractionContextType::Guild, InteractionContextType::BotDm]);
+    for cmd in subcommands("Follow") {
+        follow = follow.option(cmd);
+    }
+    follow = follow.option(SubCommandBuilder::new(
+        "list",
+        "List the games, mods and users you follow.",
+    ));
+
+    let mut unfollow = CommandBuilder::new(
+        "unfollow",
+        "Stop the direct message notifications about mod updates.",
+        CommandType::ChatInput,
+    )
+    .contexts([InteractionContextType::Guild, InteractionContextType::BotDm]);
+    for cmd in subcommands("Unfollow") {
+        unfollow = unfollow.option(cmd);
+    }
+
+    vec![follow.build(), unfollow.build()]
+}
+
+pub async fn follow(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+) -> Result<(), Error> {
+    if let Some(("game", value)) = command.autocomplete() {
+        return autocomplete_games(ctx, interaction, value).await;
+    }
+
+    let add = command.name == "follow";
+    match command.subcommand() {
+        Some(("list", _)) if add => list(ctx, interaction).await,
+        Some((kind, opts)) => change(ctx, interaction, kind, opts, add).await,
+        None => Ok(()),
+    }
+}
+
+/// `/follow list`
+async fn list(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let user_id = author(interaction);
+    let locale = Locale::from_interaction(interaction);
+    let follows = ctx.follows.list(user_id)?;
+    if follows.is_empty() {
+        let content = locale.tr("You don't follow any games, mods or users.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let mut grouped = BTreeMap::<GameId, Vec<(Follow, bool)>>::new();
+    for (game_id, follow, disabled) in follows {
+        grouped.entry(game_id).or_default().push((follow, disabled));
+    }
+
+    let filter = Id::_in(grouped.keys().map(|g| g.0).collect::<Vec<_>>());
+    let games = ctx.modio.games().search(filter).collect().await?;
+    let games = games
+        .into_iter()
+        .map(|g| (g.id, g.name))
+        .collect::<HashMap<_, _>>();
+
+    let mut disabled_follows = false;
+    let mut content = ContentBuilder::new(4000);
+    for (game_id, follows) in grouped {
+        let mod_ids = follows
+            .iter()
+            .filter_map(|(f, _)| match f {
+                Follow::Mod(id) => Some(id.0),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mods = if mod_ids.is_empty() {
+            HashMap::new()
+        } else {
+            let filter = Id::_in(mod_ids);
+            let mods = ctx.modio.game(*game_id).mods().search(filter).collect();
+            mods.await?
+                .into_iter()
+                .map(|m| (m.id, m.name))
+                .collect::<HashMap<_, _>>()
+        };
+
+        match games.get(&game_id.0) {
+            Some(name) => _ = writeln!(&mut content, "**{name}**"),
+            None => _ = writeln!(&mut content, "**{game_id}**"),
+        }
+        for (follow, disabled) in follows {
+            let suffix = if disabled {
+                locale.tr(" (disabled)")
+            } else {
+                ""
+            };
+            disabled_follows |= disabled;
+
+            let _ = match follow {
+                Follow::Game => {
+                    let label = locale.tr("New and updated mods");
+                    writeln!(&mut content, "{label}{suffix}")
+                }
+                Follow::Mod(id) => match mods.get(&id.0) {
+                    Some(name) => writeln!(&mut content, "Mod: `{id}.` {name}{suffix}"),
+                    None => writeln!(&mut content, "Mod: `{id}`{suffix}"),
+                },
+                Follow::User(name) => {
+                    let label = locale.tr("User");
+                    writeln!(&mut content, "{label}: {name}{suffix}")
+                }
+            };
+        }
+        _ = writeln!(&mut content);
+    }
+    if disabled_follows {
+        _ = writeln!(
+            &mut content,
+            "{}",
+            locale.tr(
+                "Direct messages to you failed, follow something again to enable the notifications."
+            )
+        );
+    }
+
+    let title = locale.tr("Follows");
+    update_response_from_content(ctx, interaction, title, &content.content).await
+}
+
+/// `/follow game|mod|user` and `/unfollow game|mod|user`
+async fn change(
+    ctx: &Context,
+    interaction: &Interaction,
+    kind: &str,
+    opts: &[CommandDataOption],
+    add: bool,
+) -> Result<(), Error> {
+    let mut game = None;
+    let mut mod_filter = None;
+    let mut name = None;
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => game = Some(s),
+            CommandOptionValue::String(s) if opt.name == "mod" => {
+                mod_filter = Some(s.into_filter());
+            }
+            CommandOptionValue::String(s) if opt.name == "name" => name = Some(s.trim()),
+            _ => {}
+        }
+    }
+
+    defer_ephemeral(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    let game = game.expect("required option");
+    let Some(game) = search_game(ctx, interaction.guild_id(), game).await? else {
+        let content = locale.tr("Game not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    if add
+        && !game
+            .api_access_options
+            .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
+    {
+        let content = locale.format(
+            ":no_entry: Third party API access is disabled for '{game}' but is required for notifications.",
+            &[("game", &game.name)],
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let (follow, label) = match kind {
+        "game" => (Follow::Game, format!("'{}'", game.name)),
+        "mod" => {
+            let filter = mod_filter.expect("required option");
+            let mods = ctx.modio.game(game.id).mods();
+            let Some(mod_) = mods.search(filter).first().await? else {
+                let content = locale.tr("Mod not found.");
+                return update_response_content(ctx, interaction, content).await;
+            };
+            (Follow::Mod(ModId(mod_.id)), format!("'{}'", mod_.name))
+        }
+        "user" => {
+            let name = name.expect("required option");
+            let label = locale.format(
+                "the mods of '{user}' for '{game}'",
+                &[("user", name), ("game", &game.name)],
+            );
+            (Follow::User(name.to_owned()), label)
+        }
+        _ => return Ok(()),
+    };
+
+    let user_id = author(interaction);
+    let game_id = GameId(game.id);
+    let args = [("target", label.as_str())];
+    let content = if add {
+        if ctx.follows.follow(user_id, game_id, &follow)? {
+            locale.format(
+                "You are now following {target}. Notifications are sent as direct messages.",
+                &args,
+            )
+        } else {
+            locale.format("You are already following {target}.", &args)
+        }
+    } else if ctx.follows.unfollow(user_id, game_id, &follow)? {
+        locale.format("You are no longer following {target}.", &args)
+    } else {
+        locale.format("You are not following {target}.", &args)
+    };
+    update_response_content(ctx, interaction, &content).await
+}
+
+fn author(interaction: &Interaction) -> UserId {
+    UserId(interaction.author_id().expect("interaction has an author"))
+}
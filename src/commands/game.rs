@@ -10,16 +10,24 @@ use twilight_model::application::interaction::application_command::{
 use twilight_model::application::interaction::{Interaction, InteractionContextType};
 use twilight_model::channel::message::embed::EmbedField;
 use twilight_model::channel::message::Embed;
-use twilight_util::builder::command::{CommandBuilder, StringBuilder};
-use twilight_util::builder::embed::{EmbedBuilder, ImageSource};
+use twilight_util::builder::command::{
+    CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+};
+use twilight_util::builder::embed::{EmbedAuthorBuilder, EmbedBuilder, ImageSource};
 
 use super::{
-    autocomplete_games, create_response, defer_response, update_response_content,
-    update_response_from_content, AutocompleteExt, EphemeralMessage, InteractionExt,
+    autocomplete_games, create_response, defer_response, search_game, update_response_content,
+    update_response_from_content, AutocompleteExt, EphemeralMessage, InteractionExt, SubCommandExt,
 };
 use crate::bot::Context;
+use crate::db::stats::{game_stats, GameStats};
+use crate::db::types::GameId;
 use crate::error::Error;
-use crate::util::{ContentBuilder, IntoFilter};
+use crate::i18n::Locale;
+use crate::util::{self, ContentBuilder, IntoFilter};
+
+/// Maximum number of data points rendered in a sparkline.
+const SPARKLINE_WIDTH: usize = 30;
 
 pub fn commands() -> Vec<Command> {
     vec![
@@ -30,9 +38,28 @@ pub fn commands() -> Vec<Command> {
         )
         .option(StringBuilder::new("search", "ID or search").autocomplete(true))
         .build(),
-        CommandBuilder::new("game", "Display the default game.", CommandType::ChatInput)
-            .contexts([InteractionContextType::Guild])
-            .build(),
+        CommandBuilder::new(
+            "game",
+            "Display the default game or the statistics of a game.",
+            CommandType::ChatInput,
+        )
+        .contexts([InteractionContextType::Guild])
+        .option(SubCommandBuilder::new("info", "Display the default game."))
+        .option(
+            SubCommandBuilder::new("stats", "Show the growth of a game over time.")
+                .option(
+                    StringBuilder::new("game", "ID or search game instead of the default game.")
+                        .autocomplete(true),
+                )
+                .option(
+                    IntegerBuilder::new("period", "Time period of the statistics.").choices([
+                        ("7 days", 7),
+                        ("30 days", 30),
+                        ("90 days", 90),
+                    ]),
+                ),
+        )
+        .build(),
     ]
 }
 
@@ -55,24 +82,25 @@ pub async fn games(
 
     defer_response(ctx, interaction).await?;
 
+    let locale = Locale::from_interaction(interaction);
     let mut games = ctx.modio.games().search(filter).iter().await?.take(100);
 
     match games.size_hint() {
-        (0, _) => update_response_content(ctx, interaction, "No games found.").await,
+        (0, _) => update_response_content(ctx, interaction, locale.tr("No games found.")).await,
         (1, _) => {
             let game = games.try_next().await?;
             if let Some(game) = game {
-                let embed = create_embed(game);
+                let embed = create_embed(game, locale);
 
                 ctx.interaction()
                     .update_response(&interaction.token)
-                    .content(Some("Found 1 game."))
+                    .content(Some(locale.tr("Found 1 game.")))
                     .embeds(Some(&[embed]))
                     .await?;
 
                 Ok(())
             } else {
-                update_response_content(ctx, interaction, "No games found.").await
+                update_response_content(ctx, interaction, locale.tr("No games found.")).await
             }
         }
         _ => {
@@ -81,19 +109,41 @@ pub async fn games(
                 _ = writeln!(&mut buf, "`{}.` {}", game.id, game.name);
             }
 
-            update_response_from_content(ctx, interaction, "Games", &buf.content).await
+            let title = locale.tr("Games");
+            update_response_from_content(ctx, interaction, title, &buf.content).await
         }
     }
 }
 
-pub async fn game(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+pub async fn game(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+) -> Result<(), Error> {
+    if let Some(("game", value)) = command.autocomplete() {
+        return autocomplete_games(ctx, interaction, value).await;
+    }
+
+    match command.subcommand() {
+        Some(("info", _)) => info(ctx, interaction).await,
+        Some(("stats", opts)) => stats(ctx, interaction, opts).await,
+        _ => Ok(()),
+    }
+}
+
+/// `/game info`
+async fn info(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+    let locale = Locale::from_interaction(interaction);
     let game_id = match interaction.guild_id() {
-        Some(guild_id) => ctx.settings.game(guild_id)?.map(|id| id.0),
+        Some(guild_id) => ctx
+            .settings
+            .default_game(guild_id, interaction.channel_id())?
+            .map(|id| id.0),
         _ => None,
     };
 
     let Some(game_id) = game_id else {
-        let data = "Default game is not set.".into_ephemeral();
+        let data = locale.tr("Default game is not set.").into_ephemeral();
 
         return create_response(ctx, interaction, data).await;
     };
@@ -102,7 +152,7 @@ pub async fn game(ctx: &Context, interaction: &Interaction) -> Result<(), Error>
 
     let game = ctx.modio.game(game_id).get().await?;
 
-    let embed = create_embed(game);
+    let embed = create_embed(game, locale);
 
     ctx.interaction()
         .update_response(&interaction.token)
@@ -112,14 +162,134 @@ pub async fn game(ctx: &Context, interaction: &Interaction) -> Result<(), Error>
     Ok(())
 }
 
-fn create_embed(game: Game) -> Embed {
+/// `/game stats [game] [period]`
+async fn stats(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let mut game = None;
+    let mut days = 30;
+
+    defer_response(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => {
+                game = search_game(ctx, interaction.guild_id(), s).await?;
+
+                if game.is_none() {
+                    let content = locale.tr("Game not found.");
+                    return update_response_content(ctx, interaction, content).await;
+                }
+            }
+            CommandOptionValue::Integer(v) if opt.name == "period" => {
+                days = *v;
+            }
+            _ => {}
+        }
+    }
+
+    let game = match (game, interaction.guild_id()) {
+        (Some(game), _) => game,
+        (None, Some(guild_id)) => {
+            let Some(game_id) = ctx
+                .settings
+                .default_game(guild_id, interaction.channel_id())?
+            else {
+                let content = locale.tr("Default game is not set.");
+                return update_response_content(ctx, interaction, content).await;
+            };
+            ctx.modio.game(game_id.0).get().await?
+        }
+        (None, None) => {
+            let content = locale.tr("Default game is not set.");
+            return update_response_content(ctx, interaction, content).await;
+        }
+    };
+
+    #[allow(clippy::cast_possible_wrap)]
+    let since = util::current_timestamp() as i64 - days * 86400;
+    let history = game_stats(&ctx.pool, GameId(game.id), since)?;
+
+    let embed = create_stats_embed(&game, &history, days, locale);
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+fn create_stats_embed(game: &Game, history: &[GameStats], days: i64, locale: Locale) -> Embed {
+    let mut embed = EmbedBuilder::new().title(locale.tr("Statistics")).author(
+        EmbedAuthorBuilder::new(&game.name)
+            .url(game.profile_url.to_string())
+            .icon_url(ImageSource::url(game.icon.thumb_64x64.to_string()).unwrap()),
+    );
+
+    if history.len() < 2 {
+        embed = embed.description(locale.tr("Not enough statistics recorded yet."));
+        return embed.build();
+    }
+
+    let mods = history.iter().map(|s| s.mods).collect::<Vec<_>>();
+    let subscribers = history.iter().map(|s| s.subscribers).collect::<Vec<_>>();
+    let downloads = history.iter().map(|s| s.downloads).collect::<Vec<_>>();
+
+    embed
+        .description(locale.format(
+            "Growth over the last {days} days.",
+            &[("days", &days.to_string())],
+        ))
+        .field(growth_field(locale.tr("Mods"), &mods))
+        .field(growth_field(locale.tr("Subscribers"), &subscribers))
+        .field(growth_field(locale.tr("Downloads"), &downloads))
+        .build()
+}
+
+/// Creates a field with the current value, the growth and a sparkline of the values.
+pub fn growth_field(name: &str, values: &[i64]) -> EmbedField {
+    let (first, last) = match values {
+        [first, .., last] => (*first, *last),
+        [value] => (*value, *value),
+        [] => (0, 0),
+    };
+    let delta = last - first;
+    #[allow(clippy::cast_precision_loss)]
+    let percent = if first > 0 {
+        delta as f64 * 100.0 / first as f64
+    } else {
+        0.0
+    };
+
+    // Reduce the data points to the width of the sparkline.
+    let step = values.len().div_ceil(SPARKLINE_WIDTH).max(1);
+    let mut points = values.iter().copied().step_by(step).collect::<Vec<_>>();
+    if values.len().saturating_sub(1) % step != 0 {
+        points.push(last);
+    }
+
+    EmbedField {
+        name: name.to_owned(),
+        value: format!(
+            "**{last}** ({delta:+}, {percent:+.1}%)\n`{}`",
+            util::sparkline(&points)
+        ),
+        inline: false,
+    }
+}
+
+fn create_embed(game: Game, locale: Locale) -> Embed {
     let mut embed = EmbedBuilder::new()
         .title(game.name)
         .url(game.profile_url.to_string())
         .description(game.summary)
         .image(ImageSource::url(game.logo.thumb_640x360).unwrap())
         .field(EmbedField {
-            name: "Info".into(),
+            name: locale.tr("Info").into(),
             value: format!(
                 r"**Id:** {}
 **Name-Id:** {}
@@ -131,12 +301,15 @@ fn create_embed(game: Game) -> Embed {
 
     if let Some(stats) = game.stats {
         embed = embed.field(EmbedField {
-            name: "Stats".into(),
+            name: locale.tr("Stats").into(),
             value: format!(
-                r"**Mods:** {}
-**Subscribers:** {}
-**Downloads:** {}",
-                stats.mods_total, stats.subscribers_total, stats.downloads.total,
+                "**{}:** {}\n**{}:** {}\n**{}:** {}",
+                locale.tr("Mods"),
+                stats.mods_total,
+                locale.tr("Subscribers"),
+                stats.subscribers_total,
+                locale.tr("Downloads"),
+                stats.downloads.total,
             ),
             inline: true,
         });
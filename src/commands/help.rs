@@ -1,32 +1,152 @@
-use twilight_model::application::command::{Command, CommandType};
+use twilight_model::application::command::{
+    Command, CommandOptionChoice, CommandOptionChoiceValue, CommandType,
+};
 use twilight_model::application::interaction::application_command::{
     CommandData, CommandOptionValue,
 };
 use twilight_model::application::interaction::Interaction;
+use twilight_model::http::interaction::{InteractionResponse, InteractionResponseType};
 use twilight_util::builder::command::{CommandBuilder, StringBuilder};
 use twilight_util::builder::embed::EmbedBuilder;
+use twilight_util::builder::InteractionResponseDataBuilder;
 
-use super::create_response;
+use super::{create_response, AutocompleteExt};
 use crate::bot::Context;
 use crate::commands::EphemeralMessage;
 use crate::error::Error;
+use crate::i18n::Locale;
 
 const HELP_ABOUT: (&str, &str) = ("**Command: /about**", include_str!("help/about.md"));
-const HELP_GAME: (&str, &str) = ("**Command: /game**", include_str!("help/game.md"));
+const HELP_FOLLOW_GAME: (&str, &str) = (
+    "**Command: /follow game**",
+    include_str!("help/follow-game.md"),
+);
+const HELP_FOLLOW_MOD: (&str, &str) = (
+    "**Command: /follow mod**",
+    include_str!("help/follow-mod.md"),
+);
+const HELP_FOLLOW_USER: (&str, &str) = (
+    "**Command: /follow user**",
+    include_str!("help/follow-user.md"),
+);
+const HELP_FOLLOW_LIST: (&str, &str) = (
+    "**Command: /follow list**",
+    include_str!("help/follow-list.md"),
+);
+const HELP_GAME: (&str, &str) = ("**Command: /game info**", include_str!("help/game.md"));
+const HELP_GAME_STATS: (&str, &str) = (
+    "**Command: /game stats**",
+    include_str!("help/game-stats.md"),
+);
 const HELP_GAMES: (&str, &str) = ("**Command: /games**", include_str!("help/games.md"));
 const HELP_MODS: (&str, &str) = ("**Command: /mods**", include_str!("help/mods.md"));
+const HELP_MOD_STATS: (&str, &str) = ("**Command: /mod stats**", include_str!("help/mod-stats.md"));
 const HELP_POPULAR: (&str, &str) = ("**Command: /popular**", include_str!("help/popular.md"));
+const HELP_RANDOM: (&str, &str) = ("**Command: /random**", include_str!("help/random.md"));
+const HELP_SEARCH: (&str, &str) = ("**Command: /search**", include_str!("help/search.md"));
 const HELP_SETTINGS_DEFAULT_GAME: (&str, &str) = (
     "**Command: /settings default-game**",
     include_str!("help/settings-default-game.md"),
 );
+const HELP_SETTINGS_CHANNEL_GAME: (&str, &str) = (
+    "**Command: /settings channel-game**",
+    include_str!("help/settings-channel-game.md"),
+);
+const HELP_SETTINGS_GAMES_LIST: (&str, &str) = (
+    "**Command: /settings games list**",
+    include_str!("help/settings-games-list.md"),
+);
+const HELP_SETTINGS_GAMES_ADD: (&str, &str) = (
+    "**Command: /settings games add**",
+    include_str!("help/settings-games-add.md"),
+);
+const HELP_SETTINGS_GAMES_RM: (&str, &str) = (
+    "**Command: /settings games rm**",
+    include_str!("help/settings-games-rm.md"),
+);
+const HELP_SETTINGS_LOG_CHANNEL: (&str, &str) = (
+    "**Command: /settings log-channel**",
+    include_str!("help/settings-log-channel.md"),
+);
+const HELP_SETTINGS_EDIT_WINDOW: (&str, &str) = (
+    "**Command: /settings edit-window**",
+    include_str!("help/settings-edit-window.md"),
+);
+const HELP_SETTINGS_LANGUAGE: (&str, &str) = (
+    "**Command: /settings language**",
+    include_str!("help/settings-language.md"),
+);
+const HELP_SETTINGS_PERMISSIONS_LIST: (&str, &str) = (
+    "**Command: /settings permissions list**",
+    include_str!("help/settings-permissions-list.md"),
+);
+const HELP_SETTINGS_PERMISSIONS_GRANT: (&str, &str) = (
+    "**Command: /settings permissions grant**",
+    include_str!("help/settings-permissions-grant.md"),
+);
+const HELP_SETTINGS_PERMISSIONS_REVOKE: (&str, &str) = (
+    "**Command: /settings permissions revoke**",
+    include_str!("help/settings-permissions-revoke.md"),
+);
 const HELP_SUBS_OVERVIEW: (&str, &str) = (
     "**Command: /subs overview**",
     include_str!("help/subs-overview.md"),
 );
 const HELP_SUBS_LIST: (&str, &str) = ("**Command: /subs list**", include_str!("help/subs-list.md"));
 const HELP_SUBS_ADD: (&str, &str) = ("**Command: /subs add**", include_str!("help/subs-add.md"));
+const HELP_SUBS_SETUP: (&str, &str) = (
+    "**Command: /subs setup**",
+    include_str!("help/subs-setup.md"),
+);
 const HELP_SUBS_RM: (&str, &str) = ("**Command: /subs rm**", include_str!("help/subs-rm.md"));
+const HELP_SUBS_EDIT: (&str, &str) = ("**Command: /subs edit**", include_str!("help/subs-edit.md"));
+const HELP_SUBS_HISTORY: (&str, &str) = (
+    "**Command: /subs history**",
+    include_str!("help/subs-history.md"),
+);
+const HELP_SUBS_PAUSE: (&str, &str) = (
+    "**Command: /subs pause**",
+    include_str!("help/subs-pause.md"),
+);
+const HELP_SUBS_RESUME: (&str, &str) = (
+    "**Command: /subs resume**",
+    include_str!("help/subs-resume.md"),
+);
+const HELP_SUBS_TEST: (&str, &str) = ("**Command: /subs test**", include_str!("help/subs-test.md"));
+const HELP_SUBS_COPY: (&str, &str) = ("**Command: /subs copy**", include_str!("help/subs-copy.md"));
+const HELP_SUBS_MOVE: (&str, &str) = ("**Command: /subs move**", include_str!("help/subs-move.md"));
+const HELP_SUBS_EXPORT: (&str, &str) = (
+    "**Command: /subs export**",
+    include_str!("help/subs-export.md"),
+);
+const HELP_SUBS_IMPORT: (&str, &str) = (
+    "**Command: /subs import**",
+    include_str!("help/subs-import.md"),
+);
+const HELP_SUBS_MOD_LIST: (&str, &str) = (
+    "**Command: /subs mod list**",
+    include_str!("help/subs-mod-list.md"),
+);
+const HELP_SUBS_MOD_ADD: (&str, &str) = (
+    "**Command: /subs mod add**",
+    include_str!("help/subs-mod-add.md"),
+);
+const HELP_SUBS_MOD_RM: (&str, &str) = (
+    "**Command: /subs mod rm**",
+    include_str!("help/subs-mod-rm.md"),
+);
+const HELP_SUBS_TEMPLATE_SHOW: (&str, &str) = (
+    "**Command: /subs template show**",
+    include_str!("help/subs-template-show.md"),
+);
+const HELP_SUBS_TEMPLATE_SET: (&str, &str) = (
+    "**Command: /subs template set**",
+    include_str!("help/subs-template-set.md"),
+);
+const HELP_SUBS_TEMPLATE_RESET: (&str, &str) = (
+    "**Command: /subs template reset**",
+    include_str!("help/subs-template-reset.md"),
+);
 const HELP_SUBS_MODS_MUTED: (&str, &str) = (
     "**Command: /subs mods muted**",
     include_str!("help/subs-mods-muted.md"),
@@ -51,6 +171,180 @@ const HELP_SUBS_USERS_UNMUTE: (&str, &str) = (
     "**Command: /subs users unmute**",
     include_str!("help/subs-users-unmute.md"),
 );
+const HELP_UNFOLLOW: (&str, &str) = ("**Command: /unfollow**", include_str!("help/unfollow.md"));
+
+/// Help topics in the order they are suggested.
+const TOPICS: &[(&str, (&str, &str))] = &[
+    ("about", HELP_ABOUT),
+    ("follow game", HELP_FOLLOW_GAME),
+    ("follow mod", HELP_FOLLOW_MOD),
+    ("follow user", HELP_FOLLOW_USER),
+    ("follow list", HELP_FOLLOW_LIST),
+    ("unfollow", HELP_UNFOLLOW),
+    ("game info", HELP_GAME),
+    ("game stats", HELP_GAME_STATS),
+    ("games", HELP_GAMES),
+    ("mods", HELP_MODS),
+    ("mod stats", HELP_MOD_STATS),
+    ("popular", HELP_POPULAR),
+    ("random", HELP_RANDOM),
+    ("search", HELP_SEARCH),
+    ("settings default-game", HELP_SETTINGS_DEFAULT_GAME),
+    ("settings channel-game", HELP_SETTINGS_CHANNEL_GAME),
+    ("settings games list", HELP_SETTINGS_GAMES_LIST),
+    ("settings games add", HELP_SETTINGS_GAMES_ADD),
+    ("settings games rm", HELP_SETTINGS_GAMES_RM),
+    ("settings log-channel", HELP_SETTINGS_LOG_CHANNEL),
+    ("settings edit-window", HELP_SETTINGS_EDIT_WINDOW),
+    ("settings language", HELP_SETTINGS_LANGUAGE),
+    ("settings permissions list", HELP_SETTINGS_PERMISSIONS_LIST),
+    (
+        "settings permissions grant",
+        HELP_SETTINGS_PERMISSIONS_GRANT,
+    ),
+    (
+        "settings permissions revoke",
+        HELP_SETTINGS_PERMISSIONS_REVOKE,
+    ),
+    ("subs overview", HELP_SUBS_OVERVIEW),
+    ("subs list", HELP_SUBS_LIST),
+    ("subs add", HELP_SUBS_ADD),
+    ("subs setup", HELP_SUBS_SETUP),
+    ("subs rm", HELP_SUBS_RM),
+    ("subs edit", HELP_SUBS_EDIT),
+    ("subs mod list", HELP_SUBS_MOD_LIST),
+    ("subs mod add", HELP_SUBS_MOD_ADD),
+    ("subs mod rm", HELP_SUBS_MOD_RM),
+    ("subs mods muted", HELP_SUBS_MODS_MUTED),
+    ("subs mods mute", HELP_SUBS_MODS_MUTE),
+    ("subs mods unmute", HELP_SUBS_MODS_UNMUTE),
+    ("subs users muted", HELP_SUBS_USERS_MUTED),
+    ("subs users mute", HELP_SUBS_USERS_MUTE),
+    ("subs users unmute", HELP_SUBS_USERS_UNMUTE),
+    ("subs history", HELP_SUBS_HISTORY),
+    ("subs pause", HELP_SUBS_PAUSE),
+    ("subs resume", HELP_SUBS_RESUME),
+    ("subs test", HELP_SUBS_TEST),
+    ("subs template show", HELP_SUBS_TEMPLATE_SHOW),
+    ("subs template set", HELP_SUBS_TEMPLATE_SET),
+    ("subs template reset", HELP_SUBS_TEMPLATE_RESET),
+    ("subs copy", HELP_SUBS_COPY),
+    ("subs move", HELP_SUBS_MOVE),
+    ("subs export", HELP_SUBS_EXPORT),
+    ("subs import", HELP_SUBS_IMPORT),
+];
+
+/// German translations of the help topics.
+const TOPICS_DE: &[(&str, &str)] = &[
+    ("about", include_str!("help/de/about.md")),
+    ("follow game", include_str!("help/de/follow-game.md")),
+    ("follow mod", include_str!("help/de/follow-mod.md")),
+    ("follow user", include_str!("help/de/follow-user.md")),
+    ("follow list", include_str!("help/de/follow-list.md")),
+    ("unfollow", include_str!("help/de/unfollow.md")),
+    ("game info", include_str!("help/de/game.md")),
+    ("game stats", include_str!("help/de/game-stats.md")),
+    ("games", include_str!("help/de/games.md")),
+    ("mods", include_str!("help/de/mods.md")),
+    ("mod stats", include_str!("help/de/mod-stats.md")),
+    ("popular", include_str!("help/de/popular.md")),
+    ("random", include_str!("help/de/random.md")),
+    ("search", include_str!("help/de/search.md")),
+    (
+        "settings default-game",
+        include_str!("help/de/settings-default-game.md"),
+    ),
+    (
+        "settings channel-game",
+        include_str!("help/de/settings-channel-game.md"),
+    ),
+    (
+        "settings games list",
+        include_str!("help/de/settings-games-list.md"),
+    ),
+    (
+        "settings games add",
+        include_str!("help/de/settings-games-add.md"),
+    ),
+    (
+        "settings games rm",
+        include_str!("help/de/settings-games-rm.md"),
+    ),
+    (
+        "settings log-channel",
+        include_str!("help/de/settings-log-channel.md"),
+    ),
+    (
+        "settings edit-window",
+        include_str!("help/de/settings-edit-window.md"),
+    ),
+    (
+        "settings language",
+        include_str!("help/de/settings-language.md"),
+    ),
+    (
+        "settings permissions list",
+        include_str!("help/de/settings-permissions-list.md"),
+    ),
+    (
+        "settings permissions grant",
+        include_str!("help/de/settings-permissions-grant.md"),
+    ),
+    (
+        "settings permissions revoke",
+        include_str!("help/de/settings-permissions-revoke.md"),
+    ),
+    ("subs overview", include_str!("help/de/subs-overview.md")),
+    ("subs list", include_str!("help/de/subs-list.md")),
+    ("subs add", include_str!("help/de/subs-add.md")),
+    ("subs setup", include_str!("help/de/subs-setup.md")),
+    ("subs rm", include_str!("help/de/subs-rm.md")),
+    ("subs edit", include_str!("help/de/subs-edit.md")),
+    ("subs mod list", include_str!("help/de/subs-mod-list.md")),
+    ("subs mod add", include_str!("help/de/subs-mod-add.md")),
+    ("subs mod rm", include_str!("help/de/subs-mod-rm.md")),
+    (
+        "subs mods muted",
+        include_str!("help/de/subs-mods-muted.md"),
+    ),
+    ("subs mods mute", include_str!("help/de/subs-mods-mute.md")),
+    (
+        "subs mods unmute",
+        include_str!("help/de/subs-mods-unmute.md"),
+    ),
+    (
+        "subs users muted",
+        include_str!("help/de/subs-users-muted.md"),
+    ),
+    (
+        "subs users mute",
+        include_str!("help/de/subs-users-mute.md"),
+    ),
+    (
+        "subs users unmute",
+        include_str!("help/de/subs-users-unmute.md"),
+    ),
+    ("subs history", include_str!("help/de/subs-history.md")),
+    ("subs pause", include_str!("help/de/subs-pause.md")),
+    ("subs resume", include_str!("help/de/subs-resume.md")),
+    ("subs test", include_str!("help/de/subs-test.md")),
+    (
+        "subs template show",
+        include_str!("help/de/subs-template-show.md"),
+    ),
+    (
+        "subs template set",
+        include_str!("help/de/subs-template-set.md"),
+    ),
+    (
+        "subs template reset",
+        include_str!("help/de/subs-template-reset.md"),
+    ),
+    ("subs copy", include_str!("help/de/subs-copy.md")),
+    ("subs move", include_str!("help/de/subs-move.md")),
+    ("subs export", include_str!("help/de/subs-export.md")),
+    ("subs import", include_str!("help/de/subs-import.md")),
+];
 
 pub fn commands() -> Vec<Command> {
     vec![CommandBuilder::new(
@@ -61,24 +355,7 @@ pub fn commands() -> Vec<Command> {
     .option(
         StringBuilder::new("command", "Command to get help for.")
             .required(true)
-            .choices([
-                ("about", "about"),
-                ("game", "game"),
-                ("games", "games"),
-                ("mods", "mods"),
-                ("popular", "popular"),
-                ("settings default-game", "settings default-game"),
-                ("subs overview", "subs overview"),
-                ("subs list", "subs list"),
-                ("subs add", "subs add"),
-                ("subs rm", "subs rm"),
-                ("subs mods muted", "subs mods muted"),
-                ("subs mods mute", "subs mods mute"),
-                ("subs mods unmute", "subs mods unmute"),
-                ("subs users muted", "subs users muted"),
-                ("subs users mute", "subs users mute"),
-                ("subs users unmute", "subs users unmute"),
-            ]),
+            .autocomplete(true),
     )
     .build()]
 }
@@ -88,29 +365,26 @@ pub async fn help(
     interaction: &Interaction,
     command: &CommandData,
 ) -> Result<(), Error> {
+    if let Some((_, value)) = command.autocomplete() {
+        return autocomplete_topics(ctx, interaction, value).await;
+    }
+
     let command = command.options.iter().find_map(|opt| match &opt.value {
         CommandOptionValue::String(value) => Some(value.as_str()),
         _ => None,
     });
-    let (title, description) = match command {
-        Some("about") => HELP_ABOUT,
-        Some("game") => HELP_GAME,
-        Some("games") => HELP_GAMES,
-        Some("mods") => HELP_MODS,
-        Some("popular") => HELP_POPULAR,
-        Some("settings default-game") => HELP_SETTINGS_DEFAULT_GAME,
-        Some("subs overview") => HELP_SUBS_OVERVIEW,
-        Some("subs list") => HELP_SUBS_LIST,
-        Some("subs add") => HELP_SUBS_ADD,
-        Some("subs rm") => HELP_SUBS_RM,
-        Some("subs mods muted") => HELP_SUBS_MODS_MUTED,
-        Some("subs mods mute") => HELP_SUBS_MODS_MUTE,
-        Some("subs mods unmute") => HELP_SUBS_MODS_UNMUTE,
-        Some("subs users muted") => HELP_SUBS_USERS_MUTED,
-        Some("subs users mute") => HELP_SUBS_USERS_MUTE,
-        Some("subs users unmute") => HELP_SUBS_USERS_UNMUTE,
-        _ => return Ok(()),
+    let locale = Locale::from_interaction(interaction);
+    let Some((name, (title, description))) = TOPICS.iter().find(|(name, _)| Some(*name) == command)
+    else {
+        let data = locale.tr("Unknown command.").into_ephemeral();
+        return create_response(ctx, interaction, data).await;
+    };
+    let translated = match locale {
+        Locale::En => None,
+        Locale::De => TOPICS_DE.iter().find(|(topic, _)| topic == name),
     };
+    let description = translated.map_or(*description, |(_, description)| *description);
+    let title = title.replacen("Command:", locale.tr("Command:"), 1);
     let data = EmbedBuilder::new()
         .title(title)
         .description(description)
@@ -119,3 +393,32 @@ pub async fn help(
     create_response(ctx, interaction, data).await?;
     Ok(())
 }
+
+async fn autocomplete_topics(
+    ctx: &Context,
+    interaction: &Interaction,
+    value: &str,
+) -> Result<(), Error> {
+    let value = value.trim_start_matches('/').to_lowercase();
+    let choices = TOPICS
+        .iter()
+        .filter(|(name, _)| name.contains(&value))
+        .take(25)
+        .map(|(name, _)| CommandOptionChoice {
+            name: (*name).to_owned(),
+            name_localizations: None,
+            value: CommandOptionChoiceValue::String((*name).to_owned()),
+        });
+    let data = InteractionResponseDataBuilder::new()
+        .choices(choices)
+        .build();
+    let response = InteractionResponse {
+        kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+        data: Some(data),
+    };
+    ctx.interaction()
+        .create_response(interaction.id, &interaction.token, &response)
+        .await?;
+
+    Ok(())
+}
@@ -3,10 +3,10 @@ use std::collections::HashSet;
 use std::fmt::Write;
 
 use modio::filter::prelude::*;
-use modio::mods::filters::Popular;
+use modio::mods::filters::{MaturityOption as MaturityFilter, Popular, Tags as TagsFilter};
 use modio::types::games::{ApiAccessOptions, Game};
 use modio::types::id::{GameId, ModId};
-use modio::types::mods::{Mod, Statistics};
+use modio::types::mods::{MaturityOption, Mod, Statistics};
 use serde_derive::{Deserialize, Serialize};
 use tokio_stream::StreamExt;
 use twilight_model::application::command::{Command, CommandType};
@@ -17,18 +17,24 @@ use twilight_model::application::interaction::message_component::MessageComponen
 use twilight_model::application::interaction::{Interaction, InteractionContextType};
 use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
 use twilight_model::channel::message::embed::{Embed, EmbedField};
-use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+use twilight_util::builder::command::{
+    CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+};
 use twilight_util::builder::embed::{
     EmbedAuthorBuilder, EmbedBuilder, EmbedFooterBuilder, ImageSource,
 };
 
+use super::game::growth_field;
 use super::{
     autocomplete_games, create_response, defer_component_response, defer_response, search_game,
-    update_response_content, AutocompleteExt, EphemeralMessage, InteractionExt,
+    update_response_content, AutocompleteExt, EphemeralMessage, InteractionExt, SubCommandExt,
 };
 use crate::bot::Context;
+use crate::db::stats::{mod_stats, ModStats};
+use crate::db::{types, Tags};
 use crate::error::Error;
-use crate::util::format_timestamp;
+use crate::i18n::Locale;
+use crate::util::{self, discord_timestamp, IntoFilter, TimestampStyle};
 
 pub fn commands() -> Vec<Command> {
     vec![
@@ -48,6 +54,36 @@ pub fn commands() -> Vec<Command> {
                     .autocomplete(true),
             )
             .build(),
+        CommandBuilder::new("random", "Show a random mod.", CommandType::ChatInput)
+            .contexts([InteractionContextType::Guild])
+            .option(
+                StringBuilder::new("game", "ID or search game instead of the default game.")
+                    .autocomplete(true),
+            )
+            .option(StringBuilder::new("tags", "Comma-separated list of tags"))
+            .build(),
+        CommandBuilder::new(
+            "mod",
+            "Show details of a single mod.",
+            CommandType::ChatInput,
+        )
+        .contexts([InteractionContextType::Guild])
+        .option(
+            SubCommandBuilder::new("stats", "Show the statistics of a mod over time.")
+                .option(StringBuilder::new("mod", "ID or search").required(true))
+                .option(
+                    StringBuilder::new("game", "ID or search game instead of the default game.")
+                        .autocomplete(true),
+                )
+                .option(
+                    IntegerBuilder::new("period", "Time period of the statistics.").choices([
+                        ("7 days", 7),
+                        ("30 days", 30),
+                        ("90 days", 90),
+                    ]),
+                ),
+        )
+        .build(),
     ]
 }
 
@@ -64,6 +100,7 @@ pub async fn list(
     let mut game_id = None;
 
     defer_response(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
 
     for opt in &command.options {
         match &opt.value {
@@ -71,10 +108,10 @@ pub async fn list(
                 search = Some(s);
             }
             CommandOptionValue::String(s) if opt.name == "game" => {
-                let game = search_game(ctx, s).await?;
+                let game = search_game(ctx, interaction.guild_id(), s).await?;
 
                 if game.is_none() {
-                    let data = "Game not found.".into_ephemeral();
+                    let data = locale.tr("Game not found.").into_ephemeral();
                     return create_response(ctx, interaction, data).await;
                 }
                 game_id = game.map(|g| g.id);
@@ -85,26 +122,31 @@ pub async fn list(
 
     let game_id = match (game_id, interaction.guild_id()) {
         (Some(game_id), _) => Some(game_id),
-        (_, Some(guild_id)) => ctx.settings.game(guild_id)?.map(|id| id.0),
+        (_, Some(guild_id)) => ctx
+            .settings
+            .default_game(guild_id, interaction.channel_id())?
+            .map(|id| id.0),
         _ => None,
     };
 
     let Some(game_id) = game_id else {
-        let content = "Default game is not set.";
+        let content = locale.tr("Default game is not set.");
         return update_response_content(ctx, interaction, content).await;
     };
 
     let (filter, title): (Filter, Cow<'_, _>) = if let Some(search) = search {
         match (search.strip_prefix('@'), search.parse::<ModId>()) {
-            (Some(name_id), _) => (NameId::eq(name_id), "Mods".into()),
-            (_, Ok(id)) => (Id::eq(id), "Mods".into()),
+            (Some(name_id), _) => (NameId::eq(name_id), locale.tr("Mods").into()),
+            (_, Ok(id)) => (Id::eq(id), locale.tr("Mods").into()),
             (_, Err(_)) => (
                 Fulltext::eq(search),
-                format!("Mods matching: '{search}'").into(),
+                locale
+                    .format("Mods matching: '{query}'", &[("query", search)])
+                    .into(),
             ),
         }
     } else {
-        (Filter::default(), "Mods".into())
+        (Filter::default(), locale.tr("Mods").into())
     };
     let game = ctx.modio.game(game_id);
     let mods = game.mods();
@@ -117,14 +159,14 @@ pub async fn list(
         .await?;
 
     let Some(page) = first_page else {
-        let content = "No mods found.";
+        let content = locale.tr("No mods found.");
         return update_response_content(ctx, interaction, content).await;
     };
 
     let (embeds, components) = match page.as_slice() {
         [mod_] => {
             let game = game.get().await?;
-            let embed = create_mod_embed(&game, mod_).build();
+            let embed = create_mod_embed(&game, mod_, locale).build();
             (Some(vec![embed]), None)
         }
         list => {
@@ -188,13 +230,16 @@ pub async fn list_component(
         ..
     } = serde_urlencoded::from_str(custom_id).unwrap();
 
+    let locale = Locale::from_interaction(interaction);
     let (filter, title): (Filter, Cow<'_, _>) = if let Some(search) = search {
         (
             Fulltext::eq(search),
-            format!("Mods matching: '{search}'").into(),
+            locale
+                .format("Mods matching: '{query}'", &[("query", search)])
+                .into(),
         )
     } else {
-        (Filter::default(), "Mods".into())
+        (Filter::default(), locale.tr("Mods").into())
     };
     let filter = filter.offset(offset).limit(20);
     let game = ctx.modio.game(game_id);
@@ -234,15 +279,16 @@ pub async fn popular(
     }
 
     defer_response(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
 
     let game_id = match command.options.as_slice() {
         [CommandDataOption {
             value: CommandOptionValue::String(s),
             ..
         }] => {
-            let game = search_game(ctx, s).await?;
+            let game = search_game(ctx, interaction.guild_id(), s).await?;
             if game.is_none() {
-                let content = "Game not found.";
+                let content = locale.tr("Game not found.");
                 return update_response_content(ctx, interaction, content).await;
             }
             game.map(|g| g.id)
@@ -252,12 +298,15 @@ pub async fn popular(
 
     let game_id = match (game_id, interaction.guild_id()) {
         (Some(game_id), _) => Some(game_id),
-        (_, Some(guild_id)) => ctx.settings.game(guild_id)?.map(|id| id.0),
+        (_, Some(guild_id)) => ctx
+            .settings
+            .default_game(guild_id, interaction.channel_id())?
+            .map(|id| id.0),
         _ => None,
     };
 
     let Some(game_id) = game_id else {
-        let content = "Default game is not set.";
+        let content = locale.tr("Default game is not set.");
         return update_response_content(ctx, interaction, content).await;
     };
 
@@ -267,7 +316,7 @@ pub async fn popular(
     let game = game.get().await?;
 
     if mods.is_empty() {
-        let content = "No mods founds.";
+        let content = locale.tr("No mods found.");
         return update_response_content(ctx, interaction, content).await;
     }
 
@@ -303,6 +352,318 @@ pub async fn popular(
     Ok(())
 }
 
+pub async fn random(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+) -> Result<(), Error> {
+    if let Some(("game", value)) = command.autocomplete() {
+        return autocomplete_games(ctx, interaction, value).await;
+    }
+
+    let mut game_id = None;
+    let mut tags = None;
+
+    defer_response(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    for opt in &command.options {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => {
+                let game = search_game(ctx, interaction.guild_id(), s).await?;
+
+                if game.is_none() {
+                    let content = locale.tr("Game not found.");
+                    return update_response_content(ctx, interaction, content).await;
+                }
+                game_id = game.map(|g| g.id);
+            }
+            CommandOptionValue::String(s) if opt.name == "tags" => {
+                tags = Some(s.as_str());
+            }
+            _ => {}
+        }
+    }
+
+    let game_id = match (game_id, interaction.guild_id()) {
+        (Some(game_id), _) => Some(game_id),
+        (_, Some(guild_id)) => ctx
+            .settings
+            .default_game(guild_id, interaction.channel_id())?
+            .map(|id| id.0),
+        _ => None,
+    };
+
+    let Some(game_id) = game_id else {
+        let content = locale.tr("Default game is not set.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let custom_id = RandomCustomId {
+        game_id,
+        tags: tags.map(ToOwned::to_owned),
+    };
+    update_random_response(ctx, interaction, &custom_id).await
+}
+
+#[derive(Deserialize, Serialize)]
+struct RandomCustomId {
+    #[serde(rename = "g")]
+    game_id: GameId,
+    #[serde(rename = "t")]
+    tags: Option<String>,
+}
+
+pub async fn random_component(
+    ctx: &Context,
+    interaction: &Interaction,
+    component: &MessageComponentInteractionData,
+) -> Result<(), Error> {
+    let custom_id = component
+        .custom_id
+        .strip_prefix("random:")
+        .unwrap_or(&component.custom_id);
+
+    let custom_id = serde_urlencoded::from_str(custom_id).unwrap();
+
+    defer_component_response(ctx, interaction).await?;
+
+    update_random_response(ctx, interaction, &custom_id).await
+}
+
+async fn update_random_response(
+    ctx: &Context,
+    interaction: &Interaction,
+    custom_id: &RandomCustomId,
+) -> Result<(), Error> {
+    let locale = Locale::from_interaction(interaction);
+    let mut filter = Filter::default();
+    if let Some(tags) = &custom_id.tags {
+        let tags = Tags::from_csv(tags);
+        if !tags.is_empty() {
+            filter = filter.and(TagsFilter::eq(tags.iter().collect::<Vec<_>>()));
+        }
+    }
+    // Explicit mods are only shown in age-restricted channels.
+    let nsfw = interaction
+        .channel
+        .as_ref()
+        .and_then(|c| c.nsfw)
+        .unwrap_or(false);
+    if !nsfw {
+        filter = filter.and(MaturityFilter::lt(MaturityOption::EXPLICIT.bits()));
+    }
+
+    let game = ctx.modio.game(custom_id.game_id);
+    let mods = game.mods();
+
+    let first_page = mods
+        .search(filter.clone().limit(1))
+        .paged()
+        .await?
+        .try_next()
+        .await?;
+
+    let total = first_page.map(|p| p.total()).unwrap_or_default();
+    let offset = util::random(total);
+
+    let mod_ = if total > 0 {
+        mods.search(filter.offset(offset).limit(1)).first().await?
+    } else {
+        None
+    };
+
+    let Some(mod_) = mod_ else {
+        let content = locale.tr("No mods found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    let game = game.get().await?;
+
+    let embed = create_mod_embed(&game, &mod_, locale).build();
+
+    let custom_id = String::from("random:") + &serde_urlencoded::to_string(custom_id).unwrap();
+    let components = (custom_id.len() <= 100).then(|| {
+        let reroll = Button {
+            custom_id: Some(custom_id),
+            style: ButtonStyle::Primary,
+            label: Some(locale.tr("reroll").to_owned()),
+            disabled: false,
+            emoji: None,
+            url: None,
+            sku_id: None,
+        };
+        vec![Component::from(ActionRow {
+            components: vec![reroll.into()],
+        })]
+    });
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .content(None)
+        .embeds(Some(&[embed]))
+        .components(components.as_deref())
+        .await?;
+
+    Ok(())
+}
+
+pub async fn mod_(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+) -> Result<(), Error> {
+    if let Some(("game", value)) = command.autocomplete() {
+        return autocomplete_games(ctx, interaction, value).await;
+    }
+
+    match command.subcommand() {
+        Some(("stats", opts)) => stats(ctx, interaction, opts).await,
+        _ => Ok(()),
+    }
+}
+
+/// `/mod stats <mod> [game] [period]`
+async fn stats(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let mut search = None;
+    let mut game_id = None;
+    let mut days = 30;
+
+    defer_response(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "mod" => {
+                search = Some(s);
+            }
+            CommandOptionValue::String(s) if opt.name == "game" => {
+                let game = search_game(ctx, interaction.guild_id(), s).await?;
+
+                if game.is_none() {
+                    let content = locale.tr("Game not found.");
+                    return update_response_content(ctx, interaction, content).await;
+                }
+                game_id = game.map(|g| g.id);
+            }
+            CommandOptionValue::Integer(v) if opt.name == "period" => {
+                days = *v;
+            }
+            _ => {}
+        }
+    }
+
+    let game_id = match (game_id, interaction.guild_id()) {
+        (Some(game_id), _) => Some(game_id),
+        (_, Some(guild_id)) => ctx
+            .settings
+            .default_game(guild_id, interaction.channel_id())?
+            .map(|id| id.0),
+        _ => None,
+    };
+
+    let Some(game_id) = game_id else {
+        let content = locale.tr("Default game is not set.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let search = search.expect("required option");
+    let game = ctx.modio.game(game_id);
+    let Some(mod_) = game.mods().search(search.into_filter()).first().await? else {
+        let content = locale.tr("Mod not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    let game = game.get().await?;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let since = util::current_timestamp() as i64 - days * 86400;
+    let history = mod_stats(
+        &ctx.pool,
+        types::GameId(game.id),
+        types::ModId(mod_.id),
+        since,
+    )?;
+
+    let embed = create_stats_embed(&game, &mod_, &history, days, locale);
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+
+    Ok(())
+}
+
+fn create_stats_embed(
+    game: &Game,
+    mod_: &Mod,
+    history: &[ModStats],
+    days: i64,
+    locale: Locale,
+) -> Embed {
+    let mut embed = EmbedBuilder::new()
+        .title(&mod_.name)
+        .url(mod_.profile_url.to_string())
+        .thumbnail(ImageSource::url(mod_.logo.thumb_320x180.to_string()).unwrap())
+        .author(
+            EmbedAuthorBuilder::new(&game.name)
+                .url(game.profile_url.to_string())
+                .icon_url(ImageSource::url(game.icon.thumb_64x64.to_string()).unwrap()),
+        );
+
+    let (first, last) = match history {
+        [first, .., last] => (first, last),
+        [_] => {
+            let desc = locale.tr("Not enough statistics recorded yet.");
+            return embed.description(desc).build();
+        }
+        [] => {
+            let desc = locale.tr(
+                "No statistics recorded yet. Statistics are only recorded for subscribed and followed mods.",
+            );
+            return embed.description(desc).build();
+        }
+    };
+
+    let downloads = history.iter().map(|s| s.downloads).collect::<Vec<_>>();
+    let subscribers = history.iter().map(|s| s.subscribers).collect::<Vec<_>>();
+
+    embed = embed
+        .description(locale.format(
+            "Statistics over the last {days} days.",
+            &[("days", &days.to_string())],
+        ))
+        .field(growth_field(locale.tr("Downloads"), &downloads))
+        .field(growth_field(locale.tr("Subscribers"), &subscribers))
+        .field(EmbedField {
+            name: locale.tr("Rating").to_owned(),
+            value: locale.format(
+                "Votes: {votes} ({delta})\nRank: {rank} ({change})",
+                &[
+                    (
+                        "votes",
+                        &format!("+{}/-{}", last.ratings_positive, last.ratings_negative),
+                    ),
+                    (
+                        "delta",
+                        &format!(
+                            "+{}/-{}",
+                            last.ratings_positive - first.ratings_positive,
+                            last.ratings_negative - first.ratings_negative,
+                        ),
+                    ),
+                    ("rank", &last.rank.to_string()),
+                    ("change", &format!("{:+}", first.rank - last.rank)),
+                ],
+            ),
+            inline: false,
+        });
+    embed.build()
+}
+
 fn create_list_embed(mods: &[Mod], title: &str, page: usize, page_count: usize) -> Embed {
     let mut content = String::new();
     for mod_ in mods {
@@ -365,7 +726,7 @@ fn create_browse_buttons(
     row.into()
 }
 
-fn create_mod_embed(game: &Game, mod_: &Mod) -> EmbedBuilder {
+fn create_mod_embed(game: &Game, mod_: &Mod, locale: Locale) -> EmbedBuilder {
     let with_ddl = game
         .api_access_options
         .contains(ApiAccessOptions::ALLOW_DIRECT_DOWNLOAD);
@@ -387,7 +748,7 @@ fn create_mod_embed(game: &Game, mod_: &Mod) -> EmbedBuilder {
         )
         .footer(footer);
 
-    create_fields(builder, game, mod_, false, with_ddl)
+    create_fields(builder, game, mod_, false, with_ddl, locale)
 }
 
 #[allow(clippy::too_many_lines)]
@@ -397,57 +758,58 @@ pub fn create_fields(
     m: &Mod,
     is_new: bool,
     with_ddl: bool,
+    locale: Locale,
 ) -> EmbedBuilder {
-    fn ratings(stats: &Statistics) -> EmbedField {
+    fn ratings(stats: &Statistics, locale: Locale) -> EmbedField {
         EmbedField {
-            name: "Rating".to_owned(),
-            value: format!(
-                r"Rank: {}/{}
-Downloads: {}
-Subscribers: {}
-Votes: +{}/-{}",
-                stats.popularity.rank_position,
-                stats.popularity.rank_total,
-                stats.downloads_total,
-                stats.subscribers_total,
-                stats.ratings.positive,
-                stats.ratings.negative,
+            name: locale.tr("Rating").to_owned(),
+            value: locale.format(
+                "Rank: {rank}/{total}\nDownloads: {downloads}\nSubscribers: {subscribers}\nVotes: +{positive}/-{negative}",
+                &[
+                    ("rank", &stats.popularity.rank_position.to_string()),
+                    ("total", &stats.popularity.rank_total.to_string()),
+                    ("downloads", &stats.downloads_total.to_string()),
+                    ("subscribers", &stats.subscribers_total.to_string()),
+                    ("positive", &stats.ratings.positive.to_string()),
+                    ("negative", &stats.ratings.negative.to_string()),
+                ],
             ),
             inline: true,
         }
     }
     #[allow(clippy::cast_possible_wrap)]
-    fn dates(m: &Mod) -> EmbedField {
-        let added = format_timestamp(m.date_added.as_secs());
-        let updated = format_timestamp(m.date_updated.as_secs());
+    fn dates(m: &Mod, locale: Locale) -> EmbedField {
+        let added = discord_timestamp(m.date_added.as_secs(), TimestampStyle::ShortDate);
+        let updated = discord_timestamp(m.date_updated.as_secs(), TimestampStyle::Relative);
         EmbedField {
-            name: "Dates".to_owned(),
-            value: format!("Created: {added}\nUpdated: {updated}"),
+            name: locale.tr("Dates").to_owned(),
+            value: locale.format(
+                "Created: {added}\nUpdated: {updated}",
+                &[("added", &added), ("updated", &updated)],
+            ),
             inline: true,
         }
     }
-    fn info(m: &Mod, with_ddl: bool) -> Option<EmbedField> {
-        let mut info = if with_ddl {
-            String::from("Links: ")
-        } else {
-            String::new()
-        };
+    fn info(m: &Mod, with_ddl: bool, locale: Locale) -> Option<EmbedField> {
+        let prefix = if with_ddl { locale.tr("Links: ") } else { "" };
+        let mut info = String::from(prefix);
         if let Some(homepage) = &m.homepage_url {
-            _ = write!(info, "[Homepage]({homepage}), ");
+            _ = write!(info, "[{}]({homepage}), ", locale.tr("Homepage"));
         }
         if let Some(f) = &m.modfile {
             if with_ddl {
-                _ = writeln!(info, "[Download]({})", f.download.binary_url);
+                let link = &f.download.binary_url;
+                _ = writeln!(info, "[{}]({link})", locale.tr("Download"));
             }
             if let Some(version) = &f.version {
-                _ = writeln!(info, "Version: {version}");
+                _ = writeln!(info, "{}: {version}", locale.tr("Version"));
             }
             let size = bytesize::ByteSize::b(f.filesize).display().si_short();
-            _ = writeln!(info, "Size: {size}");
+            _ = writeln!(info, "{}: {size}", locale.tr("Size"));
         }
-        if info.len() > 7 {
+        if info.len() > prefix.len() {
             Some(EmbedField {
-                name: "Info".to_owned(),
+                name: locale.tr("Info").to_owned(),
                 value: info,
                 inline: true,
             })
@@ -455,7 +817,7 @@ Votes: +{}/-{}",
             None
         }
     }
-    fn tags(g: &Game, m: &Mod) -> Option<EmbedField> {
+    fn tags(g: &Game, m: &Mod, locale: Locale) -> Option<EmbedField> {
         if m.tags.is_empty() {
             return None;
         }
@@ -481,26 +843,26 @@ Votes: +{}/-{}",
         }
 
         Some(EmbedField {
-            name: "Tags".to_owned(),
+            name: locale.tr("Tags").to_owned(),
             value: tags,
             inline: true,
         })
     }
 
     if is_new {
-        if let Some(field) = info(m, with_ddl) {
+        if let Some(field) = info(m, with_ddl, locale) {
             builder = builder.field(field);
         }
-        if let Some(field) = tags(g, m) {
+        if let Some(field) = tags(g, m, locale) {
             builder = builder.field(field);
         }
     } else {
-        builder = builder.field(ratings(&m.stats));
-        if let Some(field) = info(m, with_ddl) {
+        builder = builder.field(ratings(&m.stats, locale));
+        if let Some(field) = info(m, with_ddl, locale) {
             builder = builder.field(field);
         }
-        builder = builder.field(dates(m));
-        if let Some(field) = tags(g, m) {
+        builder = builder.field(dates(m, locale));
+        if let Some(field) = tags(g, m, locale) {
             builder = builder.field(field);
         }
     }
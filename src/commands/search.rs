@@ -0,0 +1,298 @@
+use std::fmt::Write;
+
+use futures_util::stream::FuturesUnordered;
+use modio::filter::prelude::*;
+use modio::types::id::GameId;
+use modio::types::mods::Mod;
+use serde_derive::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+use twilight_model::application::command::{Command, CommandType};
+use twilight_model::application::interaction::application_command::{
+    CommandData, CommandOptionValue,
+};
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::application::interaction::{Interaction, InteractionContextType};
+use twilight_model::channel::message::component::{ActionRow, Button, ButtonStyle, Component};
+use twilight_model::channel::message::Embed;
+use twilight_util::builder::command::{CommandBuilder, StringBuilder};
+use twilight_util::builder::embed::EmbedBuilder;
+
+use super::{defer_component_response, defer_response, update_response_content};
+use crate::bot::Context;
+use crate::db::autocomplete::third_party_games;
+use crate::error::Error;
+use crate::i18n::Locale;
+use crate::util;
+
+const GAMES_PER_PAGE: usize = 5;
+const MODS_PER_GAME: usize = 5;
+/// Number of games searched concurrently.
+const BATCH_SIZE: usize = 10;
+/// Maximum number of games searched for a single page to limit the requests per button click.
+const MAX_SCANNED_GAMES: usize = 30;
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+pub fn commands() -> Vec<Command> {
+    vec![CommandBuilder::new(
+        "search",
+        "Search mods across all games.",
+        CommandType::ChatInput,
+    )
+    .contexts([InteractionContextType::Guild, InteractionContextType::BotDm])
+    .option(
+        StringBuilder::new("query", "Search term")
+            .required(true)
+            .max_length(50),
+    )
+    .build()]
+}
+
+#[derive(Deserialize, Serialize)]
+struct CustomId {
+    #[serde(rename = "q")]
+    query: String,
+    #[serde(rename = "a")]
+    after: Option<GameId>,
+    #[serde(rename = "b")]
+    before: Option<GameId>,
+}
+
+struct Page {
+    results: Vec<(GameId, String, Vec<Mod>, usize)>,
+    has_prev: bool,
+    has_next: bool,
+    first: GameId,
+    last: GameId,
+}
+
+pub async fn search(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+) -> Result<(), Error> {
+    let query = command.options.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) if opt.name == "query" => Some(s.as_str()),
+        _ => None,
+    });
+    let query = query.expect("required option");
+
+    defer_response(ctx, interaction).await?;
+
+    let locale = Locale::from_interaction(interaction);
+    let Some(page) = search_page(ctx, query, None, None).await? else {
+        let content = locale.tr("No mods found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let embed = create_embed(query, &page, locale);
+    let components = create_browse_buttons(query, &page);
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .components(components.as_ref().map(std::slice::from_ref))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn search_component(
+    ctx: &Context,
+    interaction: &Interaction,
+    component: &MessageComponentInteractionData,
+) -> Result<(), Error> {
+    let custom_id = component
+        .custom_id
+        .strip_prefix("search:")
+        .unwrap_or(&component.custom_id);
+
+    let CustomId {
+        query,
+        after,
+        before,
+    } = serde_urlencoded::from_str(custom_id).unwrap();
+
+    defer_component_response(ctx, interaction).await?;
+
+    if let Some(page) = search_page(ctx, &query, after, before).await? {
+        let locale = Locale::from_interaction(interaction);
+        let embed = create_embed(&query, &page, locale);
+        let components = create_browse_buttons(&query, &page);
+
+        ctx.interaction()
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .components(components.as_ref().map(std::slice::from_ref))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Searches the games after or before the given game id until enough games with matching mods
+/// are found or the maximum number of games for a page is searched.
+async fn search_page(
+    ctx: &Context,
+    query: &str,
+    after: Option<GameId>,
+    before: Option<GameId>,
+) -> Result<Option<Page>, Error> {
+    let games = third_party_games(&ctx.pool)?;
+
+    let candidates: Vec<_> = match (after, before) {
+        (_, Some(before)) => games.iter().rev().filter(|(id, _)| id.0 < before).collect(),
+        (Some(after), _) => games.iter().filter(|(id, _)| id.0 > after).collect(),
+        (None, None) => games.iter().collect(),
+    };
+
+    let mut results = Vec::new();
+    let mut scanned = Vec::new();
+
+    for batch in candidates
+        .chunks(BATCH_SIZE)
+        .take(MAX_SCANNED_GAMES / BATCH_SIZE)
+    {
+        let mut st = batch
+            .iter()
+            .map(|(id, name)| async move {
+                let filter = Fulltext::eq(query).limit(MODS_PER_GAME);
+                let page = ctx.modio.game(id.0).mods().search(filter).paged().await;
+                let page = match page {
+                    Ok(mut st) => st.try_next().await,
+                    Err(e) => Err(e),
+                };
+                (id.0, name, page)
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        while let Some((game_id, name, page)) = st.next().await {
+            match page {
+                Ok(Some(page)) if !page.is_empty() => {
+                    let total = page.total();
+                    results.push((game_id, name.clone(), page.into_iter().collect(), total));
+                }
+                Ok(_) => {}
+                Err(e) => tracing::debug!("failed to search mods for game={game_id}: {e}"),
+            }
+        }
+        scanned.extend(batch.iter().map(|(id, _)| id.0));
+
+        if results.len() >= GAMES_PER_PAGE {
+            break;
+        }
+    }
+
+    // Keep the games in search order and continue the next page after the last kept game.
+    let backwards = before.is_some();
+    let order = |id: &GameId| scanned.iter().position(|s| s == id);
+    results.sort_by_key(|(id, _, _, _)| order(id));
+    results.truncate(GAMES_PER_PAGE);
+
+    let boundary = if results.len() == GAMES_PER_PAGE {
+        results.last().map(|(id, _, _, _)| *id)
+    } else {
+        scanned.last().copied()
+    };
+    let (Some(start), Some(boundary)) = (scanned.first().copied(), boundary) else {
+        return Ok(None);
+    };
+    let (first, last) = if backwards {
+        (boundary, start)
+    } else {
+        (start, boundary)
+    };
+    results.sort_by_key(|(id, _, _, _)| *id);
+
+    let page = Page {
+        has_prev: games.iter().any(|(id, _)| id.0 < first),
+        has_next: games.iter().any(|(id, _)| id.0 > last),
+        results,
+        first,
+        last,
+    };
+    if page.results.is_empty() && !page.has_next && !page.has_prev {
+        return Ok(None);
+    }
+    Ok(Some(page))
+}
+
+fn create_embed(query: &str, page: &Page, locale: Locale) -> Embed {
+    let mut lines = Vec::new();
+    for (game_id, name, mods, total) in &page.results {
+        lines.push(format!("**{name}** (`{game_id}`)"));
+        for mod_ in mods {
+            lines.push(format!(
+                "`{}.` [{}]({})",
+                mod_.id, mod_.name, mod_.profile_url
+            ));
+        }
+        if *total > mods.len() {
+            let count = (total - mods.len()).to_string();
+            let more = locale.format("and {count} more", &[("count", &count)]);
+            lines.push(format!("_{more}_"));
+        }
+        lines.push(String::new());
+    }
+
+    // Stop at the last complete line that fits into the description.
+    let mut content = String::new();
+    for line in lines {
+        if content.len() + line.len() + 1 > MAX_DESCRIPTION_LENGTH {
+            break;
+        }
+        _ = writeln!(content, "{line}");
+    }
+    if content.is_empty() {
+        content.push_str(locale.tr("No mods found in the searched games."));
+    }
+
+    let title = locale.format("Mods matching: '{query}'", &[("query", query)]);
+    EmbedBuilder::new()
+        .title(util::truncate(title, MAX_TITLE_LENGTH))
+        .description(content)
+        .build()
+}
+
+fn create_browse_buttons(query: &str, page: &Page) -> Option<Component> {
+    fn create_custom_id(id: &CustomId) -> String {
+        String::from("search:") + &serde_urlencoded::to_string(id).unwrap()
+    }
+    let prev = Button {
+        custom_id: Some(create_custom_id(&CustomId {
+            query: query.to_owned(),
+            after: None,
+            before: Some(page.first),
+        })),
+        style: ButtonStyle::Primary,
+        label: Some("prev".to_owned()),
+        disabled: !page.has_prev,
+        emoji: None,
+        url: None,
+        sku_id: None,
+    };
+    let next = Button {
+        custom_id: Some(create_custom_id(&CustomId {
+            query: query.to_owned(),
+            after: Some(page.last),
+            before: None,
+        })),
+        style: ButtonStyle::Primary,
+        label: Some("next".to_owned()),
+        disabled: !page.has_next,
+        emoji: None,
+        url: None,
+        sku_id: None,
+    };
+    // Custom ids are limited to 100 characters.
+    if [&prev, &next]
+        .iter()
+        .any(|b| b.custom_id.as_ref().is_some_and(|id| id.len() > 100))
+    {
+        return None;
+    }
+    let row = ActionRow {
+        components: vec![prev.into(), next.into()],
+    };
+    Some(row.into())
+}
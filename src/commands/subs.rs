@@ -5,18 +5,19 @@ use std::fmt::{Display, Write};
 use futures_util::stream::FuturesUnordered;
 use modio::filter::prelude::*;
 use modio::types::games::{ApiAccessOptions, Game};
-use modio::types::mods::Mod;
-use modio::Modio;
+use modio::types::mods::{EventType, Mod};
 use tokio_stream::StreamExt;
 use twilight_model::application::command::{Command, CommandType};
 use twilight_model::application::interaction::application_command::{
     CommandData, CommandDataOption, CommandOptionValue,
 };
 use twilight_model::application::interaction::{Interaction, InteractionContextType};
+use twilight_model::channel::ChannelType;
+use twilight_model::gateway::payload::incoming::MemberChunk;
 use twilight_model::guild::Permissions;
 use twilight_util::builder::command::{
-    BooleanBuilder, CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
-    SubCommandGroupBuilder,
+    AttachmentBuilder, BooleanBuilder, ChannelBuilder, CommandBuilder, IntegerBuilder,
+    StringBuilder, SubCommandBuilder, SubCommandGroupBuilder,
 };
 use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
 
@@ -25,11 +26,31 @@ use super::{
     update_response_from_content, AutocompleteExt, EphemeralMessage, InteractionExt, SubCommandExt,
 };
 use crate::bot::Context;
-use crate::db::types::{ChannelId, GameId, ModId};
-use crate::db::{Events, Tags};
+use crate::db::types::{ChannelId, GameId, GuildId, ModId, RoleId, UserId};
+use crate::db::{
+    AuditEntry, DeliveryFailure, Events, ModSubscription, Style, Subscription, SubscriptionChange,
+    Tags, MAX_PERMISSION_FAILURES, PAUSED_INDEFINITELY,
+};
 use crate::error::Error;
-use crate::util::{ContentBuilder, IntoFilter};
+use crate::i18n::Locale;
+use crate::tasks::events::{create_mod_message, deliver, is_wanted};
+use crate::util::{self, ContentBuilder, IntoFilter, TimestampStyle};
+
+mod edit;
+mod export;
+pub mod setup;
+mod template;
+
+/// Number of the latest mods searched for a match by `/subs test`.
+const TEST_MODS_LIMIT: usize = 50;
+
+/// Number of audit log entries shown by `/subs history`.
+const HISTORY_LIMIT: i64 = 25;
 
+const THIRD_PARTY_DISABLED: &str =
+    ":no_entry: Third party API access is disabled for '{game}' but is required for subscriptions.";
+
+#[allow(clippy::too_many_lines)]
 pub fn commands() -> Vec<Command> {
     vec![CommandBuilder::new(
         "subs",
@@ -59,7 +80,23 @@ pub fn commands() -> Vec<Command> {
                 ("All", i64::from(Events::ALL.bits())),
             ]),
         )
-        .option(BooleanBuilder::new("explicit", "Allow explicit content")),
+        .option(BooleanBuilder::new("explicit", "Allow explicit content"))
+        .option(BooleanBuilder::new(
+            "crosspost",
+            "Publish the notifications in announcement channels",
+        ))
+        .option(style_option()),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "setup",
+            "Subscribe the current channel to a game by choosing the tags and options.",
+        )
+        .option(
+            StringBuilder::new("game", "ID or search")
+                .required(true)
+                .autocomplete(true),
+        ),
     )
     .option(
         SubCommandBuilder::new(
@@ -80,6 +117,62 @@ pub fn commands() -> Vec<Command> {
             ]),
         ),
     )
+    .option(
+        SubCommandBuilder::new("edit", "Edit a subscription of the current channel.")
+            .option(
+                StringBuilder::new("subscription", "The subscription to edit")
+                    .required(true)
+                    .autocomplete(true),
+            )
+            .option(StringBuilder::new(
+                "tags",
+                "Comma-separated list of tags, `-` to remove all tags",
+            ))
+            .option(
+                IntegerBuilder::new("type", "Type of the mod updates.").choices([
+                    ("New mods", i64::from(Events::NEW.bits())),
+                    ("Updated mods", i64::from(Events::UPD.bits())),
+                    ("All", i64::from(Events::ALL.bits())),
+                ]),
+            )
+            .option(BooleanBuilder::new("explicit", "Allow explicit content"))
+            .option(BooleanBuilder::new(
+                "crosspost",
+                "Publish the notifications in announcement channels",
+            ))
+            .option(style_option()),
+    )
+    .option(
+        SubCommandGroupBuilder::new("mod", "Subscribe to the updates of single mods.").subcommands(
+            [
+                SubCommandBuilder::new("list", "List the subscribed mods"),
+                SubCommandBuilder::new(
+                    "add",
+                    "Subscribe the current channel to the updates of a mod.",
+                )
+                .option(
+                    StringBuilder::new("game", "ID or search")
+                        .required(true)
+                        .autocomplete(true),
+                )
+                .option(StringBuilder::new("mod", "ID or search").required(true))
+                .option(BooleanBuilder::new(
+                    "crosspost",
+                    "Publish the notifications in announcement channels",
+                )),
+                SubCommandBuilder::new(
+                    "rm",
+                    "Unsubscribe the current channel from the updates of a mod.",
+                )
+                .option(
+                    StringBuilder::new("game", "ID or search")
+                        .required(true)
+                        .autocomplete(true),
+                )
+                .option(StringBuilder::new("mod", "ID or search").required(true)),
+            ],
+        ),
+    )
     .option(
         SubCommandGroupBuilder::new("mods", "Mute update notifications for a mod.").subcommands([
             SubCommandBuilder::new("muted", "List muted mods"),
@@ -119,27 +212,354 @@ pub fn commands() -> Vec<Command> {
                     .option(StringBuilder::new("name", "username").required(true)),
             ]),
     )
+    .option(SubCommandBuilder::new(
+        "export",
+        "Export the subscriptions of this server to a file.",
+    ))
+    .option(
+        SubCommandBuilder::new("import", "Import subscriptions from an exported file.")
+            .option(AttachmentBuilder::new("file", "Exported file").required(true))
+            .option(StringBuilder::new(
+                "channels",
+                "Map the channels of the file to channels of this server, e.g. `123=#mods`",
+            )),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "pause",
+            "Pause the notifications of the current channel without removing the subscriptions.",
+        )
+        .option(
+            StringBuilder::new("game", "Only pause the subscriptions of this game")
+                .autocomplete(true),
+        )
+        .option(StringBuilder::new(
+            "duration",
+            "Resume the notifications automatically after e.g. `12h`, `3d` or `1w2d`",
+        )),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "resume",
+            "Resume the paused notifications of the current channel.",
+        )
+        .option(
+            StringBuilder::new("game", "Only resume the subscriptions of this game")
+                .autocomplete(true),
+        ),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "test",
+            "Send a test notification for a game to the current channel.",
+        )
+        .option(
+            StringBuilder::new("game", "ID or search")
+                .required(true)
+                .autocomplete(true),
+        ),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "copy",
+            "Copy the subscriptions of a channel to another channel.",
+        )
+        .option(
+            ChannelBuilder::new("from", "Channel to copy the subscriptions from.")
+                .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement])
+                .required(true),
+        )
+        .option(
+            ChannelBuilder::new("to", "Channel to copy the subscriptions to.")
+                .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement])
+                .required(true),
+        ),
+    )
+    .option(
+        SubCommandBuilder::new(
+            "move",
+            "Move the subscriptions of a channel to another channel.",
+        )
+        .option(
+            ChannelBuilder::new("from", "Channel to move the subscriptions from.")
+                .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement])
+                .required(true),
+        )
+        .option(
+            ChannelBuilder::new("to", "Channel to move the subscriptions to.")
+                .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement])
+                .required(true),
+        ),
+    )
+    .option(
+        SubCommandBuilder::new("history", "Show the latest changes of the subscriptions.").option(
+            ChannelBuilder::new("channel", "Only show the changes of this channel.")
+                .channel_types([ChannelType::GuildText, ChannelType::GuildAnnouncement]),
+        ),
+    )
+    .option(
+        SubCommandGroupBuilder::new("template", "Customize the notifications.").subcommands([
+            SubCommandBuilder::new(
+                "show",
+                "Show the notification template and the available placeholders.",
+            )
+            .option(
+                StringBuilder::new("subscription", "Template of the subscription")
+                    .autocomplete(true),
+            ),
+            SubCommandBuilder::new("set", "Set the notification template and show a preview.")
+                .option(
+                    StringBuilder::new("subscription", "Only change the subscription")
+                        .autocomplete(true),
+                )
+                .option(
+                    StringBuilder::new("content", "Message text, `-` for the default")
+                        .max_length(2000),
+                )
+                .option(
+                    StringBuilder::new("title", "Title of the embed, `-` for the default")
+                        .max_length(256),
+                )
+                .option(
+                    StringBuilder::new(
+                        "description",
+                        "Description of the embed, `-` for the default",
+                    )
+                    .max_length(4096),
+                ),
+            SubCommandBuilder::new("reset", "Remove the notification template.").option(
+                StringBuilder::new("subscription", "Template of the subscription")
+                    .autocomplete(true),
+            ),
+        ]),
+    )
     .contexts([InteractionContextType::Guild])
-    .default_member_permissions(Permissions::MANAGE_CHANNELS)
     .build()]
 }
 
+fn style_option() -> StringBuilder {
+    StringBuilder::new("style", "Layout of the notifications").choices([
+        ("Full", Style::Full.as_str()),
+        ("Compact", Style::Compact.as_str()),
+        ("Text only", Style::Text.as_str()),
+    ])
+}
+
+/// Returns `true` if the member has the `Administrator` or `Manage Channels` permission or one
+/// of the roles granted with `/settings permissions`.
+pub fn can_manage(ctx: &Context, interaction: &Interaction) -> Result<bool, Error> {
+    let (Some(guild_id), Some(member)) = (interaction.guild_id(), &interaction.member) else {
+        return Ok(false);
+    };
+    let allowed = Permissions::ADMINISTRATOR | Permissions::MANAGE_CHANNELS;
+    if member.permissions.is_some_and(|p| p.intersects(allowed)) {
+        return Ok(true);
+    }
+    let roles = ctx.settings.roles(guild_id)?;
+    Ok(member.roles.iter().any(|r| roles.contains(&RoleId(*r))))
+}
+
+/// Fetches the bot's member of the guild and updates its roles in the cache.
+///
+/// The gateway only sends member updates with the privileged `GUILD_MEMBERS` intent, so the
+/// member is refreshed before the permissions of the bot are checked.
+async fn refresh_current_member(ctx: &Context, guild_id: GuildId) {
+    let Some(user_id) = ctx.cache.current_user().map(|user| user.id) else {
+        return;
+    };
+    let member = async {
+        let resp = ctx.client.guild_member(guild_id.0, user_id).await?;
+        Ok::<_, Error>(resp.model().await?)
+    };
+    match member.await {
+        Ok(member) => ctx.cache.update(&MemberChunk {
+            chunk_count: 1,
+            chunk_index: 0,
+            guild_id: guild_id.0,
+            members: vec![member],
+            nonce: None,
+            not_found: vec![],
+            presences: vec![],
+        }),
+        Err(e) => tracing::error!("{e}"),
+    }
+}
+
+/// Returns the permissions the bot is missing in the channel to deliver notifications.
+///
+/// Returns `None` if the permissions can't be computed from the cache.
+pub fn missing_permissions(ctx: &Context, channel_id: ChannelId) -> Option<Permissions> {
+    let user_id = ctx.cache.current_user()?.id;
+    let is_thread = ctx.cache.channel(*channel_id)?.kind.is_thread();
+    let permissions = ctx
+        .cache
+        .permissions()
+        .in_channel(user_id, *channel_id)
+        .ok()?;
+
+    let send = if is_thread {
+        Permissions::SEND_MESSAGES_IN_THREADS
+    } else {
+        Permissions::SEND_MESSAGES
+    };
+    let required = Permissions::VIEW_CHANNEL | send | Permissions::EMBED_LINKS;
+    Some(required.difference(permissions))
+}
+
+/// Returns a warning if the bot is missing permissions in the channel.
+fn permissions_warning(ctx: &Context, channel_id: ChannelId, locale: Locale) -> Option<String> {
+    let missing = missing_permissions(ctx, channel_id).filter(|p| !p.is_empty())?;
+    let names = missing
+        .iter_names()
+        .map(|(name, _)| format!("`{name}`"))
+        .collect::<Vec<_>>();
+    Some(locale.format(
+        ":warning: The bot is missing the permission(s) {permissions} in {channel}.",
+        &[
+            ("permissions", &names.join(", ")),
+            ("channel", &format!("<#{channel_id}>")),
+        ],
+    ))
+}
+
+/// Returns a warning if crossposting is enabled for a channel that is not an announcement channel.
+pub(super) fn crosspost_warning(
+    ctx: &Context,
+    channel_id: ChannelId,
+    crosspost: bool,
+    locale: Locale,
+) -> Option<&'static str> {
+    let kind = ctx.cache.channel(*channel_id).map(|c| c.kind)?;
+    (crosspost && kind != ChannelType::GuildAnnouncement).then(|| {
+        locale.tr(":warning: Notifications are only crossposted in announcement channels.")
+    })
+}
+
+/// Appends the delivery failures of a channel.
+fn push_failure(content: &mut String, failure: &DeliveryFailure, locale: Locale) {
+    let count = failure.failures.to_string();
+    let line = if failure.paused {
+        locale.format(
+            ":pause_button: Paused after {count} failed deliveries",
+            &[("count", &count)],
+        )
+    } else {
+        locale.format(":warning: {count} failed deliveries", &[("count", &count)])
+    };
+    _ = writeln!(content, "{line}");
+    let time = util::discord_timestamp(failure.last_failure, TimestampStyle::Relative);
+    let line = locale.format(
+        "Last error {time}: `{error}`",
+        &[("time", &time), ("error", &failure.last_error)],
+    );
+    _ = writeln!(content, "{line}");
+}
+
+/// Looks up the names of the subscribed mods.
+///
+/// Mods deleted on mod.io are missing from the returned names.
+async fn mod_names<'a, I>(ctx: &Context, mods: I) -> Result<HashMap<u64, String>, Error>
+where
+    I: IntoIterator<Item = &'a ModSubscription>,
+{
+    let mut grouped = BTreeMap::<GameId, Vec<_>>::new();
+    for sub in mods {
+        grouped.entry(sub.game).or_default().push(sub.mod_id.0);
+    }
+
+    let mut st = grouped
+        .into_iter()
+        .map(|(GameId(game), mods)| async move {
+            let filter = Id::_in(mods);
+            ctx.modio.game(game).mods().search(filter).collect().await
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    let mut names = HashMap::new();
+    while let Some(list) = st.try_next().await? {
+        names.extend(list.into_iter().map(|m| (m.id.get(), m.name)));
+    }
+    Ok(names)
+}
+
+/// Appends the subscribed mod with its name if the mod still exists.
+fn push_mod(content: &mut String, names: &HashMap<u64, String>, mod_id: &ModId) {
+    match names.get(&mod_id.get()) {
+        Some(name) => _ = write!(content, "`{mod_id}.` {name}"),
+        None => _ = write!(content, "`{mod_id}.`"),
+    }
+}
+
+/// Appends the pause state to the content if the subscription is paused.
+fn push_paused<W: Write>(content: &mut W, paused_until: Option<i64>, now: i64, locale: Locale) {
+    match paused_until.filter(|t| *t > now) {
+        Some(PAUSED_INDEFINITELY) => _ = write!(content, " :pause_button: {}", locale.tr("Paused")),
+        Some(until) => {
+            let until = util::discord_timestamp(until, TimestampStyle::DateTime);
+            let paused = locale.format("Paused until {until}", &[("until", &until)]);
+            _ = write!(content, " :pause_button: {paused}");
+        }
+        None => {}
+    }
+}
+
+/// Appends the invalid tags and the available tags of the game to the content.
+pub(super) fn push_invalid_tags<'a, I>(
+    content: &mut String,
+    invalid: I,
+    game_tags: &Tags,
+    locale: Locale,
+) where
+    I: Iterator<Item = &'a String>,
+{
+    content.push_str(locale.tr("Invalid tag(s): "));
+    push_tags(content, invalid);
+
+    content.push('\n');
+    content.push_str(locale.tr("Available tags: "));
+    push_tags(content, game_tags.iter());
+}
+
 pub async fn handle_command(
     ctx: &Context,
     interaction: &Interaction,
     command: &CommandData,
 ) -> Result<(), Error> {
-    if let Some(("game", value)) = command.autocomplete() {
-        return autocomplete_games(ctx, interaction, value).await;
+    match command.autocomplete() {
+        Some(("game", value)) => return autocomplete_games(ctx, interaction, value).await,
+        Some(("subscription", value)) => {
+            return edit::autocomplete_subscriptions(ctx, interaction, value).await;
+        }
+        _ => {}
+    }
+
+    if !can_manage(ctx, interaction)? {
+        let locale = Locale::from_interaction(interaction);
+        let data = locale
+            .tr(":no_entry: You are not allowed to manage the subscriptions.")
+            .into_ephemeral();
+        return create_response(ctx, interaction, data).await;
     }
 
     match command.subcommand() {
         Some(("overview", _)) => overview(ctx, interaction).await,
         Some(("list", _)) => list(ctx, interaction).await,
         Some(("add", opts)) => subscribe(ctx, interaction, opts).await,
+        Some(("setup", opts)) => setup::setup(ctx, interaction, opts).await,
         Some(("rm", opts)) => unsubscribe(ctx, interaction, opts).await,
+        Some(("edit", opts)) => edit::edit(ctx, interaction, opts).await,
+        Some(("mod", opts)) => mod_(ctx, interaction, opts).await,
         Some(("mods", opts)) => mods(ctx, interaction, opts).await,
         Some(("users", opts)) => users(ctx, interaction, opts).await,
+        Some(("export", _)) => export::export(ctx, interaction).await,
+        Some(("import", opts)) => export::import(ctx, interaction, command, opts).await,
+        Some(("pause", opts)) => pause(ctx, interaction, opts, true).await,
+        Some(("resume", opts)) => pause(ctx, interaction, opts, false).await,
+        Some(("test", opts)) => test(ctx, interaction, opts).await,
+        Some(("copy", opts)) => transfer(ctx, interaction, opts, false).await,
+        Some(("move", opts)) => transfer(ctx, interaction, opts, true).await,
+        Some(("history", opts)) => history(ctx, interaction, opts).await,
+        Some(("template", opts)) => template::template(ctx, interaction, opts).await,
         _ => Ok(()),
     }
 }
@@ -147,20 +567,27 @@ pub async fn handle_command(
 async fn overview(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
     let guild_id = interaction.guild_id().unwrap();
 
-    let (subs, excluded_mods, excluded_users) = ctx.subscriptions.list_for_overview(guild_id)?;
+    let (mut subs, excluded_mods, excluded_users) =
+        ctx.subscriptions.list_for_overview(guild_id)?;
+    let mut mods = ctx.subscriptions.list_mods_for_guild(guild_id)?;
 
-    if subs.is_empty() && excluded_mods.is_empty() && excluded_users.is_empty() {
-        let data = "No subscriptions found.".into_ephemeral();
+    let locale = Locale::from_interaction(interaction);
+    if subs.is_empty() && mods.is_empty() && excluded_mods.is_empty() && excluded_users.is_empty() {
+        let data = locale.tr("No subscriptions found.").into_ephemeral();
         return create_response(ctx, interaction, data).await;
     }
 
     defer_ephemeral(ctx, interaction).await?;
+    refresh_current_member(ctx, guild_id).await;
+
+    let failures = ctx.subscriptions.failures(guild_id)?;
 
     // Collect all game ids to fetch from modio.
     let mut game_ids = subs
         .values()
         .flatten()
-        .map(|(g, _, _, _)| g)
+        .map(|s| &s.game)
+        .chain(mods.values().flatten().map(|m| &m.game))
         .chain(excluded_mods.keys().map(|(g, _)| g))
         .chain(excluded_users.keys().map(|(g, _)| g))
         .collect::<Vec<_>>();
@@ -175,26 +602,54 @@ async fn overview(ctx: &Context, interaction: &Interaction) -> Result<(), Error>
         .map(|g| (g.id.get(), g.name))
         .collect::<HashMap<_, _>>();
 
-    let mut embed = EmbedBuilder::new().title("Subscriptions");
+    let mod_names = mod_names(ctx, mods.values().flatten()).await?;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let now = util::current_timestamp() as i64;
+
+    let mut embed = EmbedBuilder::new().title(locale.tr("Subscriptions"));
+
+    let mut channels = subs.keys().chain(mods.keys()).copied().collect::<Vec<_>>();
+    channels.sort_unstable();
+    channels.dedup();
 
     let mut content = String::new();
-    for (channel_id, subs) in subs {
-        _ = writeln!(&mut content, "__Channel:__ <#{channel_id}>");
-        for (game_id, tags, evts, explicit) in subs {
-            if let Some(game) = games.get(&game_id.get()) {
-                _ = write!(&mut content, "`{game_id}.` {game}");
+    for channel_id in channels {
+        _ = writeln!(
+            &mut content,
+            "__{}:__ <#{channel_id}>",
+            locale.tr("Channel")
+        );
+        if let Some(warning) = permissions_warning(ctx, channel_id, locale) {
+            _ = writeln!(&mut content, "{warning}");
+        }
+        if let Some(failure) = failures.get(&channel_id) {
+            push_failure(&mut content, failure, locale);
+        }
+        for sub in subs.remove(&channel_id).unwrap_or_default() {
+            if let Some(game) = games.get(&sub.game.get()) {
+                _ = write!(&mut content, "`{}.` {game}", sub.game);
             } else {
-                _ = write!(&mut content, "{game_id}");
+                _ = write!(&mut content, "{}", sub.game);
             }
-            content.push_str(evts.to_suffix());
+            content.push_str(sub.events.to_suffix());
 
-            if !tags.is_empty() {
-                content.push_str(" | Tags: ");
-                push_tags(&mut content, tags.iter());
+            if !sub.tags.is_empty() {
+                _ = write!(&mut content, " | {}: ", locale.tr("Tags"));
+                push_tags(&mut content, sub.tags.iter());
             }
-            if !explicit {
+            if !sub.explicit {
                 content.push_str(" :underage:");
             }
+            push_paused(&mut content, sub.paused_until, now, locale);
+            content.push('\n');
+        }
+        for sub in mods.remove(&channel_id).unwrap_or_default() {
+            push_mod(&mut content, &mod_names, &sub.mod_id);
+            if let Some(game) = games.get(&sub.game.get()) {
+                _ = write!(&mut content, " ({game})");
+            }
+            push_paused(&mut content, sub.paused_until, now, locale);
             content.push('\n');
         }
         content.push('\n');
@@ -203,15 +658,15 @@ async fn overview(ctx: &Context, interaction: &Interaction) -> Result<(), Error>
 
     if !excluded_mods.is_empty() {
         embed = embed.field(EmbedFieldBuilder::new(
-            "Muted mods",
-            to_content(&games, excluded_mods),
+            locale.tr("Muted mods"),
+            to_content(&games, excluded_mods, locale),
         ));
     }
 
     if !excluded_users.is_empty() {
         embed = embed.field(EmbedFieldBuilder::new(
-            "Muted users",
-            to_content(&games, excluded_users),
+            locale.tr("Muted users"),
+            to_content(&games, excluded_users, locale),
         ));
     }
     ctx.interaction()
@@ -222,7 +677,7 @@ async fn overview(ctx: &Context, interaction: &Interaction) -> Result<(), Error>
     Ok(())
 }
 
-fn to_content<I, E, D>(games: &HashMap<u64, String>, excluded: I) -> String
+fn to_content<I, E, D>(games: &HashMap<u64, String>, excluded: I, locale: Locale) -> String
 where
     I: IntoIterator<Item = ((GameId, ChannelId), E)>,
     E: IntoIterator<Item = D>,
@@ -238,7 +693,11 @@ where
 
     let mut content = String::new();
     for (channel_id, entries) in excluded {
-        _ = writeln!(&mut content, "__Channel:__ <#{channel_id}>");
+        _ = writeln!(
+            &mut content,
+            "__{}:__ <#{channel_id}>",
+            locale.tr("Channel")
+        );
         for (game_id, items) in entries {
             if let Some(game) = games.get(&game_id.get()) {
                 _ = write!(&mut content, "`{game_id}.` {game}: ");
@@ -261,42 +720,66 @@ where
 async fn list(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
     let channel_id = interaction.channel_id().unwrap();
     let subs = ctx.subscriptions.list_for_channel(channel_id)?;
+    let mods = ctx.subscriptions.list_mods(channel_id)?;
 
-    if subs.is_empty() {
-        let data = "No subscriptions found.".into_ephemeral();
+    let locale = Locale::from_interaction(interaction);
+    if subs.is_empty() && mods.is_empty() {
+        let data = locale.tr("No subscriptions found.").into_ephemeral();
         return create_response(ctx, interaction, data).await;
     }
 
     defer_ephemeral(ctx, interaction).await?;
 
-    let filter = Id::_in(subs.iter().map(|s| s.0).collect::<Vec<_>>());
+    let game_ids = subs
+        .iter()
+        .map(|s| s.game)
+        .chain(mods.iter().map(|m| m.game));
+    let filter = Id::_in(game_ids.collect::<Vec<_>>());
     let list = ctx.modio.games().search(filter).collect().await?;
     let games = list
         .into_iter()
         .map(|g| (g.id, g.name))
         .collect::<HashMap<_, _>>();
 
+    #[allow(clippy::cast_possible_wrap)]
+    let now = util::current_timestamp() as i64;
+
     let mut content = String::new();
-    for (game_id, tags, evts, explicit) in subs {
-        let Some(name) = games.get(&game_id) else {
+    for sub in subs {
+        let Some(name) = games.get(&sub.game) else {
             continue;
         };
-        _ = write!(&mut content, "`{game_id}.` {name}");
+        _ = write!(&mut content, "`{}.` {name}", sub.game);
 
-        content.push_str(evts.to_suffix());
+        content.push_str(sub.events.to_suffix());
 
-        if !tags.is_empty() {
-            content.push_str(" | Tags: ");
-            push_tags(&mut content, tags.iter());
+        if !sub.tags.is_empty() {
+            _ = write!(&mut content, " | {}: ", locale.tr("Tags"));
+            push_tags(&mut content, sub.tags.iter());
         }
-        if !explicit {
+        if !sub.explicit {
             content.push_str(" :underage:");
         }
+        push_paused(&mut content, sub.paused_until, now, locale);
         content.push('\n');
     }
 
+    if !mods.is_empty() {
+        let mod_names = mod_names(ctx, &mods).await?;
+
+        _ = writeln!(&mut content, "\n__{}:__", locale.tr("Mods"));
+        for sub in mods {
+            push_mod(&mut content, &mod_names, &sub.mod_id);
+            if let Some(name) = games.get(&sub.game) {
+                _ = write!(&mut content, " ({name})");
+            }
+            push_paused(&mut content, sub.paused_until, now, locale);
+            content.push('\n');
+        }
+    }
+
     let embed = EmbedBuilder::new()
-        .title("Subscriptions")
+        .title(locale.tr("Subscriptions"))
         .description(content)
         .build();
 
@@ -308,6 +791,7 @@ async fn list(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::too_many_lines)]
 async fn subscribe(
     ctx: &Context,
     interaction: &Interaction,
@@ -317,16 +801,19 @@ async fn subscribe(
     let mut tags = None;
     let mut evts = Events::ALL;
     let mut explicit = None;
+    let mut crosspost = None;
+    let mut style = None;
 
     defer_ephemeral(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
 
     for opt in opts {
         match &opt.value {
             CommandOptionValue::String(s) if opt.name == "game" => {
-                game = search_game(ctx, s).await?;
+                game = search_game(ctx, interaction.guild_id(), s).await?;
 
                 if game.is_none() {
-                    let content = "Game not found.";
+                    let content = locale.tr("Game not found.");
                     return update_response_content(ctx, interaction, content).await;
                 }
             }
@@ -344,6 +831,12 @@ async fn subscribe(
             CommandOptionValue::Boolean(v) if opt.name == "explicit" => {
                 explicit = Some(*v);
             }
+            CommandOptionValue::Boolean(v) if opt.name == "crosspost" => {
+                crosspost = Some(*v);
+            }
+            CommandOptionValue::String(s) if opt.name == "style" => {
+                style = s.parse().ok();
+            }
             _ => {}
         }
     }
@@ -353,10 +846,7 @@ async fn subscribe(
         .api_access_options
         .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
     {
-        let content = format!(
-            ":no_entry: Third party API access is disabled for '{}' but is required for subscriptions.",
-            game.name
-        );
+        let content = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
         return update_response_content(ctx, interaction, &content).await;
     }
 
@@ -374,12 +864,15 @@ async fn subscribe(
         .unwrap_or_default();
 
     if !sub_tags.is_subset(&game_tags) {
-        let mut content = format!("Failed to subscribe to '{}'.\n", game.name);
-        content.push_str("Invalid tag(s): ");
-        push_tags(&mut content, sub_tags.difference(&game_tags));
-
-        content.push_str("\nAvailable tags: ");
-        push_tags(&mut content, game_tags.iter());
+        let args = [("game", game.name.as_str())];
+        let mut content = locale.format("Failed to subscribe to '{game}'.", &args);
+        content.push('\n');
+        push_invalid_tags(
+            &mut content,
+            sub_tags.difference(&game_tags),
+            &game_tags,
+            locale,
+        );
 
         return update_response_content(ctx, interaction, &content).await;
     }
@@ -388,16 +881,37 @@ async fn subscribe(
     let explicit = explicit.unwrap_or(true);
 
     let game_id = GameId(game.id);
+    let change = SubscriptionChange {
+        tags: sub_tags,
+        events: evts,
+        explicit,
+        crosspost,
+        style,
+    };
     let ret = ctx
         .subscriptions
-        .add(game_id, channel_id, sub_tags, guild_id, evts, explicit);
+        .add(game_id, channel_id, guild_id, change, actor(interaction));
 
     let content: Cow<'_, str> = match ret {
-        Ok(()) => format!("Subscribed to '{}'.", game.name).into(),
+        Ok(entry) => {
+            announce_changes(ctx, entry.as_slice()).await;
+            refresh_current_member(ctx, guild_id).await;
+            let mut content = locale.format("Subscribed to '{game}'.", &[("game", &game.name)]);
+            if let Some(warning) = permissions_warning(ctx, channel_id, locale) {
+                content.push('\n');
+                content.push_str(&warning);
+            }
+            let crosspost = crosspost.unwrap_or_default();
+            if let Some(warning) = crosspost_warning(ctx, channel_id, crosspost, locale) {
+                content.push('\n');
+                content.push_str(warning);
+            }
+            content.into()
+        }
         Err(e) => {
             tracing::error!("{e}");
 
-            "Failed to add subscription.".into()
+            locale.tr("Failed to add subscription.").into()
         }
     };
 
@@ -414,14 +928,15 @@ async fn unsubscribe(
     let mut evts = Events::ALL;
 
     defer_ephemeral(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
 
     for opt in opts {
         match &opt.value {
             CommandOptionValue::String(s) if opt.name == "game" => {
-                game = search_game(ctx, s).await?;
+                game = search_game(ctx, interaction.guild_id(), s).await?;
 
                 if game.is_none() {
-                    let content = "Game not found.";
+                    let content = locale.tr("Game not found.");
                     return update_response_content(ctx, interaction, content).await;
                 }
             }
@@ -454,12 +969,15 @@ async fn unsubscribe(
         .unwrap_or_default();
 
     if !sub_tags.is_subset(&game_tags) {
-        let mut content = format!("Failed to unsubscribe from '{}'.\n", game.name);
-        content.push_str("Invalid tag(s): ");
-        push_tags(&mut content, sub_tags.difference(&game_tags));
-
-        content.push_str("\nAvailable tags: ");
-        push_tags(&mut content, game_tags.iter());
+        let args = [("game", game.name.as_str())];
+        let mut content = locale.format("Failed to unsubscribe from '{game}'.", &args);
+        content.push('\n');
+        push_invalid_tags(
+            &mut content,
+            sub_tags.difference(&game_tags),
+            &game_tags,
+            locale,
+        );
 
         return update_response_content(ctx, interaction, &content).await;
     }
@@ -468,14 +986,18 @@ async fn unsubscribe(
     let game_id = GameId(game.id);
     let ret = ctx
         .subscriptions
-        .remove(game_id, channel_id, sub_tags, evts);
+        .remove(game_id, channel_id, sub_tags, evts, actor(interaction));
 
     let content: Cow<'_, str> = match ret {
-        Ok(()) => format!("Unsubscribed from '{}'.", game.name).into(),
+        Ok(entry) => {
+            announce_changes(ctx, entry.as_slice()).await;
+            let args = [("game", game.name.as_str())];
+            locale.format("Unsubscribed from '{game}'.", &args).into()
+        }
         Err(e) => {
             tracing::error!("{e}");
 
-            "Failed to remove subscription.".into()
+            locale.tr("Failed to remove subscription.").into()
         }
     };
 
@@ -498,6 +1020,221 @@ async fn mods(
     }
 }
 
+/// `/subs mod`
+async fn mod_(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    match opts.subcommand() {
+        Some(("list", _)) => mod_list(ctx, interaction).await,
+        Some(("add", opts)) => mod_add(ctx, interaction, opts).await,
+        Some(("rm", opts)) => mod_rm(ctx, interaction, opts).await,
+        _ => Ok(()),
+    }
+}
+
+/// `/subs mod list`
+async fn mod_list(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+    let channel_id = interaction.channel_id().unwrap();
+    let subscribed = ctx.subscriptions.list_mods(channel_id)?;
+    let locale = Locale::from_interaction(interaction);
+    if subscribed.is_empty() {
+        let content = locale.tr("No mod is subscribed.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let mut grouped = BTreeMap::<GameId, Vec<_>>::new();
+    for sub in subscribed {
+        grouped.entry(sub.game).or_default().push(sub);
+    }
+
+    let mut st = grouped
+        .into_iter()
+        .map(|(GameId(game), mods)| async move {
+            let filter = Id::_in(mods.iter().map(|m| m.mod_id.0).collect::<Vec<_>>());
+            let (game, found) = tokio::try_join!(
+                ctx.modio.game(game).get(),
+                ctx.modio.game(game).mods().search(filter).collect(),
+            )?;
+            Ok::<_, modio::Error>((game, found, mods))
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    #[allow(clippy::cast_possible_wrap)]
+    let now = util::current_timestamp() as i64;
+
+    let mut buf = ContentBuilder::new(4000);
+    while let Some((game, found, mods)) = st.try_next().await? {
+        _ = writeln!(&mut buf, "**{}**", game.name);
+        for sub in mods {
+            // Mods deleted on mod.io are listed with their id only.
+            match found.iter().find(|m| m.id == sub.mod_id.0) {
+                Some(m) => _ = write!(&mut buf, "`{}.` {}", m.id, m.name),
+                None => _ = write!(&mut buf, "`{}.`", sub.mod_id),
+            }
+            push_paused(&mut buf, sub.paused_until, now, locale);
+            _ = writeln!(&mut buf);
+        }
+        _ = writeln!(&mut buf);
+    }
+
+    let title = locale.tr("Subscribed Mods");
+    update_response_from_content(ctx, interaction, title, &buf.content).await
+}
+
+/// `/subs mod add <game> <mod> [crosspost]`
+async fn mod_add(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let (game, mod_filter) = game_mod_options(opts);
+    let crosspost = opts.iter().find_map(|opt| match opt.value {
+        CommandOptionValue::Boolean(v) if opt.name == "crosspost" => Some(v),
+        _ => None,
+    });
+    let game = game.expect("required option");
+    let mod_filter = mod_filter.expect("required option").into_filter();
+
+    let locale = Locale::from_interaction(interaction);
+    let guild_id = interaction.guild_id();
+    let (game, mod_) = match find_game_mod(ctx, guild_id, game, mod_filter).await? {
+        (None, _) => {
+            let content = locale.tr("Game not found.");
+            return update_response_content(ctx, interaction, content).await;
+        }
+        (_, None) => {
+            let content = locale.tr("Mod not found.");
+            return update_response_content(ctx, interaction, content).await;
+        }
+        (Some(game), Some(mod_)) => (game, mod_),
+    };
+
+    if !game
+        .api_access_options
+        .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
+    {
+        let content = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let channel_id = interaction.channel_id().unwrap();
+    let guild_id = interaction.guild_id().unwrap();
+    let game_id = GameId(game.id);
+    let mod_id = ModId(mod_.id);
+
+    let ret = ctx.subscriptions.add_mod(
+        game_id,
+        channel_id,
+        guild_id,
+        &mod_id,
+        crosspost,
+        actor(interaction),
+    );
+
+    let args = [("mod", mod_.name.as_str())];
+    let mut content = match ret {
+        Ok(Some(entry)) => {
+            announce_changes(ctx, &[entry]).await;
+            locale.format("Subscribed to the mod '{mod}'.", &args)
+        }
+        Ok(None) => locale.format("The mod '{mod}' is already subscribed.", &args),
+        Err(e) => {
+            tracing::error!("{e}");
+            locale.format("Failed to subscribe to the mod '{mod}'.", &args)
+        }
+    };
+    refresh_current_member(ctx, guild_id).await;
+    if let Some(warning) = permissions_warning(ctx, channel_id, locale) {
+        content.push('\n');
+        content.push_str(&warning);
+    }
+    let crosspost = crosspost.unwrap_or_default();
+    if let Some(warning) = crosspost_warning(ctx, channel_id, crosspost, locale) {
+        content.push('\n');
+        content.push_str(warning);
+    }
+
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/subs mod rm <game> <mod>`
+///
+/// A subscribed mod id is removed without a lookup so that mods deleted on mod.io can still be
+/// unsubscribed.
+async fn mod_rm(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let (game, mod_filter) = game_mod_options(opts);
+    let game = game.expect("required option");
+    let mod_filter = mod_filter.expect("required option");
+
+    let locale = Locale::from_interaction(interaction);
+    let channel_id = interaction.channel_id().unwrap();
+    let subscribed = ctx.subscriptions.list_mods(channel_id)?;
+
+    let stored = mod_filter
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| subscribed.into_iter().find(|sub| sub.mod_id.get() == id));
+    let (game_id, mod_id, name) = if let Some(sub) = stored {
+        let name = sub.mod_id.to_string();
+        (sub.game, sub.mod_id, name)
+    } else {
+        let guild_id = interaction.guild_id();
+        let mod_filter = mod_filter.into_filter();
+        match find_game_mod(ctx, guild_id, game, mod_filter).await? {
+            (None, _) => {
+                let content = locale.tr("Game not found.");
+                return update_response_content(ctx, interaction, content).await;
+            }
+            (_, None) => {
+                let content = locale.tr("Mod not found.");
+                return update_response_content(ctx, interaction, content).await;
+            }
+            (Some(game), Some(mod_)) => (GameId(game.id), ModId(mod_.id), mod_.name),
+        }
+    };
+
+    let ret = ctx
+        .subscriptions
+        .remove_mod(game_id, channel_id, &mod_id, actor(interaction));
+
+    let args = [("mod", name.as_str())];
+    let content = match ret {
+        Ok(Some(entry)) => {
+            announce_changes(ctx, &[entry]).await;
+            locale.format("Unsubscribed from the mod '{mod}'.", &args)
+        }
+        Ok(None) => locale.format("The mod '{mod}' is not subscribed.", &args),
+        Err(e) => {
+            tracing::error!("{e}");
+            locale.format("Failed to unsubscribe from the mod '{mod}'.", &args)
+        }
+    };
+
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// Returns the values of the `game` and `mod` options.
+fn game_mod_options(opts: &[CommandDataOption]) -> (Option<&String>, Option<&String>) {
+    let mut game = None;
+    let mut mod_ = None;
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => game = Some(s),
+            CommandOptionValue::String(s) if opt.name == "mod" => mod_ = Some(s),
+            _ => {}
+        }
+    }
+    (game, mod_)
+}
+
 /// `/subs mods muted`
 async fn mods_muted(
     ctx: &Context,
@@ -506,10 +1243,11 @@ async fn mods_muted(
 ) -> Result<(), Error> {
     let channel_id = interaction.channel_id().unwrap();
     let excluded = ctx.subscriptions.list_excluded_mods(channel_id)?;
+    let locale = Locale::from_interaction(interaction);
 
     let muted = match excluded.len() {
         0 => {
-            let content = "No mod is muted.";
+            let content = locale.tr("No mod is muted.");
             return update_response_content(ctx, interaction, content).await;
         }
         1 => {
@@ -547,7 +1285,8 @@ async fn mods_muted(
         }
     };
 
-    update_response_from_content(ctx, interaction, "Muted Mods", &muted.content).await
+    let title = locale.tr("Muted Mods");
+    update_response_from_content(ctx, interaction, title, &muted.content).await
 }
 
 /// `/subs mods mute <game> <mod>`
@@ -556,44 +1295,41 @@ async fn mods_mute(
     interaction: &Interaction,
     opts: &[CommandDataOption],
 ) -> Result<(), Error> {
-    let mut game_filter = None;
-    let mut mod_filter = None;
-    for opt in opts {
-        match &opt.value {
-            CommandOptionValue::String(s) if opt.name == "game" => {
-                game_filter = Some(s.into_filter());
-            }
-            CommandOptionValue::String(s) if opt.name == "mod" => {
-                mod_filter = Some(s.into_filter());
-            }
-            _ => {}
-        }
-    }
-
-    let game_filter = game_filter.expect("required option");
-    let mod_filter = mod_filter.expect("required option");
+    let (game, mod_filter) = game_mod_options(opts);
+    let game = game.expect("required option");
+    let mod_filter = mod_filter.expect("required option").into_filter();
 
-    let game_mod = find_game_mod(&ctx.modio, game_filter, mod_filter).await?;
+    let locale = Locale::from_interaction(interaction);
+    let game_mod = find_game_mod(ctx, interaction.guild_id(), game, mod_filter).await?;
 
     let content: Cow<'_, str> = match game_mod {
-        (None, _) => "Game not found.".into(),
-        (_, None) => "Mod not found.".into(),
+        (None, _) => locale.tr("Game not found.").into(),
+        (_, None) => locale.tr("Mod not found.").into(),
         (Some(game), Some(mod_)) => {
             let channel_id = interaction.channel_id().unwrap();
             let guild_id = interaction.guild_id().unwrap();
 
             let game_id = GameId(game.id);
             let mod_id = ModId(mod_.id);
-            let ret = ctx
-                .subscriptions
-                .mute_mod(game_id, channel_id, guild_id, mod_id);
-
-            let content = if let Err(e) = ret {
-                tracing::error!("{e}");
+            let ret = ctx.subscriptions.mute_mod(
+                game_id,
+                channel_id,
+                guild_id,
+                mod_id,
+                actor(interaction),
+            );
+
+            let args = [("mod", mod_.name.as_str())];
+            let content = match ret {
+                Ok(entry) => {
+                    announce_changes(ctx, entry.as_slice()).await;
+                    locale.format("The mod '{mod}' is now muted.", &args)
+                }
+                Err(e) => {
+                    tracing::error!("{e}");
 
-                format!("Failed to mute '{}'.", mod_.name)
-            } else {
-                format!("The mod '{}' is now muted.", mod_.name)
+                    locale.format("Failed to mute '{mod}'.", &args)
+                }
             };
 
             content.into()
@@ -609,41 +1345,36 @@ async fn mods_unmute(
     interaction: &Interaction,
     opts: &[CommandDataOption],
 ) -> Result<(), Error> {
-    let mut game_filter = None;
-    let mut mod_filter = None;
-    for opt in opts {
-        match &opt.value {
-            CommandOptionValue::String(s) if opt.name == "game" => {
-                game_filter = Some(s.into_filter());
-            }
-            CommandOptionValue::String(s) if opt.name == "mod" => {
-                mod_filter = Some(s.into_filter());
-            }
-            _ => {}
-        }
-    }
-
-    let game_filter = game_filter.expect("required option");
-    let mod_filter = mod_filter.expect("required option");
+    let (game, mod_filter) = game_mod_options(opts);
+    let game = game.expect("required option");
+    let mod_filter = mod_filter.expect("required option").into_filter();
 
-    let game_mod = find_game_mod(&ctx.modio, game_filter, mod_filter).await?;
+    let locale = Locale::from_interaction(interaction);
+    let game_mod = find_game_mod(ctx, interaction.guild_id(), game, mod_filter).await?;
 
     let content: Cow<'_, str> = match game_mod {
-        (None, _) => "Game not found.".into(),
-        (_, None) => "Mod not found.".into(),
+        (None, _) => locale.tr("Game not found.").into(),
+        (_, None) => locale.tr("Mod not found.").into(),
         (Some(game), Some(mod_)) => {
             let channel_id = interaction.channel_id().unwrap();
 
             let game_id = GameId(game.id);
             let mod_id = ModId(mod_.id);
-            let ret = ctx.subscriptions.unmute_mod(game_id, channel_id, mod_id);
+            let ret = ctx
+                .subscriptions
+                .unmute_mod(game_id, channel_id, mod_id, actor(interaction));
 
-            let content = if let Err(e) = ret {
-                tracing::error!("{e}");
+            let args = [("mod", mod_.name.as_str())];
+            let content = match ret {
+                Ok(entry) => {
+                    announce_changes(ctx, entry.as_slice()).await;
+                    locale.format("The mod '{mod}' is now unmuted.", &args)
+                }
+                Err(e) => {
+                    tracing::error!("{e}");
 
-                format!("Failed to unmute '{}'.", mod_.name)
-            } else {
-                format!("The mod '{}' is now unmuted.", mod_.name)
+                    locale.format("Failed to unmute '{mod}'.", &args)
+                }
             };
 
             content.into()
@@ -677,10 +1408,11 @@ async fn users_muted(
 ) -> Result<(), Error> {
     let channel_id = interaction.channel_id().unwrap();
     let excluded = ctx.subscriptions.list_excluded_users(channel_id)?;
+    let locale = Locale::from_interaction(interaction);
 
     let muted = match excluded.len() {
         0 => {
-            let content = "No user is muted.";
+            let content = locale.tr("No user is muted.");
             return update_response_content(ctx, interaction, content).await;
         }
         1 => {
@@ -713,7 +1445,8 @@ async fn users_muted(
         }
     };
 
-    update_response_from_content(ctx, interaction, "Muted Users", &muted.content).await
+    let title = locale.tr("Muted Users");
+    update_response_from_content(ctx, interaction, title, &muted.content).await
 }
 
 /// `/subs users mute <game> <username>`
@@ -722,12 +1455,12 @@ async fn users_mute(
     interaction: &Interaction,
     opts: &[CommandDataOption],
 ) -> Result<(), Error> {
-    let mut game_filter = None;
+    let mut game = None;
     let mut name = None;
     for opt in opts {
         match &opt.value {
             CommandOptionValue::String(s) if opt.name == "game" => {
-                game_filter = Some(s.into_filter());
+                game = Some(s);
             }
             CommandOptionValue::String(s) if opt.name == "name" => {
                 name = Some(s);
@@ -736,31 +1469,41 @@ async fn users_mute(
         }
     }
 
-    let game_filter = game_filter.expect("required option");
+    let game = game.expect("required option");
     let name = name.expect("required option");
 
-    let game = ctx.modio.games().search(game_filter).first().await?;
+    let locale = Locale::from_interaction(interaction);
+    let game = search_game(ctx, interaction.guild_id(), game).await?;
     let content: Cow<'_, str> = match game {
         Some(game) => {
             let guild_id = interaction.guild_id().unwrap();
             let channel_id = interaction.channel_id().unwrap();
 
             let game_id = GameId(game.id);
-            let ret = ctx
-                .subscriptions
-                .mute_user(game_id, channel_id, guild_id, name);
-
-            let content = if let Err(e) = ret {
-                tracing::error!("{e}");
+            let ret = ctx.subscriptions.mute_user(
+                game_id,
+                channel_id,
+                guild_id,
+                name,
+                actor(interaction),
+            );
+
+            let args = [("user", name.as_str()), ("game", &game.name)];
+            let content = match ret {
+                Ok(entry) => {
+                    announce_changes(ctx, entry.as_slice()).await;
+                    locale.format("The user '{user}' is now muted for '{game}'.", &args)
+                }
+                Err(e) => {
+                    tracing::error!("{e}");
 
-                format!("Failed to mute '{name}'.")
-            } else {
-                format!("The user '{name}' is now muted for '{}'.", game.name)
+                    locale.format("Failed to mute '{user}'.", &args)
+                }
             };
 
             content.into()
         }
-        None => "Game not found.".into(),
+        None => locale.tr("Game not found.").into(),
     };
 
     update_response_content(ctx, interaction, &content).await
@@ -772,12 +1515,12 @@ async fn users_unmute(
     interaction: &Interaction,
     opts: &[CommandDataOption],
 ) -> Result<(), Error> {
-    let mut game_filter = None;
+    let mut game = None;
     let mut name = None;
     for opt in opts {
         match &opt.value {
             CommandOptionValue::String(s) if opt.name == "game" => {
-                game_filter = Some(s.into_filter());
+                game = Some(s);
             }
             CommandOptionValue::String(s) if opt.name == "name" => {
                 name = Some(s);
@@ -786,43 +1529,515 @@ async fn users_unmute(
         }
     }
 
-    let game_filter = game_filter.expect("required option");
+    let game = game.expect("required option");
     let name = name.expect("required option");
 
-    let game = ctx.modio.games().search(game_filter).first().await?;
+    let locale = Locale::from_interaction(interaction);
+    let game = search_game(ctx, interaction.guild_id(), game).await?;
     let content: Cow<'_, str> = match game {
         Some(game) => {
             let channel_id = interaction.channel_id().unwrap();
 
             let game_id = GameId(game.id);
-            let ret = ctx.subscriptions.unmute_user(game_id, channel_id, name);
+            let ret = ctx
+                .subscriptions
+                .unmute_user(game_id, channel_id, name, actor(interaction));
 
-            let content = if let Err(e) = ret {
-                tracing::error!("{e}");
+            let args = [("user", name.as_str()), ("game", &game.name)];
+            let content = match ret {
+                Ok(entry) => {
+                    announce_changes(ctx, entry.as_slice()).await;
+                    locale.format("The user '{user}' is now unmuted for '{game}'.", &args)
+                }
+                Err(e) => {
+                    tracing::error!("{e}");
 
-                format!("Failed to unmute '{name}'.")
-            } else {
-                format!("The user '{name}' is now unmuted for '{}'.", game.name)
+                    locale.format("Failed to unmute '{user}'.", &args)
+                }
             };
 
             content.into()
         }
-        None => "Game not found.".into(),
+        None => locale.tr("Game not found.").into(),
+    };
+
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/subs pause [game] [duration]` and `/subs resume [game]`
+async fn pause(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+    pause: bool,
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    let mut game = None;
+    let mut duration = None;
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "game" => {
+                game = search_game(ctx, interaction.guild_id(), s).await?;
+
+                if game.is_none() {
+                    let content = locale.tr("Game not found.");
+                    return update_response_content(ctx, interaction, content).await;
+                }
+            }
+            CommandOptionValue::String(s) if opt.name == "duration" => {
+                let Some(value) = util::parse_duration(s) else {
+                    let content = locale.format(
+                        "Invalid duration `{duration}`, use e.g. `12h`, `3d` or `1w2d`.",
+                        &[("duration", s)],
+                    );
+                    return update_response_content(ctx, interaction, &content).await;
+                };
+                duration = Some(value);
+            }
+            _ => {}
+        }
+    }
+
+    let channel_id = interaction.channel_id().unwrap();
+    let game_id = game.as_ref().map(|g| GameId(g.id));
+    let actor = actor(interaction);
+
+    #[allow(clippy::cast_possible_wrap)]
+    let until = duration.map(|d| (util::current_timestamp() + d.as_secs()) as i64);
+
+    let entries = if pause {
+        ctx.subscriptions.pause(channel_id, game_id, until, actor)?
+    } else {
+        // Resuming the whole channel also resumes a channel paused after delivery failures.
+        if game_id.is_none() {
+            ctx.subscriptions.reset_failures(channel_id)?;
+        }
+        ctx.subscriptions.resume(channel_id, game_id, actor)?
     };
+    announce_changes(ctx, &entries).await;
 
+    let target = match &game {
+        Some(game) => format!("'{}'", game.name),
+        None => locale.tr("this channel").to_owned(),
+    };
+    let count = entries.len().to_string();
+    let until = until.map(|t| util::discord_timestamp(t, TimestampStyle::DateTime));
+    let args = [
+        ("count", count.as_str()),
+        ("target", &target),
+        ("until", until.as_deref().unwrap_or_default()),
+    ];
+    let content = match (pause, entries.len(), &until) {
+        (_, 0, _) => locale.format("No subscriptions of {target} changed.", &args),
+        (true, _, Some(_)) => locale.format(
+            "Paused {count} subscription(s) of {target} until {until}.",
+            &args,
+        ),
+        (true, _, None) => locale.format("Paused {count} subscription(s) of {target}.", &args),
+        (false, _, _) => locale.format("Resumed {count} subscription(s) of {target}.", &args),
+    };
     update_response_content(ctx, interaction, &content).await
 }
 
+/// `/subs test <game>`
+#[allow(clippy::too_many_lines)]
+async fn test(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let mut game = None;
+    for opt in opts {
+        if let CommandOptionValue::String(s) = &opt.value {
+            if opt.name == "game" {
+                game = search_game(ctx, interaction.guild_id(), s).await?;
+            }
+        }
+    }
+    let locale = Locale::from_interaction(interaction);
+    let Some(game) = game else {
+        let content = locale.tr("Game not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let channel_id = interaction.channel_id().unwrap();
+    let guild_id = interaction.guild_id().unwrap();
+    let game_id = GameId(game.id);
+    let subs = ctx
+        .subscriptions
+        .list_with_ids(channel_id)?
+        .into_iter()
+        .filter(|sub| sub.game == game_id)
+        .map(|sub| Subscription {
+            id: sub.id,
+            channel: channel_id,
+            guild: guild_id,
+            tags: sub.tags,
+            events: sub.events,
+            explicit: sub.explicit,
+            crosspost: sub.crosspost,
+            style: sub.style,
+        })
+        .collect::<Vec<_>>();
+
+    if subs.is_empty() {
+        let args = [("game", game.name.as_str())];
+        let content = locale.format("This channel is not subscribed to '{game}'.", &args);
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let excluded_mods = ctx
+        .subscriptions
+        .list_excluded_mods(channel_id)?
+        .into_iter()
+        .map(|(game_id, mods)| ((game_id, channel_id), mods))
+        .collect();
+    let excluded_users = ctx
+        .subscriptions
+        .list_excluded_users(channel_id)?
+        .into_iter()
+        .map(|(game_id, users)| ((game_id, channel_id), users))
+        .collect();
+
+    let filter = with_limit(TEST_MODS_LIMIT).order_by(DateUpdated::desc());
+    let mods = ctx
+        .modio
+        .game(game.id)
+        .mods()
+        .search(filter)
+        .first_page()
+        .await?;
+
+    let found = mods.iter().find_map(|m| {
+        [EventType::MOD_AVAILABLE, EventType::MODFILE_CHANGED]
+            .into_iter()
+            .find_map(|evt| {
+                subs.iter()
+                    .find(|sub| is_wanted(sub, game_id, m, &evt, &excluded_mods, &excluded_users))
+                    .map(|sub| (m, evt, sub))
+            })
+    });
+    let Some((mod_, evt, sub)) = found else {
+        let content = locale.format(
+            "None of the latest {limit} mods of '{game}' matches the subscriptions of this channel.",
+            &[("limit", &TEST_MODS_LIMIT.to_string()), ("game", &game.name)],
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    };
+
+    let template = ctx.subscriptions.subscription_template(sub.guild, sub.id)?;
+    let language = ctx.settings.language(guild_id)?.unwrap_or_default();
+    let (content, embed) =
+        create_mod_message(&game, mod_, &evt, template.as_ref(), sub.style, language);
+    let notice = language.tr("*This is a test notification.*");
+    let content = match content {
+        Some(content) => format!("{notice}\n{content}"),
+        None => notice.to_owned(),
+    };
+
+    let channel = format!("<#{channel_id}>");
+    let content = match deliver(ctx, channel_id, Some(&content), embed.as_slice()).await {
+        Ok(_) => {
+            ctx.subscriptions.reset_failures(channel_id)?;
+            let args = [("mod", mod_.name.as_str())];
+            locale.format("Sent a test notification for '{mod}'.", &args)
+        }
+        Err(e) if util::is_missing_permissions_error(e.kind()) => locale.format(
+            ":no_entry: The notification could not be sent to {channel}. \
+             The bot is missing the `Send Messages` or `Embed Links` permission.",
+            &[("channel", &channel)],
+        ),
+        Err(e) => locale.format(
+            ":no_entry: The notification could not be sent to {channel}: {error}",
+            &[("channel", &channel), ("error", &e.to_string())],
+        ),
+    };
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/subs copy <from> <to>` and `/subs move <from> <to>`
+async fn transfer(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+    move_subs: bool,
+) -> Result<(), Error> {
+    let mut from = None;
+    let mut to = None;
+    for opt in opts {
+        match (opt.name.as_str(), &opt.value) {
+            ("from", CommandOptionValue::Channel(id)) => from = Some(ChannelId(*id)),
+            ("to", CommandOptionValue::Channel(id)) => to = Some(ChannelId(*id)),
+            _ => {}
+        }
+    }
+    let (Some(from), Some(to)) = (from, to) else {
+        return Ok(());
+    };
+    let locale = Locale::from_interaction(interaction);
+    if from == to {
+        let data = locale
+            .tr("The channels must be different.")
+            .into_ephemeral();
+        return create_response(ctx, interaction, data).await;
+    }
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().unwrap();
+    let actor = actor(interaction);
+    let transfer = if move_subs {
+        ctx.subscriptions.move_to(guild_id, from, to, actor)?
+    } else {
+        ctx.subscriptions.copy(guild_id, from, to, actor)?
+    };
+    let from = format!("<#{from}>");
+    if transfer.is_empty() {
+        let content = locale.format(
+            "No subscriptions found in {channel}.",
+            &[("channel", &from)],
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    }
+    announce_changes(ctx, &transfer.entries).await;
+
+    let text = if move_subs {
+        "Moved {subs} subscription(s), {mods} mod subscription(s) and {mutes} mute(s) from {from} to {to}."
+    } else {
+        "Copied {subs} subscription(s), {mods} mod subscription(s) and {mutes} mute(s) from {from} to {to}."
+    };
+    let content = locale.format(
+        text,
+        &[
+            ("subs", &transfer.subscriptions.to_string()),
+            ("mods", &transfer.mods.to_string()),
+            ("mutes", &transfer.mutes.to_string()),
+            ("from", &from),
+            ("to", &format!("<#{to}>")),
+        ],
+    );
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// `/subs history [channel]`
+async fn history(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let channel_id = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::Channel(id) => Some(ChannelId(*id)),
+        _ => None,
+    });
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let guild_id = interaction.guild_id().unwrap();
+    let entries = ctx
+        .subscriptions
+        .history(guild_id, channel_id, HISTORY_LIMIT)?;
+
+    let locale = Locale::from_interaction(interaction);
+    if entries.is_empty() {
+        let content = locale.tr("No changes recorded.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let games = game_names(ctx, &entries).await?;
+
+    let mut buf = ContentBuilder::new(4000);
+    for entry in &entries {
+        _ = writeln!(&mut buf, "{}", format_entry(entry, &games, locale));
+    }
+
+    let title = locale.tr("Subscription History");
+    update_response_from_content(ctx, interaction, title, &buf.content).await
+}
+
+/// Announces the changes in the log channel of the guilds.
+pub async fn announce_changes(ctx: &Context, entries: &[AuditEntry]) {
+    let mut guilds = BTreeMap::<_, Vec<_>>::new();
+    for entry in entries {
+        guilds.entry(entry.guild).or_default().push(entry);
+    }
+
+    for (guild_id, entries) in guilds {
+        let channel_id = match ctx.settings.log_channel(guild_id) {
+            Ok(Some(channel_id)) => channel_id,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("{e}");
+                continue;
+            }
+        };
+        let games = match game_names(ctx, entries.iter().copied()).await {
+            Ok(games) => games,
+            Err(e) => {
+                tracing::warn!("failed to load games for the audit log: {e}");
+                HashMap::new()
+            }
+        };
+        let locale = guild_locale(ctx, guild_id);
+
+        let mut buf = ContentBuilder::new(4000);
+        for entry in entries {
+            _ = writeln!(&mut buf, "{}", format_entry(entry, &games, locale));
+        }
+        for content in buf {
+            let embed = EmbedBuilder::new()
+                .title(locale.tr("Subscription Changes"))
+                .description(content)
+                .build();
+
+            if let Err(e) = ctx
+                .client
+                .create_message(*channel_id)
+                .embeds(&[embed])
+                .await
+            {
+                tracing::warn!("failed to announce changes in #{channel_id}: {e}");
+            }
+        }
+    }
+}
+
+/// Notifies the guild owner that the notifications for the channel have been paused.
+///
+/// The notice is posted in the log channel of the guild or else sent as direct message.
+pub async fn announce_paused(ctx: &Context, guild_id: GuildId, channel_id: ChannelId, error: &str) {
+    let owner_id = match ctx.cache.guild(guild_id.0) {
+        Some(guild) => Some(guild.owner_id()),
+        None => match ctx.client.guild(guild_id.0).await {
+            Ok(resp) => resp.model().await.ok().map(|g| g.owner_id),
+            Err(e) => {
+                tracing::warn!("failed to load guild {guild_id}: {e}");
+                None
+            }
+        },
+    };
+
+    let locale = guild_locale(ctx, guild_id);
+    let mut content = locale.format(
+        "Notifications for {channel} have been paused after {count} failed deliveries because \
+         the bot is missing permissions in the channel.\n\
+         Last error: `{error}`\n\
+         Grant the bot the `Send Messages` and `Embed Links` permissions and use `/subs test` \
+         in the channel to resume the notifications.",
+        &[
+            ("channel", &format!("<#{channel_id}>")),
+            ("count", &MAX_PERMISSION_FAILURES.to_string()),
+            ("error", error),
+        ],
+    );
+
+    let log_channel = ctx.settings.log_channel(guild_id).unwrap_or_else(|e| {
+        tracing::error!("{e}");
+        None
+    });
+    let target = match (log_channel, owner_id) {
+        (Some(log_channel), owner_id) => {
+            if let Some(owner_id) = owner_id {
+                content.insert_str(0, &format!("<@{owner_id}> "));
+            }
+            *log_channel
+        }
+        (None, Some(owner_id)) => match ctx.client.create_private_channel(owner_id).await {
+            Ok(resp) => match resp.model().await {
+                Ok(channel) => channel.id,
+                Err(e) => {
+                    tracing::warn!("failed to open DM with the owner of {guild_id}: {e}");
+                    return;
+                }
+            },
+            Err(e) => {
+                tracing::warn!("failed to open DM with the owner of {guild_id}: {e}");
+                return;
+            }
+        },
+        (None, None) => return,
+    };
+
+    if let Err(e) = ctx.client.create_message(target).content(&content).await {
+        tracing::warn!("failed to announce the paused channel #{channel_id}: {e}");
+    }
+}
+
+async fn game_names<'a, I>(ctx: &Context, entries: I) -> Result<HashMap<u64, String>, Error>
+where
+    I: IntoIterator<Item = &'a AuditEntry>,
+{
+    let mut game_ids = entries.into_iter().map(|e| e.game).collect::<Vec<_>>();
+    game_ids.sort_unstable();
+    game_ids.dedup();
+
+    let filter = Id::_in(game_ids);
+    let list = ctx.modio.games().search(filter).collect().await?;
+    Ok(list.into_iter().map(|g| (g.id.get(), g.name)).collect())
+}
+
+fn format_entry(entry: &AuditEntry, games: &HashMap<u64, String>, locale: Locale) -> String {
+    let time = util::discord_timestamp(entry.timestamp, TimestampStyle::DateTime);
+    let action = locale.tr(entry.action.label());
+    let channel = format!("<#{}>", entry.channel);
+    let mut s = match entry.actor {
+        Some(actor) => locale.format(
+            "{time} **{action}** in {channel} by {actor}",
+            &[
+                ("time", &time),
+                ("action", action),
+                ("channel", &channel),
+                ("actor", &format!("<@{actor}>")),
+            ],
+        ),
+        None => locale.format(
+            "{time} **{action}** in {channel} by the bot",
+            &[("time", &time), ("action", action), ("channel", &channel)],
+        ),
+    };
+    let game = locale.tr("Game");
+    match games.get(&entry.game.get()) {
+        Some(name) => _ = write!(&mut s, "\n{game}: `{}.` {name}", entry.game),
+        None => _ = write!(&mut s, "\n{game}: `{}`", entry.game),
+    }
+    if let Some(before) = &entry.before {
+        _ = write!(&mut s, "\n{}: {before}", locale.tr("Before"));
+    }
+    if let Some(after) = &entry.after {
+        _ = write!(&mut s, "\n{}: {after}", locale.tr("After"));
+    }
+    s.push('\n');
+    s
+}
+
+/// Returns the language of the guild for messages outside of interactions.
+fn guild_locale(ctx: &Context, guild_id: GuildId) -> Locale {
+    let locale = ctx.settings.language(guild_id).unwrap_or_else(|e| {
+        tracing::error!("{e}");
+        None
+    });
+    locale.unwrap_or_default()
+}
+
+fn actor(interaction: &Interaction) -> UserId {
+    UserId(interaction.author_id().expect("guild only command"))
+}
+
 async fn find_game_mod(
-    modio: &Modio,
-    game_filter: Filter,
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    search: &str,
     mod_filter: Filter,
 ) -> Result<(Option<Game>, Option<Mod>), Error> {
-    let Some(game) = modio.games().search(game_filter).first().await? else {
+    let Some(game) = search_game(ctx, guild_id, search).await? else {
         return Ok((None, None));
     };
 
-    let mod_ = modio
+    let mod_ = ctx
+        .modio
         .game(game.id)
         .mods()
         .search(mod_filter)
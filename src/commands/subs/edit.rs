@@ -0,0 +1,210 @@
+use modio::types::games::ApiAccessOptions;
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+use twilight_model::application::interaction::application_command::{
+    CommandDataOption, CommandOptionValue,
+};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::http::interaction::{InteractionResponse, InteractionResponseType};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use super::{
+    actor, announce_changes, can_manage, crosspost_warning, push_invalid_tags, THIRD_PARTY_DISABLED,
+};
+use crate::bot::Context;
+use crate::commands::{defer_ephemeral, update_response_content, InteractionExt};
+use crate::db::{Events, Style, SubscriptionChange, Tags};
+use crate::error::Error;
+use crate::i18n::Locale;
+use crate::util;
+
+/// Maximum length of the name of an autocomplete choice.
+const MAX_CHOICE_LENGTH: usize = 100;
+
+/// `/subs edit <subscription> [tags] [type] [explicit] [crosspost] [style]`
+#[allow(clippy::too_many_lines)]
+pub async fn edit(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let mut sub_id = None;
+    let mut tags = None;
+    let mut evts = None;
+    let mut explicit = None;
+    let mut crosspost = None;
+    let mut style = None;
+
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::String(s) if opt.name == "subscription" => {
+                sub_id = s.parse::<i32>().ok();
+            }
+            CommandOptionValue::String(s) if opt.name == "tags" => {
+                tags = Some(s.trim());
+            }
+            CommandOptionValue::Integer(v) if opt.name == "type" => {
+                evts = if (1..=3).contains(v) {
+                    #[allow(clippy::cast_possible_truncation)]
+                    Some(Events::from_bits_truncate(*v as i32))
+                } else {
+                    Some(Events::ALL)
+                };
+            }
+            CommandOptionValue::Boolean(v) if opt.name == "explicit" => {
+                explicit = Some(*v);
+            }
+            CommandOptionValue::Boolean(v) if opt.name == "crosspost" => {
+                crosspost = Some(*v);
+            }
+            CommandOptionValue::String(s) if opt.name == "style" => {
+                style = s.parse::<Style>().ok();
+            }
+            _ => {}
+        }
+    }
+
+    defer_ephemeral(ctx, interaction).await?;
+    let locale = Locale::from_interaction(interaction);
+
+    let channel_id = interaction.channel_id().unwrap();
+    let (mut found, others): (Vec<_>, Vec<_>) = ctx
+        .subscriptions
+        .list_with_ids(channel_id)?
+        .into_iter()
+        .partition(|sub| Some(sub.id) == sub_id);
+
+    let Some(old) = found.pop() else {
+        let content = locale.tr("Subscription not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+
+    let game = ctx.modio.game(old.game.0).get().await?;
+    if !game
+        .api_access_options
+        .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
+    {
+        let content = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let sub_tags = match tags {
+        None => old.tags,
+        Some("-") => Tags::default(),
+        Some(tags) => {
+            let game_tags = game
+                .tag_options
+                .into_iter()
+                .flat_map(|opt| opt.tags)
+                .collect::<Tags>();
+
+            let (hidden, mut sub_tags) = Tags::from_csv(tags).partition();
+
+            if !sub_tags.is_subset(&game_tags) {
+                let args = [("game", game.name.as_str())];
+                let mut content =
+                    locale.format("Failed to edit the subscription of '{game}'.", &args);
+                content.push('\n');
+                let invalid = sub_tags.difference(&game_tags);
+                push_invalid_tags(&mut content, invalid, &game_tags, locale);
+
+                return update_response_content(ctx, interaction, &content).await;
+            }
+            sub_tags.extend(hidden);
+            sub_tags
+        }
+    };
+
+    if others
+        .iter()
+        .any(|sub| sub.game == old.game && *sub.tags == *sub_tags)
+    {
+        let content = locale.format(
+            "A subscription to '{game}' with the same tags already exists.",
+            &[("game", &game.name)],
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let crosspost = crosspost.unwrap_or(old.crosspost);
+    let change = SubscriptionChange {
+        tags: sub_tags,
+        events: evts.unwrap_or(old.events),
+        explicit: explicit.unwrap_or(old.explicit),
+        crosspost: Some(crosspost),
+        style,
+    };
+    let ret = ctx
+        .subscriptions
+        .edit(old.id, channel_id, change, actor(interaction))?;
+
+    let args = [("game", game.name.as_str())];
+    let mut content = match ret {
+        Some(entry) => {
+            announce_changes(ctx, &[entry]).await;
+            locale.format("Updated the subscription to '{game}'.", &args)
+        }
+        None => locale.format("The subscription to '{game}' is unchanged.", &args),
+    };
+    if let Some(warning) = crosspost_warning(ctx, channel_id, crosspost, locale) {
+        content.push('\n');
+        content.push_str(warning);
+    }
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// Suggests the subscriptions of the current channel to members allowed to manage them.
+pub async fn autocomplete_subscriptions(
+    ctx: &Context,
+    interaction: &Interaction,
+    value: &str,
+) -> Result<(), Error> {
+    let value = value.to_lowercase();
+    let subs = match interaction.channel_id() {
+        Some(channel_id) if can_manage(ctx, interaction)? => {
+            ctx.subscriptions.list_with_ids(channel_id)?
+        }
+        _ => Vec::new(),
+    };
+
+    let choices = subs
+        .into_iter()
+        .map(|sub| {
+            let mut label = sub.game_name.unwrap_or_else(|| sub.game.to_string());
+            label.push_str(sub.events.to_suffix());
+            if !sub.tags.is_empty() {
+                let tags = sub.tags.iter().map(String::as_str).collect::<Vec<_>>();
+                label.push_str(" | Tags: ");
+                label.push_str(&tags.join(", "));
+            }
+            if !sub.explicit {
+                label.push_str(" | No explicit content");
+            }
+            if sub.crosspost {
+                label.push_str(" | Crosspost");
+            }
+            if sub.style != Style::Full {
+                label.push_str(" | Style: ");
+                label.push_str(sub.style.as_str());
+            }
+            (sub.id, label)
+        })
+        .filter(|(_, label)| label.to_lowercase().contains(&value))
+        .take(25)
+        .map(|(id, label)| CommandOptionChoice {
+            name: util::truncate(label, MAX_CHOICE_LENGTH),
+            name_localizations: None,
+            value: CommandOptionChoiceValue::String(id.to_string()),
+        });
+
+    let data = InteractionResponseDataBuilder::new()
+        .choices(choices)
+        .build();
+    let response = InteractionResponse {
+        kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+        data: Some(data),
+    };
+    ctx.interaction()
+        .create_response(interaction.id, &interaction.token, &response)
+        .await?;
+    Ok(())
+}
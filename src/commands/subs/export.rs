@@ -0,0 +1,600 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use modio::filter::prelude::*;
+use modio::types::games::{ApiAccessOptions, Game};
+use serde_derive::{Deserialize, Serialize};
+use twilight_model::application::interaction::application_command::{
+    CommandData, CommandDataOption, CommandOptionValue,
+};
+use twilight_model::application::interaction::Interaction;
+use twilight_model::http::attachment::Attachment;
+use twilight_model::id::Id as DiscordId;
+
+use super::{actor, announce_changes, THIRD_PARTY_DISABLED};
+use crate::bot::Context;
+use crate::commands::{defer_ephemeral, update_response_content, InteractionExt};
+use crate::db::types::{ChannelId, GameId, GuildId, ModId};
+use crate::db::{
+    Events, ImportRecords, ModSubscription, Style, SubscriptionSettings, Tags, Template,
+};
+use crate::error::Error;
+use crate::i18n::Locale;
+
+/// Format version of the exported file.
+const VERSION: u32 = 1;
+/// Maximum size of an imported file.
+const MAX_FILE_SIZE: u64 = 256 * 1024;
+/// Maximum number of validation errors shown.
+const MAX_ERRORS: usize = 20;
+
+#[derive(Default, Deserialize, Serialize)]
+struct Export {
+    version: u32,
+    #[serde(default, rename = "channel")]
+    channels: Vec<ExportChannel>,
+    /// The default notification template of the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    template: Option<ExportTemplate>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ExportChannel {
+    id: u64,
+    #[serde(
+        default,
+        rename = "subscription",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    subscriptions: Vec<ExportSubscription>,
+    #[serde(default, rename = "mod", skip_serializing_if = "Vec::is_empty")]
+    mods: Vec<ExportMod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    muted_mods: Vec<MutedMods>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    muted_users: Vec<MutedUsers>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ExportSubscription {
+    game: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default)]
+    events: EventTypes,
+    #[serde(default = "default_explicit")]
+    explicit: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    crosspost: bool,
+    #[serde(default, skip_serializing_if = "is_full_style")]
+    style: ExportStyle,
+    /// Unix timestamp until the subscription is paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused_until: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    template: Option<ExportTemplate>,
+}
+
+/// A subscription to the updates of a single mod.
+#[derive(Deserialize, Serialize)]
+struct ExportMod {
+    game: u64,
+    #[serde(rename = "mod")]
+    mod_id: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    crosspost: bool,
+    /// Unix timestamp until the subscription is paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused_until: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ExportTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum EventTypes {
+    #[default]
+    All,
+    New,
+    Updated,
+}
+
+#[derive(Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ExportStyle {
+    #[default]
+    Full,
+    Compact,
+    Text,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MutedMods {
+    game: u64,
+    mods: Vec<u64>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MutedUsers {
+    game: u64,
+    users: Vec<String>,
+}
+
+const fn default_explicit() -> bool {
+    true
+}
+
+fn is_full_style(style: &ExportStyle) -> bool {
+    *style == ExportStyle::Full
+}
+
+impl From<Events> for EventTypes {
+    fn from(events: Events) -> Self {
+        match (events.contains(Events::NEW), events.contains(Events::UPD)) {
+            (true, false) => Self::New,
+            (false, true) => Self::Updated,
+            _ => Self::All,
+        }
+    }
+}
+
+impl From<EventTypes> for Events {
+    fn from(events: EventTypes) -> Self {
+        match events {
+            EventTypes::All => Events::ALL,
+            EventTypes::New => Events::NEW,
+            EventTypes::Updated => Events::UPD,
+        }
+    }
+}
+
+impl From<Style> for ExportStyle {
+    fn from(style: Style) -> Self {
+        match style {
+            Style::Full => Self::Full,
+            Style::Compact => Self::Compact,
+            Style::Text => Self::Text,
+        }
+    }
+}
+
+impl From<ExportStyle> for Style {
+    fn from(style: ExportStyle) -> Self {
+        match style {
+            ExportStyle::Full => Style::Full,
+            ExportStyle::Compact => Style::Compact,
+            ExportStyle::Text => Style::Text,
+        }
+    }
+}
+
+impl From<Template> for ExportTemplate {
+    fn from(template: Template) -> Self {
+        Self {
+            content: template.content,
+            title: template.title,
+            description: template.description,
+        }
+    }
+}
+
+impl From<ExportTemplate> for Template {
+    fn from(template: ExportTemplate) -> Self {
+        Self {
+            content: template.content,
+            title: template.title,
+            description: template.description,
+        }
+    }
+}
+
+/// `/subs export`
+pub async fn export(ctx: &Context, interaction: &Interaction) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let locale = Locale::from_interaction(interaction);
+    let guild_id = interaction.guild_id().unwrap();
+    let (subs, excluded_mods, excluded_users) = ctx.subscriptions.list_for_overview(guild_id)?;
+    let mods = ctx.subscriptions.list_mods_for_guild(guild_id)?;
+    let template = ctx.subscriptions.template(guild_id, None)?;
+
+    if subs.is_empty() && mods.is_empty() && excluded_mods.is_empty() && excluded_users.is_empty() {
+        let content = locale.tr("No subscriptions found.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let mut channels = BTreeMap::new();
+    for (channel_id, subs) in subs {
+        let channel = channel_entry(&mut channels, channel_id);
+        for sub in subs {
+            channel.subscriptions.push(ExportSubscription {
+                game: sub.game.get(),
+                tags: sub.tags.iter().cloned().collect(),
+                events: sub.events.into(),
+                explicit: sub.explicit,
+                crosspost: sub.crosspost,
+                style: sub.style.into(),
+                paused_until: sub.paused_until,
+                template: sub.template.map(Into::into),
+            });
+        }
+    }
+    for (channel_id, mods) in mods {
+        let channel = channel_entry(&mut channels, channel_id);
+        for sub in mods {
+            channel.mods.push(ExportMod {
+                game: sub.game.get(),
+                mod_id: sub.mod_id.get(),
+                crosspost: sub.crosspost,
+                paused_until: sub.paused_until,
+            });
+        }
+    }
+    for ((game_id, channel_id), mods) in excluded_mods {
+        let mut mods = mods.iter().map(|m| m.get()).collect::<Vec<_>>();
+        mods.sort_unstable();
+        channel_entry(&mut channels, channel_id)
+            .muted_mods
+            .push(MutedMods {
+                game: game_id.get(),
+                mods,
+            });
+    }
+    for ((game_id, channel_id), users) in excluded_users {
+        let mut users = users.into_iter().collect::<Vec<_>>();
+        users.sort_unstable();
+        channel_entry(&mut channels, channel_id)
+            .muted_users
+            .push(MutedUsers {
+                game: game_id.get(),
+                users,
+            });
+    }
+
+    let export = Export {
+        version: VERSION,
+        channels: channels.into_values().collect(),
+        template: template.map(Into::into),
+    };
+    let content = toml::to_string(&export).map_err(|e| e.to_string())?;
+
+    let filename = format!("subscriptions-{guild_id}.toml");
+    let attachments = [Attachment::from_bytes(filename, content.into_bytes(), 1)];
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .content(Some(
+            locale.tr("Use `/subs import` to apply the file to a server."),
+        ))
+        .attachments(&attachments)
+        .await?;
+
+    Ok(())
+}
+
+/// `/subs import <file> [channels]`
+pub async fn import(
+    ctx: &Context,
+    interaction: &Interaction,
+    command: &CommandData,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    let mut attachment = None;
+    let mut mapping = HashMap::new();
+
+    defer_ephemeral(ctx, interaction).await?;
+
+    let locale = Locale::from_interaction(interaction);
+
+    for opt in opts {
+        match &opt.value {
+            CommandOptionValue::Attachment(id) if opt.name == "file" => {
+                attachment = command
+                    .resolved
+                    .as_ref()
+                    .and_then(|r| r.attachments.get(id));
+            }
+            CommandOptionValue::String(s) if opt.name == "channels" => {
+                let Some(map) = parse_mapping(s) else {
+                    let content = locale
+                        .tr("Invalid channel mapping. Use `old=new` pairs, e.g. `123=#mods`.");
+                    return update_response_content(ctx, interaction, content).await;
+                };
+                mapping = map;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(attachment) = attachment else {
+        let content = locale.tr("File not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    if attachment.size > MAX_FILE_SIZE {
+        let content = locale.tr("The file is too large.");
+        return update_response_content(ctx, interaction, content).await;
+    }
+
+    let text = reqwest::get(&attachment.url)
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let export = match toml::from_str::<Export>(&text) {
+        Ok(export) if export.version == VERSION => export,
+        Ok(export) => {
+            let version = export.version.to_string();
+            let content = locale.format(
+                "Unsupported file version: {version}",
+                &[("version", &version)],
+            );
+            return update_response_content(ctx, interaction, &content).await;
+        }
+        Err(e) => {
+            let content = locale.format("Invalid file: {error}", &[("error", e.message())]);
+            return update_response_content(ctx, interaction, &content).await;
+        }
+    };
+
+    let guild_id = interaction.guild_id().unwrap();
+    let mut errors = String::new();
+
+    let channels = remap_channels(ctx, guild_id, &export, &mapping, locale, &mut errors).await?;
+    let games = load_games(ctx, &export, locale, &mut errors).await?;
+    let records = into_records(export, &channels, &games, locale, &mut errors);
+
+    if !errors.is_empty() {
+        let errors = errors.lines().take(MAX_ERRORS).collect::<Vec<_>>();
+        let content = format!(
+            "{}\n{}",
+            locale.tr("Nothing was imported."),
+            errors.join("\n")
+        );
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let num_subs = records.subscriptions.values().map(Vec::len).sum::<usize>()
+        + records.mods.values().map(Vec::len).sum::<usize>();
+    let entries = ctx
+        .subscriptions
+        .import(guild_id, actor(interaction), records)?;
+    announce_changes(ctx, &entries).await;
+
+    let content = locale.format(
+        "Imported {subs} subscription(s) into {channels} channel(s).",
+        &[
+            ("subs", &num_subs.to_string()),
+            ("channels", &channels.len().to_string()),
+        ],
+    );
+    update_response_content(ctx, interaction, &content).await
+}
+
+/// Maps the channels of the file to the channels of this server.
+async fn remap_channels(
+    ctx: &Context,
+    guild_id: GuildId,
+    export: &Export,
+    mapping: &HashMap<u64, u64>,
+    locale: Locale,
+    errors: &mut String,
+) -> Result<HashMap<u64, ChannelId>, Error> {
+    let mut channels = HashMap::new();
+    for channel in &export.channels {
+        let id = mapping.get(&channel.id).copied().unwrap_or(channel.id);
+        if channels.contains_key(&channel.id) {
+            continue;
+        }
+        let belongs_to_guild = match DiscordId::new_checked(id) {
+            Some(id) => match ctx.client.channel(id).await {
+                Ok(resp) => resp.model().await?.guild_id == Some(guild_id.0),
+                Err(_) => false,
+            },
+            None => false,
+        };
+        if belongs_to_guild {
+            channels.insert(channel.id, ChannelId(DiscordId::new(id)));
+        } else {
+            let id = id.to_string();
+            let error = locale.format("Unknown channel: `{id}`", &[("id", &id)]);
+            _ = writeln!(errors, "{error}");
+        }
+    }
+
+    Ok(channels)
+}
+
+/// Loads the games of the file and checks that third party API access is enabled.
+async fn load_games(
+    ctx: &Context,
+    export: &Export,
+    locale: Locale,
+    errors: &mut String,
+) -> Result<HashMap<u64, Game>, Error> {
+    let mut game_ids = export
+        .channels
+        .iter()
+        .flat_map(|c| {
+            let subs = c.subscriptions.iter().map(|s| s.game);
+            let mods = c.mods.iter().map(|m| m.game);
+            let muted_mods = c.muted_mods.iter().map(|m| m.game);
+            let users = c.muted_users.iter().map(|u| u.game);
+            subs.chain(mods).chain(muted_mods).chain(users)
+        })
+        .collect::<Vec<_>>();
+    game_ids.sort_unstable();
+    game_ids.dedup();
+
+    let games = if game_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let filter = Id::_in(game_ids.clone());
+        let list = ctx.modio.games().search(filter).collect().await?;
+        list.into_iter()
+            .map(|g| (g.id.get(), g))
+            .collect::<HashMap<_, _>>()
+    };
+    for game_id in &game_ids {
+        match games.get(game_id) {
+            Some(game)
+                if !game
+                    .api_access_options
+                    .contains(ApiAccessOptions::ALLOW_THIRD_PARTY) =>
+            {
+                let error = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
+                _ = writeln!(errors, "{error}");
+            }
+            Some(_) => {}
+            None => {
+                let id = game_id.to_string();
+                let error = locale.format("Unknown game: `{id}`", &[("id", &id)]);
+                _ = writeln!(errors, "{error}");
+            }
+        }
+    }
+
+    Ok(games)
+}
+
+/// Converts the file into subscription records and validates the tags of the subscriptions.
+fn into_records(
+    export: Export,
+    channels: &HashMap<u64, ChannelId>,
+    games: &HashMap<u64, Game>,
+    locale: Locale,
+    errors: &mut String,
+) -> ImportRecords {
+    let mut records = ImportRecords {
+        template: export.template.map(Into::into),
+        ..Default::default()
+    };
+
+    for channel in export.channels {
+        let Some(&channel_id) = channels.get(&channel.id) else {
+            continue;
+        };
+        for sub in channel.subscriptions {
+            let Some(game) = games.get(&sub.game) else {
+                continue;
+            };
+            let game_tags = game
+                .tag_options
+                .iter()
+                .flat_map(|opt| opt.tags.iter().cloned())
+                .collect::<Tags>();
+
+            let (hidden, mut tags) = sub.tags.into_iter().collect::<Tags>().partition();
+            if !tags.is_subset(&game_tags) {
+                let invalid = tags.difference(&game_tags).cloned().collect::<Vec<_>>();
+                let args = [("game", game.name.as_str()), ("tags", &invalid.join(", "))];
+                let error = locale.format("Invalid tag(s) for '{game}': {tags}", &args);
+                _ = writeln!(errors, "{error}");
+                continue;
+            }
+            tags.extend(hidden);
+
+            records
+                .subscriptions
+                .entry(channel_id)
+                .or_default()
+                .push(SubscriptionSettings {
+                    game: GameId(game.id),
+                    tags,
+                    events: sub.events.into(),
+                    explicit: sub.explicit,
+                    crosspost: sub.crosspost,
+                    style: sub.style.into(),
+                    paused_until: sub.paused_until,
+                    template: sub.template.map(Into::into),
+                });
+        }
+        for sub in channel.mods {
+            let Some(game) = games.get(&sub.game) else {
+                continue;
+            };
+            let Some(mod_id) = modio::types::id::ModId::new_checked(sub.mod_id) else {
+                let id = sub.mod_id.to_string();
+                let error = locale.format("Invalid mod: `{id}`", &[("id", &id)]);
+                _ = writeln!(errors, "{error}");
+                continue;
+            };
+            records
+                .mods
+                .entry(channel_id)
+                .or_default()
+                .push(ModSubscription {
+                    game: GameId(game.id),
+                    mod_id: ModId(mod_id),
+                    crosspost: sub.crosspost,
+                    paused_until: sub.paused_until,
+                });
+        }
+        for muted in channel.muted_mods {
+            let Some(game) = games.get(&muted.game) else {
+                continue;
+            };
+            let mods = records
+                .excluded_mods
+                .entry((GameId(game.id), channel_id))
+                .or_default();
+            mods.extend(
+                muted
+                    .mods
+                    .into_iter()
+                    .filter_map(modio::types::id::ModId::new_checked)
+                    .map(ModId),
+            );
+        }
+        for muted in channel.muted_users {
+            let Some(game) = games.get(&muted.game) else {
+                continue;
+            };
+            records
+                .excluded_users
+                .entry((GameId(game.id), channel_id))
+                .or_default()
+                .extend(muted.users);
+        }
+    }
+    records
+}
+
+fn channel_entry(
+    channels: &mut BTreeMap<ChannelId, ExportChannel>,
+    id: ChannelId,
+) -> &mut ExportChannel {
+    channels.entry(id).or_insert_with(|| ExportChannel {
+        id: id.get(),
+        subscriptions: Vec::new(),
+        mods: Vec::new(),
+        muted_mods: Vec::new(),
+        muted_users: Vec::new(),
+    })
+}
+
+/// Parses the channel mapping `old=new,...` where the new channel can be a channel mention.
+fn parse_mapping(s: &str) -> Option<HashMap<u64, u64>> {
+    let parse_id = |s: &str| {
+        let s = s.trim();
+        let s = s
+            .strip_prefix("<#")
+            .and_then(|s| s.strip_suffix('>'))
+            .unwrap_or(s);
+        s.parse::<u64>().ok()
+    };
+    s.split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|pair| {
+            let (old, new) = pair.split_once('=')?;
+            Some((parse_id(old)?, parse_id(new)?))
+        })
+        .collect()
+}
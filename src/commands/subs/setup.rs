@@ -0,0 +1,386 @@
+use std::collections::BTreeSet;
+
+use modio::types::games::{ApiAccessOptions, Game, TagType};
+use modio::types::id::GameId;
+use serde_derive::{Deserialize, Serialize};
+use twilight_model::application::interaction::application_command::{
+    CommandDataOption, CommandOptionValue,
+};
+use twilight_model::application::interaction::message_component::MessageComponentInteractionData;
+use twilight_model::application::interaction::Interaction;
+use twilight_model::channel::message::component::{
+    ActionRow, Button, ButtonStyle, Component, SelectMenu, SelectMenuOption, SelectMenuType,
+};
+
+use super::{actor, announce_changes, can_manage, THIRD_PARTY_DISABLED};
+use crate::bot::Context;
+use crate::commands::{
+    create_response, defer_component_response, defer_ephemeral, search_game,
+    update_response_content, EphemeralMessage, InteractionExt,
+};
+use crate::db::{types, Events, SubscriptionChange, Tags};
+use crate::error::Error;
+use crate::i18n::Locale;
+
+pub const PREFIX: &str = "subs-setup:";
+
+/// Maximum number of tag groups, the last action row is used for the buttons.
+const MAX_GROUPS: usize = 4;
+/// Maximum number of options of a select menu.
+const MAX_OPTIONS: usize = 25;
+
+#[derive(Deserialize, Serialize)]
+struct CustomId<'a> {
+    #[serde(rename = "a")]
+    action: &'a str,
+    #[serde(rename = "g")]
+    game_id: GameId,
+    #[serde(rename = "i")]
+    index: Option<usize>,
+}
+
+impl CustomId<'_> {
+    fn to_custom_id(&self) -> String {
+        String::from(PREFIX) + &serde_urlencoded::to_string(self).unwrap()
+    }
+}
+
+/// A tag group of the game shown as select menu.
+struct TagGroup {
+    name: String,
+    multiple: bool,
+    tags: Vec<String>,
+    selected: BTreeSet<String>,
+}
+
+/// The state of the setup is kept in the components of the message.
+struct State {
+    game_id: GameId,
+    groups: Vec<TagGroup>,
+    events: Events,
+    explicit: bool,
+}
+
+impl State {
+    fn new(game: &Game) -> (Self, bool) {
+        let mut truncated = false;
+        let mut groups = Vec::new();
+        for opt in game.tag_options.iter().filter(|opt| !opt.hidden) {
+            if opt.tags.is_empty() {
+                continue;
+            }
+            if groups.len() == MAX_GROUPS || opt.tags.len() > MAX_OPTIONS {
+                truncated = true;
+            }
+            if groups.len() == MAX_GROUPS {
+                break;
+            }
+            groups.push(TagGroup {
+                name: opt.name.clone(),
+                multiple: matches!(opt.kind, TagType::Checkboxes),
+                tags: opt.tags.iter().take(MAX_OPTIONS).cloned().collect(),
+                selected: BTreeSet::new(),
+            });
+        }
+        let state = Self {
+            game_id: game.id,
+            groups,
+            events: Events::ALL,
+            explicit: true,
+        };
+        (state, truncated)
+    }
+
+    /// Restores the state from the components of the setup message.
+    fn from_components(game_id: GameId, components: &[Component]) -> Self {
+        let mut state = Self {
+            game_id,
+            groups: Vec::new(),
+            events: Events::empty(),
+            explicit: false,
+        };
+        let components = components.iter().flat_map(|c| match c {
+            Component::ActionRow(row) => row.components.as_slice(),
+            c => std::slice::from_ref(c),
+        });
+        for component in components {
+            match component {
+                Component::SelectMenu(menu) => {
+                    let options = menu.options.as_deref().unwrap_or_default();
+                    state.groups.push(TagGroup {
+                        name: menu.placeholder.clone().unwrap_or_default(),
+                        multiple: menu.max_values.is_some_and(|n| n > 1),
+                        tags: options.iter().map(|o| o.value.clone()).collect(),
+                        selected: options
+                            .iter()
+                            .filter(|o| o.default)
+                            .map(|o| o.value.clone())
+                            .collect(),
+                    });
+                }
+                Component::Button(button) if button.style == ButtonStyle::Success => {
+                    let custom_id = button.custom_id.as_deref().unwrap_or_default();
+                    let custom_id = custom_id.strip_prefix(PREFIX).unwrap_or(custom_id);
+                    if let Ok(CustomId { action, .. }) = serde_urlencoded::from_str(custom_id) {
+                        match action {
+                            "new" => state.events |= Events::NEW,
+                            "upd" => state.events |= Events::UPD,
+                            "explicit" => state.explicit = true,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    fn tags(&self) -> Tags {
+        self.groups
+            .iter()
+            .flat_map(|g| g.selected.iter().cloned())
+            .collect()
+    }
+
+    fn components(&self, locale: Locale) -> Vec<Component> {
+        let mut rows = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(index, group)| {
+                let options = group
+                    .tags
+                    .iter()
+                    .map(|tag| SelectMenuOption {
+                        default: group.selected.contains(tag),
+                        description: None,
+                        emoji: None,
+                        label: tag.clone(),
+                        value: tag.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                #[allow(clippy::cast_possible_truncation)]
+                let max_values = if group.multiple {
+                    options.len() as u8
+                } else {
+                    1
+                };
+                let menu = SelectMenu {
+                    channel_types: None,
+                    custom_id: self.custom_id("tags", Some(index)),
+                    default_values: None,
+                    disabled: false,
+                    kind: SelectMenuType::Text,
+                    max_values: Some(max_values),
+                    min_values: Some(0),
+                    options: Some(options),
+                    placeholder: Some(group.name.clone()),
+                };
+                Component::from(ActionRow {
+                    components: vec![menu.into()],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let buttons = vec![
+            self.toggle(
+                "new",
+                locale.tr("New mods"),
+                self.events.contains(Events::NEW),
+            ),
+            self.toggle(
+                "upd",
+                locale.tr("Updated mods"),
+                self.events.contains(Events::UPD),
+            ),
+            self.toggle("explicit", locale.tr("Explicit content"), self.explicit),
+            self.button(
+                "save",
+                locale.tr("Save"),
+                ButtonStyle::Primary,
+                self.events.is_empty(),
+            ),
+            self.button("cancel", locale.tr("Cancel"), ButtonStyle::Danger, false),
+        ];
+        rows.push(Component::from(ActionRow {
+            components: buttons.into_iter().map(Component::from).collect(),
+        }));
+        rows
+    }
+
+    fn custom_id(&self, action: &str, index: Option<usize>) -> String {
+        CustomId {
+            action,
+            game_id: self.game_id,
+            index,
+        }
+        .to_custom_id()
+    }
+
+    fn toggle(&self, action: &str, label: &str, enabled: bool) -> Button {
+        let style = if enabled {
+            ButtonStyle::Success
+        } else {
+            ButtonStyle::Secondary
+        };
+        self.button(action, label, style, false)
+    }
+
+    fn button(&self, action: &str, label: &str, style: ButtonStyle, disabled: bool) -> Button {
+        Button {
+            custom_id: Some(self.custom_id(action, None)),
+            style,
+            label: Some(label.to_owned()),
+            disabled,
+            emoji: None,
+            url: None,
+            sku_id: None,
+        }
+    }
+}
+
+/// `/subs setup <game>`
+pub async fn setup(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let mut game = None;
+    for opt in opts {
+        if let CommandOptionValue::String(s) = &opt.value {
+            if opt.name == "game" {
+                game = search_game(ctx, interaction.guild_id(), s).await?;
+            }
+        }
+    }
+    let locale = Locale::from_interaction(interaction);
+    let Some(game) = game else {
+        let content = locale.tr("Game not found.");
+        return update_response_content(ctx, interaction, content).await;
+    };
+    if !game
+        .api_access_options
+        .contains(ApiAccessOptions::ALLOW_THIRD_PARTY)
+    {
+        let content = locale.format(THIRD_PARTY_DISABLED, &[("game", &game.name)]);
+        return update_response_content(ctx, interaction, &content).await;
+    }
+
+    let (state, truncated) = State::new(&game);
+
+    let mut content = locale.format(
+        "Choose the tags and the type of mod updates for '{game}' and save the subscription.",
+        &[("game", &game.name)],
+    );
+    if truncated {
+        content.push('\n');
+        content.push_str(
+            locale.tr("Not all tags of the game can be shown, use `/subs add` for the other tags."),
+        );
+    }
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .content(Some(&content))
+        .components(Some(&state.components(locale)))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn setup_component(
+    ctx: &Context,
+    interaction: &Interaction,
+    component: &MessageComponentInteractionData,
+) -> Result<(), Error> {
+    let locale = Locale::from_interaction(interaction);
+    if !can_manage(ctx, interaction)? {
+        let data = locale
+            .tr(":no_entry: You are not allowed to manage the subscriptions.")
+            .into_ephemeral();
+        return create_response(ctx, interaction, data).await;
+    }
+
+    let custom_id = component
+        .custom_id
+        .strip_prefix(PREFIX)
+        .unwrap_or(&component.custom_id);
+
+    let CustomId {
+        action,
+        game_id,
+        index,
+    } = serde_urlencoded::from_str(custom_id).unwrap();
+
+    let Some(message) = &interaction.message else {
+        return Ok(());
+    };
+    let mut state = State::from_components(game_id, &message.components);
+
+    defer_component_response(ctx, interaction).await?;
+
+    match action {
+        "tags" => {
+            if let Some(group) = index.and_then(|i| state.groups.get_mut(i)) {
+                group.selected = component.values.iter().cloned().collect();
+            }
+        }
+        "new" => state.events.toggle(Events::NEW),
+        "upd" => state.events.toggle(Events::UPD),
+        "explicit" => state.explicit = !state.explicit,
+        "save" => return save(ctx, interaction, &state, locale).await,
+        "cancel" => {
+            ctx.interaction()
+                .update_response(&interaction.token)
+                .content(Some(locale.tr("Setup cancelled.")))
+                .components(Some(&[]))
+                .await?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .components(Some(&state.components(locale)))
+        .await?;
+
+    Ok(())
+}
+
+async fn save(
+    ctx: &Context,
+    interaction: &Interaction,
+    state: &State,
+    locale: Locale,
+) -> Result<(), Error> {
+    let channel_id = interaction.channel_id().unwrap();
+    let guild_id = interaction.guild_id().unwrap();
+
+    let game = ctx.modio.game(state.game_id).get().await?;
+
+    let change = SubscriptionChange {
+        tags: state.tags(),
+        events: state.events,
+        explicit: state.explicit,
+        crosspost: None,
+        style: None,
+    };
+    let game_id = types::GameId(state.game_id);
+    let entry = ctx
+        .subscriptions
+        .add(game_id, channel_id, guild_id, change, actor(interaction))?;
+    announce_changes(ctx, entry.as_slice()).await;
+
+    let content = locale.format("Subscribed to '{game}'.", &[("game", &game.name)]);
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .content(Some(&content))
+        .components(Some(&[]))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use std::fmt::Write;
+
+use modio::filter::prelude::*;
+use modio::types::mods::EventType;
+use twilight_model::application::interaction::application_command::{
+    CommandDataOption, CommandOptionValue,
+};
+use twilight_model::application::interaction::Interaction;
+
+use crate::bot::Context;
+use crate::commands::{
+    defer_ephemeral, update_response_content, update_response_from_content, InteractionExt,
+    SubCommandExt,
+};
+use crate::db::types::{GameId, GuildId};
+use crate::db::{Style, Template};
+use crate::error::Error;
+use crate::i18n::Locale;
+use crate::tasks::events::{create_mod_message, PLACEHOLDERS};
+use crate::util::{self, ContentBuilder};
+
+/// `/subs template show|set|reset`
+pub async fn template(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+) -> Result<(), Error> {
+    defer_ephemeral(ctx, interaction).await?;
+
+    let locale = Locale::from_interaction(interaction);
+    let Some((cmd, opts)) = opts.subcommand() else {
+        return Ok(());
+    };
+
+    let guild_id = interaction.guild_id().unwrap();
+    let sub_id = opts.iter().find_map(|opt| match &opt.value {
+        CommandOptionValue::String(s) if opt.name == "subscription" => Some(s.parse::<i32>().ok()),
+        _ => None,
+    });
+    let target = match sub_id {
+        Some(sub_id) => {
+            let channel_id = interaction.channel_id().unwrap();
+            let found = ctx
+                .subscriptions
+                .list_with_ids(channel_id)?
+                .into_iter()
+                .find(|sub| Some(sub.id) == sub_id)
+                .map(|sub| TargetSubscription {
+                    id: sub.id,
+                    game_id: sub.game,
+                    name: sub.game_name,
+                    style: sub.style,
+                });
+
+            let Some(sub) = found else {
+                let content = locale.tr("Subscription not found.");
+                return update_response_content(ctx, interaction, content).await;
+            };
+            Some(sub)
+        }
+        None => None,
+    };
+
+    match cmd {
+        "show" => show(ctx, interaction, guild_id, target, locale).await,
+        "set" => set(ctx, interaction, opts, guild_id, target, locale).await,
+        "reset" => {
+            let sub_id = target.as_ref().map(|t| t.id);
+            ctx.subscriptions
+                .set_template(guild_id, sub_id, &Template::default())?;
+            let content = locale.format(
+                "Removed the notification template of {target}.",
+                &[("target", &label(target, locale))],
+            );
+            update_response_content(ctx, interaction, &content).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The subscription of a template, `None` is the default template of the server.
+type Target = Option<TargetSubscription>;
+
+struct TargetSubscription {
+    id: i32,
+    game_id: GameId,
+    name: Option<String>,
+    style: Style,
+}
+
+fn label(target: Target, locale: Locale) -> String {
+    match target {
+        Some(sub) => match sub.name {
+            Some(name) => locale.format("the subscription to '{game}'", &[("game", &name)]),
+            None => locale.format(
+                "the subscription to the game `{id}`",
+                &[("id", &sub.game_id.to_string())],
+            ),
+        },
+        None => locale.tr("this server").to_owned(),
+    }
+}
+
+/// `/subs template show [subscription]`
+async fn show(
+    ctx: &Context,
+    interaction: &Interaction,
+    guild_id: GuildId,
+    target: Target,
+    locale: Locale,
+) -> Result<(), Error> {
+    let sub_id = target.as_ref().map(|t| t.id);
+    let template = ctx.subscriptions.template(guild_id, sub_id)?;
+    let template = template.unwrap_or_default();
+
+    let mut content = ContentBuilder::new(4000);
+    let target = label(target, locale);
+    let header = locale.format("Notification template of {target}.", &[("target", &target)]);
+    let _ = writeln!(&mut content, "{header}");
+    for (name, value) in [
+        ("Content", &template.content),
+        ("Title", &template.title),
+        ("Description", &template.description),
+    ] {
+        let name = locale.tr(name);
+        match value {
+            Some(value) => _ = writeln!(&mut content, "**{name}:**\n```\n{value}\n```"),
+            None => _ = writeln!(&mut content, "**{name}:** *{}*", locale.tr("default")),
+        }
+    }
+    if sub_id.is_some() {
+        let text = locale.tr("Fields without a template use the template of the server.");
+        let _ = writeln!(&mut content, "*{text}*");
+    }
+    let _ = writeln!(&mut content, "\n**{}:**", locale.tr("Placeholders"));
+    for (name, desc) in PLACEHOLDERS {
+        let _ = writeln!(&mut content, "`{{{name}}}` {}", locale.tr(desc));
+    }
+
+    let title = locale.tr("Template");
+    update_response_from_content(ctx, interaction, title, &content.content).await
+}
+
+/// `/subs template set [subscription] [content] [title] [description]`
+async fn set(
+    ctx: &Context,
+    interaction: &Interaction,
+    opts: &[CommandDataOption],
+    guild_id: GuildId,
+    target: Target,
+    locale: Locale,
+) -> Result<(), Error> {
+    let sub_id = target.as_ref().map(|t| t.id);
+    let mut template = ctx
+        .subscriptions
+        .template(guild_id, sub_id)?
+        .unwrap_or_default();
+
+    for opt in opts {
+        let CommandOptionValue::String(value) = &opt.value else {
+            continue;
+        };
+        let field = match opt.name.as_str() {
+            "content" => &mut template.content,
+            "title" => &mut template.title,
+            "description" => &mut template.description,
+            _ => continue,
+        };
+        if value.trim() == "-" {
+            *field = None;
+            continue;
+        }
+        let known = |name: &str| PLACEHOLDERS.iter().any(|(p, _)| *p == name).then_some("");
+        if let Err(e) = util::expand_placeholders(value, known) {
+            let args = [("field", opt.name.as_str()), ("error", &e.to_string())];
+            let content = locale.format(":no_entry: Invalid {field} template: {error}.", &args);
+            return update_response_content(ctx, interaction, &content).await;
+        }
+        *field = Some(value.clone());
+    }
+
+    ctx.subscriptions
+        .set_template(guild_id, sub_id, &template)?;
+
+    // Preview the template with the latest updated mod of the subscribed or default game.
+    let (game_id, style) = match &target {
+        Some(sub) => (Some(sub.game_id), sub.style),
+        None => (ctx.settings.game(guild_id)?, Style::Full),
+    };
+    let template = match sub_id {
+        Some(sub_id) => ctx.subscriptions.subscription_template(guild_id, sub_id)?,
+        None => Some(template),
+    };
+    let content = locale.format(
+        "Saved the notification template of {target}.",
+        &[("target", &label(target, locale))],
+    );
+
+    let Some(game_id) = game_id else {
+        let hint = locale.tr("Set a default game with `/settings default-game` to see a preview.");
+        let content = format!("{content}\n{hint}");
+        return update_response_content(ctx, interaction, &content).await;
+    };
+    let game = ctx.modio.game(*game_id).get().await?;
+    let filter = with_limit(1).order_by(DateUpdated::desc());
+    let mods = ctx.modio.game(*game_id).mods();
+    let Some(mod_) = mods.search(filter).first().await? else {
+        return update_response_content(ctx, interaction, &content).await;
+    };
+
+    let evt = EventType::MODFILE_CHANGED;
+    let language = ctx.settings.language(guild_id)?.unwrap_or_default();
+    let (preview, embed) =
+        create_mod_message(&game, &mod_, &evt, template.as_ref(), style, language);
+    let title = locale.tr("Preview");
+    let content = match preview {
+        Some(preview) => format!("{content}\n**{title}:**\n{preview}"),
+        None => format!("{content}\n**{title}:**"),
+    };
+    let content = util::truncate(content, 2000);
+
+    ctx.interaction()
+        .update_response(&interaction.token)
+        .content(Some(&content))
+        .embeds(Some(embed.as_slice()))
+        .await?;
+    Ok(())
+}
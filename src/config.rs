@@ -16,6 +16,15 @@ pub struct Config {
     pub modio: ModioConfig,
     #[serde(default)]
     pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+}
+
+#[derive(Default, Deserialize)]
+pub struct EventsConfig {
+    /// Seconds a mod must not be updated again before the update is announced.
+    #[serde(default)]
+    pub debounce: u64,
 }
 
 #[derive(Deserialize)]
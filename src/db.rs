@@ -13,12 +13,20 @@ const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 #[rustfmt::skip]
 mod schema;
 pub mod autocomplete;
+mod follows;
 mod settings;
+pub mod stats;
 mod subscriptions;
 pub mod types;
 
-pub use settings::Settings;
-pub use subscriptions::{Events, Subscription, Subscriptions, Tags};
+pub use follows::{Follow, Follows};
+pub use settings::{Settings, MAX_EDIT_WINDOW};
+pub use subscriptions::{
+    AuditEntry, DeliveryFailure, Events, ExcludedModsMap, ExcludedUsersMap, ImportRecords,
+    ModSubscription, NotificationMessage, PendingChanges, Style, Subscription, SubscriptionChange,
+    SubscriptionSettings, Subscriptions, Tags, Template, Templates, MAX_PERMISSION_FAILURES,
+    PAUSED_INDEFINITELY,
+};
 
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -2,7 +2,7 @@ use diesel::prelude::*;
 use diesel::sql_types::Text;
 use tokio::task::block_in_place;
 
-use super::types::{ApiAccessOptions, GameId};
+use super::types::{ApiAccessOptions, GameId, GuildId};
 use super::{schema, DbPool, Result};
 
 #[derive(Debug, Insertable)]
@@ -52,6 +52,34 @@ pub fn games_by_name(pool: &DbPool, value: &str) -> Result<Vec<(GameId, String)>
     Ok(result)
 }
 
+/// Returns the games of the guild matching the name, name id or alias.
+pub fn guild_games_by_name(
+    pool: &DbPool,
+    guild_id: GuildId,
+    value: &str,
+) -> Result<Vec<(GameId, String, Option<String>)>> {
+    use schema::games::dsl::*;
+    use schema::guild_games;
+
+    let conn = &mut pool.get()?;
+
+    let pattern = format!("{}%", value.trim_start_matches('@'));
+    let result = guild_games::table
+        .inner_join(games)
+        .select((id, name, guild_games::alias))
+        .filter(guild_games::guild.eq(guild_id))
+        .filter(
+            name.like(&pattern)
+                .or(name_id.like(&pattern))
+                .or(guild_games::alias.like(&pattern)),
+        )
+        .order_by(name.asc())
+        .limit(25)
+        .load(conn)?;
+
+    Ok(result)
+}
+
 pub fn games_by_name_id(pool: &DbPool, value: &str) -> Result<Vec<(GameId, String)>> {
     use schema::games::dsl::*;
 
@@ -65,3 +93,17 @@ pub fn games_by_name_id(pool: &DbPool, value: &str) -> Result<Vec<(GameId, Strin
 
     Ok(result)
 }
+
+pub fn third_party_games(pool: &DbPool) -> Result<Vec<(GameId, String)>> {
+    use schema::games::dsl::*;
+
+    let conn = &mut pool.get()?;
+
+    let result = games
+        .select((id, name))
+        .filter(autocomplete.eq(true))
+        .order_by(id.asc())
+        .load(conn)?;
+
+    Ok(result)
+}
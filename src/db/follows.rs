@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::types::{GameId, ModId, UserId};
+use super::{schema, DbPool, Result};
+
+/// The followers of the games and what they follow.
+pub type FollowsMap = HashMap<GameId, Vec<(UserId, Follow)>>;
+
+/// What a user follows of a game.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Follow {
+    /// New and updated mods of the game.
+    Game,
+    /// A single mod of the game.
+    Mod(ModId),
+    /// The mods of a mod.io user.
+    User(String),
+}
+
+#[derive(Clone)]
+pub struct Follows {
+    pub pool: DbPool,
+}
+
+impl Follows {
+    /// Loads the follows of all users whose direct messages are not disabled.
+    pub fn load(&self) -> Result<FollowsMap> {
+        let (games, mods, users) = block_in_place::<_, Result<_>>(|| {
+            let conn = &mut self.pool.get()?;
+
+            let games = {
+                use schema::follows_games::dsl::*;
+                follows_games
+                    .select((game, user))
+                    .filter(disabled.eq(false))
+                    .load::<(GameId, UserId)>(conn)?
+            };
+            let mods = {
+                use schema::follows_mods::dsl::*;
+                follows_mods
+                    .select((game, user, mod_id))
+                    .filter(disabled.eq(false))
+                    .load::<(GameId, UserId, ModId)>(conn)?
+            };
+            let users = {
+                use schema::follows_users::dsl::*;
+                follows_users
+                    .select((game, user, name))
+                    .filter(disabled.eq(false))
+                    .load::<(GameId, UserId, String)>(conn)?
+            };
+            Ok((games, mods, users))
+        })?;
+
+        let mut map = FollowsMap::new();
+        for (game_id, user_id) in games {
+            map.entry(game_id)
+                .or_default()
+                .push((user_id, Follow::Game));
+        }
+        for (game_id, user_id, mod_id) in mods {
+            map.entry(game_id)
+                .or_default()
+                .push((user_id, Follow::Mod(mod_id)));
+        }
+        for (game_id, user_id, name) in users {
+            map.entry(game_id)
+                .or_default()
+                .push((user_id, Follow::User(name)));
+        }
+        Ok(map)
+    }
+
+    /// Returns the follows of the user and whether they are disabled.
+    pub fn list(&self, user_id: UserId) -> Result<Vec<(GameId, Follow, bool)>> {
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let mut list = Vec::new();
+            {
+                use schema::follows_games::dsl::*;
+                let games = follows_games
+                    .select((game, disabled))
+                    .filter(user.eq(user_id))
+                    .load::<(GameId, bool)>(conn)?;
+                list.extend(games.into_iter().map(|(g, d)| (g, Follow::Game, d)));
+            }
+            {
+                use schema::follows_mods::dsl::*;
+                let mods = follows_mods
+                    .select((game, mod_id, disabled))
+                    .filter(user.eq(user_id))
+                    .load::<(GameId, ModId, bool)>(conn)?;
+                list.extend(mods.into_iter().map(|(g, m, d)| (g, Follow::Mod(m), d)));
+            }
+            {
+                use schema::follows_users::dsl::*;
+                let users = follows_users
+                    .select((game, name, disabled))
+                    .filter(user.eq(user_id))
+                    .load::<(GameId, String, bool)>(conn)?;
+                list.extend(users.into_iter().map(|(g, n, d)| (g, Follow::User(n), d)));
+            }
+            Ok(list)
+        })
+    }
+
+    /// Adds the follow and re-enables the disabled follows of the user.
+    ///
+    /// Returns `false` if the user already follows it.
+    pub fn follow(&self, user_id: UserId, game_id: GameId, follow: &Follow) -> Result<bool> {
+        use diesel::result::Error;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let added = conn.transaction::<_, Error, _>(|conn| {
+                let num = match follow {
+                    Follow::Game => {
+                        use schema::follows_games::dsl::*;
+                        diesel::insert_or_ignore_into(follows_games)
+                            .values((user.eq(user_id), game.eq(game_id)))
+                            .execute(conn)?
+                    }
+                    Follow::Mod(id) => {
+                        use schema::follows_mods::dsl::*;
+                        diesel::insert_or_ignore_into(follows_mods)
+                            .values((user.eq(user_id), game.eq(game_id), mod_id.eq(id)))
+                            .execute(conn)?
+                    }
+                    Follow::User(username) => {
+                        use schema::follows_users::dsl::*;
+                        diesel::insert_or_ignore_into(follows_users)
+                            .values((user.eq(user_id), game.eq(game_id), name.eq(username)))
+                            .execute(conn)?
+                    }
+                };
+                set_disabled(conn, user_id, false)?;
+                Ok(num > 0)
+            })?;
+            Ok(added)
+        })
+    }
+
+    /// Removes the follow.
+    ///
+    /// Returns `false` if the user doesn't follow it.
+    pub fn unfollow(&self, user_id: UserId, game_id: GameId, follow: &Follow) -> Result<bool> {
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let num = match follow {
+                Follow::Game => {
+                    use schema::follows_games::dsl::*;
+                    let filter = follows_games.filter(user.eq(user_id).and(game.eq(game_id)));
+                    diesel::delete(filter).execute(conn)?
+                }
+                Follow::Mod(id) => {
+                    use schema::follows_mods::dsl::*;
+                    let pred = user.eq(user_id).and(game.eq(game_id)).and(mod_id.eq(id));
+                    diesel::delete(follows_mods.filter(pred)).execute(conn)?
+                }
+                Follow::User(username) => {
+                    use schema::follows_users::dsl::*;
+                    let pred = user
+                        .eq(user_id)
+                        .and(game.eq(game_id))
+                        .and(name.eq(username));
+                    diesel::delete(follows_users.filter(pred)).execute(conn)?
+                }
+            };
+            Ok(num > 0)
+        })
+    }
+
+    /// Disables all follows of the user after a direct message couldn't be delivered.
+    pub fn disable(&self, user_id: UserId) -> Result<()> {
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            set_disabled(conn, user_id, true)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the games with follows.
+    pub fn get_games(&self) -> Result<Vec<GameId>> {
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let games = {
+                use schema::follows_games::dsl::*;
+                follows_games.select(game)
+            };
+            let mod_games = {
+                use schema::follows_mods::dsl::*;
+                follows_mods.select(game)
+            };
+            let user_games = {
+                use schema::follows_users::dsl::*;
+                follows_users.select(game)
+            };
+            Ok(games.union(mod_games).union(user_games).load(conn)?)
+        })
+    }
+
+    /// Returns the followed mods.
+    pub fn get_mods(&self) -> Result<Vec<(GameId, ModId)>> {
+        use schema::follows_mods::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let list = follows_mods.select((game, mod_id)).distinct().load(conn)?;
+            Ok(list)
+        })
+    }
+
+    pub fn cleanup_unknown_games(&self, games: &[GameId]) -> Result<()> {
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let mut num = 0;
+            {
+                use schema::follows_games::dsl::*;
+                num += diesel::delete(follows_games.filter(game.eq_any(games))).execute(conn)?;
+            }
+            {
+                use schema::follows_mods::dsl::*;
+                num += diesel::delete(follows_mods.filter(game.eq_any(games))).execute(conn)?;
+            }
+            {
+                use schema::follows_users::dsl::*;
+                num += diesel::delete(follows_users.filter(game.eq_any(games))).execute(conn)?;
+            }
+            if num > 0 {
+                tracing::info!("Deleted {num} follow(s).");
+            }
+            Ok(())
+        })
+    }
+}
+
+fn set_disabled(conn: &mut SqliteConnection, user_id: UserId, value: bool) -> QueryResult<()> {
+    {
+        use schema::follows_games::dsl::*;
+        diesel::update(follows_games.filter(user.eq(user_id)))
+            .set(disabled.eq(value))
+            .execute(conn)?;
+    }
+    {
+        use schema::follows_mods::dsl::*;
+        diesel::update(follows_mods.filter(user.eq(user_id)))
+            .set(disabled.eq(value))
+            .execute(conn)?;
+    }
+    {
+        use schema::follows_users::dsl::*;
+        diesel::update(follows_users.filter(user.eq(user_id)))
+            .set(disabled.eq(value))
+            .execute(conn)?;
+    }
+    Ok(())
+}
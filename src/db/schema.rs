@@ -1,5 +1,61 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    channel_settings (channel) {
+        channel -> BigInt,
+        guild -> BigInt,
+        game -> BigInt,
+    }
+}
+
+diesel::table! {
+    delivery_failures (channel) {
+        channel -> BigInt,
+        guild -> BigInt,
+        failures -> Integer,
+        permission_failures -> Integer,
+        last_error -> Text,
+        last_failure -> BigInt,
+        paused -> Bool,
+    }
+}
+
+diesel::table! {
+    follows_games (user, game) {
+        user -> BigInt,
+        game -> BigInt,
+        disabled -> Bool,
+    }
+}
+
+diesel::table! {
+    follows_mods (user, game, mod_id) {
+        user -> BigInt,
+        game -> BigInt,
+        mod_id -> BigInt,
+        disabled -> Bool,
+    }
+}
+
+diesel::table! {
+    follows_users (user, game, name) {
+        user -> BigInt,
+        game -> BigInt,
+        name -> Text,
+        disabled -> Bool,
+    }
+}
+
+diesel::table! {
+    game_stats (game, date) {
+        game -> BigInt,
+        date -> BigInt,
+        mods -> BigInt,
+        subscribers -> BigInt,
+        downloads -> BigInt,
+    }
+}
+
 diesel::table! {
     games (id) {
         id -> BigInt,
@@ -10,21 +66,99 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    guild_games (guild, game) {
+        guild -> BigInt,
+        game -> BigInt,
+        alias -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    mod_stats (game, mod_id, date) {
+        game -> BigInt,
+        mod_id -> BigInt,
+        date -> BigInt,
+        downloads -> BigInt,
+        subscribers -> BigInt,
+        rank -> BigInt,
+        ratings_positive -> BigInt,
+        ratings_negative -> BigInt,
+    }
+}
+
+diesel::table! {
+    notification_messages (channel, mod_id) {
+        channel -> BigInt,
+        mod_id -> BigInt,
+        message -> BigInt,
+        versions -> Text,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    notification_templates (guild, subscription) {
+        guild -> BigInt,
+        subscription -> Integer,
+        content -> Nullable<Text>,
+        title -> Nullable<Text>,
+        description -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    pending_file_changes (game, mod_id) {
+        game -> BigInt,
+        mod_id -> BigInt,
+        event -> BigInt,
+        uploaded -> BigInt,
+    }
+}
+
 diesel::table! {
     settings (guild) {
         guild -> BigInt,
         game -> Nullable<BigInt>,
+        log_channel -> Nullable<BigInt>,
+        edit_window -> Nullable<BigInt>,
+        language -> Nullable<Text>,
     }
 }
 
 diesel::table! {
-    subscriptions (game, channel, tags) {
+    subscription_roles (guild, role) {
+        guild -> BigInt,
+        role -> BigInt,
+    }
+}
+
+diesel::table! {
+    subscriptions (id) {
+        id -> Integer,
         game -> BigInt,
         channel -> BigInt,
         tags -> Text,
         guild -> BigInt,
         events -> Integer,
         explicit -> Bool,
+        paused_until -> Nullable<BigInt>,
+        crosspost -> Bool,
+        style -> Integer,
+    }
+}
+
+diesel::table! {
+    subscriptions_audit (id) {
+        id -> Integer,
+        timestamp -> BigInt,
+        guild -> BigInt,
+        channel -> BigInt,
+        game -> BigInt,
+        actor -> Nullable<BigInt>,
+        action -> Text,
+        before -> Nullable<Text>,
+        after -> Nullable<Text>,
     }
 }
 
@@ -46,10 +180,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    subscriptions_mods (game, channel, mod_id) {
+        game -> BigInt,
+        channel -> BigInt,
+        guild -> BigInt,
+        mod_id -> BigInt,
+        paused_until -> Nullable<BigInt>,
+        crosspost -> Bool,
+    }
+}
+
+diesel::joinable!(guild_games -> games (game));
+diesel::joinable!(subscriptions -> games (game));
+
 diesel::allow_tables_to_appear_in_same_query!(
+    channel_settings,
+    delivery_failures,
+    follows_games,
+    follows_mods,
+    follows_users,
+    game_stats,
     games,
+    guild_games,
+    mod_stats,
+    notification_messages,
+    notification_templates,
+    pending_file_changes,
     settings,
+    subscription_roles,
     subscriptions,
+    subscriptions_audit,
     subscriptions_exclude_mods,
     subscriptions_exclude_users,
+    subscriptions_mods,
 );
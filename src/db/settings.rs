@@ -1,8 +1,17 @@
+use std::collections::HashMap;
+
 use diesel::prelude::*;
 use tokio::task::block_in_place;
 
-use super::types::{GameId, GuildId};
+use super::types::{ChannelId, GameId, GuildId, RoleId};
 use super::{schema, DbPool, Result};
+use crate::i18n::Locale;
+
+/// Maximum time in seconds a notification can be edited instead of sending a new message.
+pub const MAX_EDIT_WINDOW: i64 = 7 * 86400;
+
+/// A known game of a guild with its alias and name.
+pub type GuildGame = (GameId, Option<String>, Option<String>);
 
 #[derive(Clone)]
 pub struct Settings {
@@ -20,8 +29,11 @@ impl Settings {
             let conn = &mut self.pool.get()?;
 
             conn.transaction::<_, Error, _>(|conn| {
-                diesel::replace_into(settings)
+                diesel::insert_into(settings)
                     .values(&change)
+                    .on_conflict(guild)
+                    .do_update()
+                    .set(game.eq(game_id))
                     .execute(conn)?;
                 Ok(())
             })?;
@@ -29,6 +41,128 @@ impl Settings {
         })
     }
 
+    pub fn set_log_channel(&self, guild_id: GuildId, channel_id: Option<ChannelId>) -> Result<()> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::insert_into(settings)
+                .values((guild.eq(guild_id), log_channel.eq(channel_id)))
+                .on_conflict(guild)
+                .do_update()
+                .set(log_channel.eq(channel_id))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the channel where changes of the subscriptions are announced.
+    pub fn log_channel(&self, guild_id: GuildId) -> Result<Option<ChannelId>> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let value = settings
+                .select(log_channel)
+                .filter(guild.eq(guild_id))
+                .first::<Option<ChannelId>>(conn)
+                .optional()?
+                .flatten();
+
+            Ok(value)
+        })
+    }
+
+    /// Sets the time in seconds during which notifications of mod updates are edited.
+    pub fn set_edit_window(&self, guild_id: GuildId, seconds: Option<i64>) -> Result<()> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::insert_into(settings)
+                .values((guild.eq(guild_id), edit_window.eq(seconds)))
+                .on_conflict(guild)
+                .do_update()
+                .set(edit_window.eq(seconds))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the time in seconds during which notifications of mod updates are edited.
+    pub fn edit_window(&self, guild_id: GuildId) -> Result<Option<i64>> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let value = settings
+                .select(edit_window)
+                .filter(guild.eq(guild_id))
+                .first::<Option<i64>>(conn)
+                .optional()?
+                .flatten();
+
+            Ok(value)
+        })
+    }
+
+    /// Sets the language of the notifications, `None` resets it to English.
+    pub fn set_language(&self, guild_id: GuildId, locale: Option<Locale>) -> Result<()> {
+        use schema::settings::dsl::*;
+
+        let code = locale.map(Locale::code);
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::insert_into(settings)
+                .values((guild.eq(guild_id), language.eq(code)))
+                .on_conflict(guild)
+                .do_update()
+                .set(language.eq(code))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the language of the notifications.
+    pub fn language(&self, guild_id: GuildId) -> Result<Option<Locale>> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let value = settings
+                .select(language)
+                .filter(guild.eq(guild_id))
+                .first::<Option<String>>(conn)
+                .optional()?
+                .flatten()
+                .and_then(|code| Locale::from_code(&code));
+
+            Ok(value)
+        })
+    }
+
+    /// Loads the languages of the notifications of all guilds.
+    pub fn languages(&self) -> Result<HashMap<GuildId, Locale>> {
+        use schema::settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let list = settings
+                .select((guild, language))
+                .filter(language.is_not_null())
+                .load::<(GuildId, Option<String>)>(conn)?;
+
+            let languages = list
+                .into_iter()
+                .filter_map(|(guild_id, code)| Some((guild_id, Locale::from_code(&code?)?)))
+                .collect();
+            Ok(languages)
+        })
+    }
+
     pub fn game(&self, guild_id: GuildId) -> Result<Option<GameId>> {
         use schema::settings::dsl::*;
 
@@ -43,6 +177,174 @@ impl Settings {
         Ok(value)
     }
 
+    pub fn set_channel_game(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        game_id: Option<GameId>,
+    ) -> Result<()> {
+        use schema::channel_settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            if let Some(game_id) = game_id {
+                let change = (channel.eq(channel_id), guild.eq(guild_id), game.eq(game_id));
+                diesel::replace_into(channel_settings)
+                    .values(&change)
+                    .execute(conn)?;
+            } else {
+                diesel::delete(channel_settings.filter(channel.eq(channel_id))).execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn channel_game(&self, channel_id: ChannelId) -> Result<Option<GameId>> {
+        use schema::channel_settings::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let value = channel_settings
+                .select(game)
+                .filter(channel.eq(channel_id))
+                .first::<GameId>(conn)
+                .optional()?;
+
+            Ok(value)
+        })
+    }
+
+    /// Returns the default game of the channel or else the default game of the guild.
+    pub fn default_game(
+        &self,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+    ) -> Result<Option<GameId>> {
+        if let Some(channel_id) = channel_id {
+            if let Some(game_id) = self.channel_game(channel_id)? {
+                return Ok(Some(game_id));
+            }
+        }
+        self.game(guild_id)
+    }
+
+    /// Adds the game to the known games of the guild.
+    ///
+    /// The alias of an already known game is only changed if a new alias is given.
+    pub fn add_game(&self, guild_id: GuildId, game_id: GameId, name: Option<&str>) -> Result<()> {
+        use schema::guild_games::dsl::*;
+
+        let values = (guild.eq(guild_id), game.eq(game_id), alias.eq(name));
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let query = diesel::insert_into(guild_games).values(&values);
+            match name {
+                Some(name) => {
+                    query
+                        .on_conflict((guild, game))
+                        .do_update()
+                        .set(alias.eq(name))
+                        .execute(conn)?;
+                }
+                None => {
+                    query.on_conflict_do_nothing().execute(conn)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove_game(&self, guild_id: GuildId, game_id: GameId) -> Result<bool> {
+        use schema::guild_games::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let filter = guild_games.filter(guild.eq(guild_id).and(game.eq(game_id)));
+            let num = diesel::delete(filter).execute(conn)?;
+            Ok(num > 0)
+        })
+    }
+
+    /// Returns the games of the guild with their alias and name from the local games table.
+    pub fn games(&self, guild_id: GuildId) -> Result<Vec<GuildGame>> {
+        use schema::games;
+        use schema::guild_games::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let list = guild_games
+                .left_join(games::table)
+                .select((game, alias, games::name.nullable()))
+                .filter(guild.eq(guild_id))
+                .order_by(game.asc())
+                .load(conn)?;
+
+            Ok(list)
+        })
+    }
+
+    pub fn game_by_alias(&self, guild_id: GuildId, name: &str) -> Result<Option<GameId>> {
+        use schema::guild_games::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let value = guild_games
+                .select(game)
+                .filter(guild.eq(guild_id).and(alias.eq(name.to_lowercase())))
+                .first::<GameId>(conn)
+                .optional()?;
+
+            Ok(value)
+        })
+    }
+
+    /// Grants the role the permission to manage the subscriptions of the guild.
+    pub fn grant_role(&self, guild_id: GuildId, role_id: RoleId) -> Result<()> {
+        use schema::subscription_roles::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::insert_into(subscription_roles)
+                .values((guild.eq(guild_id), role.eq(role_id)))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    pub fn revoke_role(&self, guild_id: GuildId, role_id: RoleId) -> Result<bool> {
+        use schema::subscription_roles::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let filter = subscription_roles.filter(guild.eq(guild_id).and(role.eq(role_id)));
+            let num = diesel::delete(filter).execute(conn)?;
+            Ok(num > 0)
+        })
+    }
+
+    /// Returns the roles allowed to manage the subscriptions of the guild.
+    pub fn roles(&self, guild_id: GuildId) -> Result<Vec<RoleId>> {
+        use schema::subscription_roles::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let list = subscription_roles
+                .select(role)
+                .filter(guild.eq(guild_id))
+                .order_by(role.asc())
+                .load(conn)?;
+
+            Ok(list)
+        })
+    }
+
     pub fn cleanup(&self, guilds: &[GuildId]) -> Result<()> {
         use schema::settings::dsl::*;
 
@@ -55,6 +357,33 @@ impl Settings {
                 Err(e) => tracing::error!("{e}"),
             }
 
+            {
+                use schema::channel_settings::dsl::*;
+                let filter = channel_settings.filter(guild.ne_all(guilds));
+                match diesel::delete(filter).execute(conn) {
+                    Ok(num) => tracing::info!("Deleted {num} channel setting(s)."),
+                    Err(e) => tracing::error!("{e}"),
+                }
+            }
+
+            {
+                use schema::subscription_roles::dsl::*;
+                let filter = subscription_roles.filter(guild.ne_all(guilds));
+                match diesel::delete(filter).execute(conn) {
+                    Ok(num) => tracing::info!("Deleted {num} subscription role(s)."),
+                    Err(e) => tracing::error!("{e}"),
+                }
+            }
+
+            {
+                use schema::guild_games::dsl::*;
+                let filter = guild_games.filter(guild.ne_all(guilds));
+                match diesel::delete(filter).execute(conn) {
+                    Ok(num) => tracing::info!("Deleted {num} guild game(s)."),
+                    Err(e) => tracing::error!("{e}"),
+                }
+            }
+
             Ok(())
         })
     }
@@ -0,0 +1,144 @@
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::types::{GameId, ModId};
+use super::{schema, DbPool, Result};
+
+/// Number of days the recorded statistics are kept.
+const RETENTION_DAYS: i64 = 90;
+const DAY: i64 = 86400;
+/// Number of records inserted per statement to stay below the variable limit of `SQLite`.
+const CHUNK_SIZE: usize = 1000;
+
+#[derive(Debug, Insertable, Queryable, Selectable)]
+#[diesel(table_name = schema::game_stats)]
+pub struct GameStats {
+    pub game: GameId,
+    /// Unix timestamp of the day the statistics were recorded.
+    pub date: i64,
+    pub mods: i64,
+    pub subscribers: i64,
+    pub downloads: i64,
+}
+
+#[derive(Debug, Insertable, Queryable, Selectable)]
+#[diesel(table_name = schema::mod_stats)]
+pub struct ModStats {
+    pub game: GameId,
+    pub mod_id: ModId,
+    /// Unix timestamp of the day the statistics were recorded.
+    pub date: i64,
+    pub downloads: i64,
+    pub subscribers: i64,
+    pub rank: i64,
+    pub ratings_positive: i64,
+    pub ratings_negative: i64,
+}
+
+/// Returns the unix timestamp of the start of the day.
+pub fn day_of(timestamp: i64) -> i64 {
+    timestamp - timestamp.rem_euclid(DAY)
+}
+
+/// Saves the statistics of the games, replacing the records of the same day,
+/// and deletes the records older than the retention period.
+pub fn record_game_stats(pool: &DbPool, records: &[GameStats]) -> Result<()> {
+    block_in_place(|| {
+        use diesel::result::Error;
+        use schema::game_stats::dsl::*;
+
+        let conn = &mut pool.get()?;
+        conn.transaction::<_, Error, _>(|conn| {
+            let num = diesel::replace_into(game_stats)
+                .values(records)
+                .execute(conn)?;
+            tracing::info!("Recorded stats for {num} games.");
+
+            if let Some(first) = records.first() {
+                let cutoff = first.date - RETENTION_DAYS * DAY;
+                let num = diesel::delete(game_stats.filter(date.lt(cutoff))).execute(conn)?;
+                if num > 0 {
+                    tracing::info!("Deleted {num} outdated game stats.");
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(())
+    })
+}
+
+/// Loads the recorded statistics of a game since the given timestamp ordered by date.
+pub fn game_stats(pool: &DbPool, game_id: GameId, since: i64) -> Result<Vec<GameStats>> {
+    use schema::game_stats::dsl::*;
+
+    block_in_place(|| {
+        let conn = &mut pool.get()?;
+        let result = game_stats
+            .select(GameStats::as_select())
+            .filter(game.eq(game_id).and(date.ge(day_of(since))))
+            .order_by(date.asc())
+            .load(conn)?;
+
+        Ok(result)
+    })
+}
+
+/// Saves the statistics of the mods of a game, replacing the records of the same day,
+/// and deletes the records of the game older than the retention period.
+pub fn record_mod_stats(pool: &DbPool, game_id: GameId, records: &[ModStats]) -> Result<()> {
+    block_in_place(|| {
+        use diesel::result::Error;
+        use schema::mod_stats::dsl::*;
+
+        let conn = &mut pool.get()?;
+        conn.transaction::<_, Error, _>(|conn| {
+            for chunk in records.chunks(CHUNK_SIZE) {
+                diesel::replace_into(mod_stats)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+
+            if let Some(first) = records.first() {
+                let cutoff = first.date - RETENTION_DAYS * DAY;
+                let filter = mod_stats.filter(game.eq(game_id).and(date.lt(cutoff)));
+                diesel::delete(filter).execute(conn)?;
+            }
+            Ok(())
+        })?;
+
+        Ok(())
+    })
+}
+
+/// Loads the recorded statistics of a mod since the given timestamp ordered by date.
+pub fn mod_stats(pool: &DbPool, game_id: GameId, id: ModId, since: i64) -> Result<Vec<ModStats>> {
+    use schema::mod_stats::dsl::*;
+
+    block_in_place(|| {
+        let conn = &mut pool.get()?;
+        let result = mod_stats
+            .select(ModStats::as_select())
+            .filter(game.eq(game_id))
+            .filter(mod_id.eq(id).and(date.ge(day_of(since))))
+            .order_by(date.asc())
+            .load(conn)?;
+
+        Ok(result)
+    })
+}
+
+/// Deletes the recorded statistics of games without subscriptions.
+pub fn cleanup_mod_stats(pool: &DbPool, games: &[GameId]) -> Result<()> {
+    use schema::mod_stats::dsl::*;
+
+    block_in_place(|| {
+        let conn = &mut pool.get()?;
+
+        let num = diesel::delete(mod_stats.filter(game.ne_all(games))).execute(conn)?;
+        if num > 0 {
+            tracing::info!("Deleted {num} mod stats of unsubscribed games.");
+        }
+        Ok(())
+    })
+}
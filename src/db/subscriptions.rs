@@ -3,30 +3,128 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use diesel::prelude::*;
 use tokio::task::block_in_place;
 
+mod audit;
 mod events;
+mod failures;
+mod messages;
+mod mods;
+mod pending;
+mod style;
 mod tags;
+mod templates;
 
-use super::types::{ChannelId, GameId, GuildId, ModId};
+use super::types::{ChannelId, GameId, GuildId, ModId, UserId};
 use super::{schema, DbPool, Result};
 
 pub type ExcludedMods = HashSet<ModId>;
 pub type ExcludedUsers = HashSet<String>;
 pub type ExcludedModsMap = HashMap<(GameId, ChannelId), ExcludedMods>;
 pub type ExcludedUsersMap = HashMap<(GameId, ChannelId), ExcludedUsers>;
-pub type GroupedSubscriptions = BTreeMap<ChannelId, Vec<(GameId, Tags, Events, bool)>>;
+pub type GroupedSubscriptions = BTreeMap<ChannelId, Vec<SubscriptionSettings>>;
 
+/// A subscription of a channel with the time until it's paused.
+#[derive(Debug, Queryable)]
+pub struct PausableSubscription {
+    pub game: GameId,
+    pub tags: Tags,
+    pub events: Events,
+    pub explicit: bool,
+    pub paused_until: Option<i64>,
+}
+
+/// A subscription of a channel with its id and the name of the game if known.
+#[derive(Debug, Queryable)]
+pub struct ChannelSubscription {
+    pub id: i32,
+    pub game: GameId,
+    pub game_name: Option<String>,
+    pub tags: Tags,
+    pub events: Events,
+    pub explicit: bool,
+    pub crosspost: bool,
+    pub style: Style,
+}
+
+/// The settings of a new or edited subscription.
+pub struct SubscriptionChange {
+    pub tags: Tags,
+    pub events: Events,
+    pub explicit: bool,
+    /// Keeps the crosspost flag of an existing subscription if not set.
+    pub crosspost: Option<bool>,
+    /// Keeps the style of an existing subscription if not set.
+    pub style: Option<Style>,
+}
+
+/// The settings of a subscription listed in the overview and the export.
+#[derive(Debug)]
+pub struct SubscriptionSettings {
+    pub game: GameId,
+    pub tags: Tags,
+    pub events: Events,
+    pub explicit: bool,
+    pub crosspost: bool,
+    pub style: Style,
+    pub paused_until: Option<i64>,
+    pub template: Option<Template>,
+}
+
+/// The audit log entries of a copied or moved channel and the number of transferred records.
+#[derive(Debug, Default)]
+pub struct Transfer {
+    pub entries: Vec<AuditEntry>,
+    pub subscriptions: usize,
+    pub mods: usize,
+    /// The number of muted mods and users.
+    pub mutes: usize,
+}
+
+impl Transfer {
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions == 0 && self.mods == 0 && self.mutes == 0
+    }
+}
+
+/// The subscriptions, mod subscriptions, mutes and the default template of an imported file.
+#[derive(Default)]
+pub struct ImportRecords {
+    pub subscriptions: GroupedSubscriptions,
+    pub mods: ChannelModsMap,
+    pub excluded_mods: ExcludedModsMap,
+    pub excluded_users: ExcludedUsersMap,
+    pub template: Option<Template>,
+}
+
+pub use audit::{Action, AuditEntry};
 pub use events::Events;
+pub use failures::{DeliveryFailure, MAX_PERMISSION_FAILURES};
+pub use messages::NotificationMessage;
+pub use mods::{ChannelModsMap, ModSubscription};
+pub use pending::PendingChanges;
+pub use style::Style;
 pub use tags::Tags;
+pub use templates::{Template, Templates};
 
 #[derive(Debug, Queryable, Selectable)]
 #[diesel(table_name = schema::subscriptions)]
 pub struct Subscription {
+    pub id: i32,
     pub channel: ChannelId,
+    pub guild: GuildId,
     pub tags: Tags,
     pub events: Events,
     pub explicit: bool,
+    /// Publish the notifications in announcement channels.
+    pub crosspost: bool,
+    pub style: Style,
 }
 
+/// Value of `paused_until` for subscriptions paused until they are resumed.
+pub const PAUSED_INDEFINITELY: i64 = i64::MAX;
+
+/// Number of days the audit log entries are kept.
+const AUDIT_RETENTION_DAYS: i64 = 90;
+
 #[derive(Clone)]
 pub struct Subscriptions {
     pub pool: DbPool,
@@ -54,17 +152,55 @@ impl Subscriptions {
                 let num = diesel::delete(filter).execute(conn)?;
                 tracing::info!("Deleted {num} excluded users.");
             }
+            {
+                use schema::subscriptions_mods::dsl::*;
+                let filter = subscriptions_mods.filter(guild.ne_all(guilds));
+                let num = diesel::delete(filter).execute(conn)?;
+                tracing::info!("Deleted {num} mod subscription(s).");
+            }
+            {
+                use schema::notification_templates::dsl::*;
+                let ids = schema::subscriptions::table.select(schema::subscriptions::id);
+                let filter = notification_templates.filter(
+                    guild
+                        .ne_all(guilds)
+                        .or(subscription.ne(0).and(subscription.ne_all(ids))),
+                );
+                let num = diesel::delete(filter).execute(conn)?;
+                tracing::info!("Deleted {num} notification template(s).");
+            }
+            {
+                use schema::delivery_failures::dsl::*;
+                let filter = delivery_failures.filter(guild.ne_all(guilds));
+                let num = diesel::delete(filter).execute(conn)?;
+                tracing::info!("Deleted {num} delivery failures.");
+            }
+            {
+                use schema::subscriptions_audit::dsl::*;
+                #[allow(clippy::cast_possible_wrap)]
+                let cutoff = crate::util::current_timestamp() as i64 - AUDIT_RETENTION_DAYS * 86400;
+                let filter =
+                    subscriptions_audit.filter(guild.ne_all(guilds).or(timestamp.lt(cutoff)));
+                let num = diesel::delete(filter).execute(conn)?;
+                tracing::info!("Deleted {num} audit log entries.");
+            }
             Ok(())
         })
     }
 
-    pub fn cleanup_unknown_channels(&self, channels: &[ChannelId]) -> Result<()> {
+    /// Deletes the subscriptions of the unknown channels and returns the recorded audit entries.
+    pub fn cleanup_unknown_channels(&self, channels: &[ChannelId]) -> Result<Vec<AuditEntry>> {
         use schema::subscriptions::dsl::*;
 
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
             let filter = subscriptions.filter(channel.eq_any(channels));
+            let records = filter
+                .clone()
+                .select((guild, channel, game, tags, events, explicit))
+                .load(conn)?;
+            let entries = record_cleanup(conn, records)?;
             let num = diesel::delete(filter).execute(conn)?;
             tracing::info!("Deleted {num} subscription(s).");
 
@@ -84,17 +220,41 @@ impl Subscriptions {
                     tracing::info!("Deleted {num} excluded user entries.");
                 }
             }
-            Ok(())
+            {
+                use schema::subscriptions_mods::dsl::*;
+                let filter = subscriptions_mods.filter(channel.eq_any(channels));
+                let num = diesel::delete(filter).execute(conn)?;
+                if num > 0 {
+                    tracing::info!("Deleted {num} mod subscription(s).");
+                }
+            }
+            {
+                use schema::notification_messages::dsl::*;
+                let filter = notification_messages.filter(channel.eq_any(channels));
+                diesel::delete(filter).execute(conn)?;
+            }
+            {
+                use schema::delivery_failures::dsl::*;
+                let filter = delivery_failures.filter(channel.eq_any(channels));
+                diesel::delete(filter).execute(conn)?;
+            }
+            Ok(entries)
         })
     }
 
-    pub fn cleanup_unknown_games(&self, games: &[GameId]) -> Result<()> {
+    /// Deletes the subscriptions of the unknown games and returns the recorded audit entries.
+    pub fn cleanup_unknown_games(&self, games: &[GameId]) -> Result<Vec<AuditEntry>> {
         use schema::subscriptions::dsl::*;
 
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
             let filter = subscriptions.filter(game.eq_any(games));
+            let records = filter
+                .clone()
+                .select((guild, channel, game, tags, events, explicit))
+                .load(conn)?;
+            let entries = record_cleanup(conn, records)?;
             let num = diesel::delete(filter).execute(conn)?;
             tracing::info!("Deleted {num} subscription(s).");
 
@@ -114,7 +274,15 @@ impl Subscriptions {
                     tracing::info!("Deleted {num} excluded user entries.");
                 }
             }
-            Ok(())
+            {
+                use schema::subscriptions_mods::dsl::*;
+                let filter = subscriptions_mods.filter(game.eq_any(games));
+                let num = diesel::delete(filter).execute(conn)?;
+                if num > 0 {
+                    tracing::info!("Deleted {num} mod subscription(s).");
+                }
+            }
+            Ok(entries)
         })
     }
 
@@ -124,12 +292,39 @@ impl Subscriptions {
         let channels = block_in_place::<_, Result<_>>(|| {
             let conn = &mut self.pool.get()?;
 
-            Ok(subscriptions.select(channel).distinct().load(conn)?)
+            let mut channels = subscriptions
+                .select(channel)
+                .distinct()
+                .load::<ChannelId>(conn)?;
+            {
+                use schema::subscriptions_mods::dsl::*;
+                let list = subscriptions_mods
+                    .select(channel)
+                    .distinct()
+                    .load::<ChannelId>(conn)?;
+                channels.extend(list);
+            }
+            Ok(channels)
         })?;
 
         Ok(channels.into_iter().collect())
     }
 
+    pub fn get_games(&self) -> Result<Vec<GameId>> {
+        use schema::subscriptions::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let games = subscriptions.select(game);
+            let mod_games = {
+                use schema::subscriptions_mods::dsl::*;
+                subscriptions_mods.select(game)
+            };
+            Ok(games.union(mod_games).load(conn)?)
+        })
+    }
+
     pub fn load(
         &self,
     ) -> Result<(
@@ -140,12 +335,21 @@ impl Subscriptions {
         use super::Error;
         use schema::subscriptions::dsl::*;
 
+        #[allow(clippy::cast_possible_wrap)]
+        let now = crate::util::current_timestamp() as i64;
+
         let (list, excluded_mods, excluded_users) = block_in_place::<_, Result<_>>(|| {
             let conn = &mut self.pool.get()?;
 
             conn.transaction::<_, Error, _>(|conn| {
+                let paused_channels = {
+                    use schema::delivery_failures::dsl::*;
+                    delivery_failures.select(channel).filter(paused.eq(true))
+                };
                 let list = subscriptions
                     .select((game, Subscription::as_select()))
+                    .filter(channel.ne_all(paused_channels))
+                    .filter(paused_until.is_null().or(paused_until.le(now)))
                     .load(conn)?;
 
                 let excluded_mods = self.load_excluded_mods()?;
@@ -207,15 +411,22 @@ impl Subscriptions {
         &self,
         guild_id: GuildId,
     ) -> Result<(GroupedSubscriptions, ExcludedModsMap, ExcludedUsersMap)> {
-        let (subs, excluded_mods, excluded_users) = block_in_place::<_, Result<_>>(|| {
+        let records = block_in_place::<_, Result<_>>(|| {
             use schema::subscriptions::dsl::*;
 
             let conn = &mut self.pool.get()?;
 
             let subs = subscriptions
-                .select((channel, game, tags, events, explicit))
+                .select((
+                    id,
+                    channel,
+                    (game, tags, events, explicit, paused_until),
+                    crosspost,
+                    style,
+                ))
                 .filter(guild.eq(guild_id))
-                .load::<(ChannelId, GameId, Tags, Events, bool)>(conn)?;
+                .load::<(i32, ChannelId, PausableSubscription, bool, Style)>(conn)?;
+            let templates = templates::subscription_templates(conn, guild_id)?;
 
             let excluded_mods = {
                 use schema::subscriptions_exclude_mods::dsl::*;
@@ -234,15 +445,25 @@ impl Subscriptions {
                     .filter(guild.eq(guild_id))
                     .load::<(ChannelId, GameId, String)>(conn)?
             };
-            Ok((subs, excluded_mods, excluded_users))
+            Ok((subs, templates, excluded_mods, excluded_users))
         })?;
+        let (subs, mut templates, excluded_mods, excluded_users) = records;
 
         let subs = subs.into_iter().fold(
             GroupedSubscriptions::new(),
-            |mut map, (channel_id, game_id, tags, events, allow_explicit)| {
+            |mut map, (sub_id, channel_id, sub, publish, sub_style)| {
                 map.entry(channel_id)
                     .or_default()
-                    .push((game_id, tags, events, allow_explicit));
+                    .push(SubscriptionSettings {
+                        game: sub.game,
+                        tags: sub.tags,
+                        events: sub.events,
+                        explicit: sub.explicit,
+                        crosspost: publish,
+                        style: sub_style,
+                        paused_until: sub.paused_until,
+                        template: templates.remove(&sub_id),
+                    });
                 map
             },
         );
@@ -266,25 +487,50 @@ impl Subscriptions {
         Ok((subs, excluded_mods, excluded_users))
     }
 
-    pub fn list_for_channel(
-        &self,
-        channel_id: ChannelId,
-    ) -> Result<Vec<(GameId, Tags, Events, bool)>> {
+    /// Returns the subscriptions of the channel with the time until they are paused.
+    pub fn list_for_channel(&self, channel_id: ChannelId) -> Result<Vec<PausableSubscription>> {
         use schema::subscriptions::dsl::*;
 
         let records = block_in_place::<_, Result<_>>(|| {
             let conn = &mut self.pool.get()?;
 
             let records = subscriptions
-                .select((game, tags, events, explicit))
+                .select((game, tags, events, explicit, paused_until))
                 .filter(channel.eq(channel_id))
-                .load::<(GameId, Tags, Events, bool)>(conn)?;
+                .load::<PausableSubscription>(conn)?;
             Ok(records)
         })?;
 
         Ok(records)
     }
 
+    /// Returns the subscriptions of the channel with their ids ordered by the game name.
+    pub fn list_with_ids(&self, channel_id: ChannelId) -> Result<Vec<ChannelSubscription>> {
+        use schema::games;
+        use schema::subscriptions::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let records = subscriptions
+                .left_join(games::table)
+                .select((
+                    id,
+                    game,
+                    games::name.nullable(),
+                    tags,
+                    events,
+                    explicit,
+                    crosspost,
+                    style,
+                ))
+                .filter(channel.eq(channel_id))
+                .order_by((games::name.asc(), game.asc(), id.asc()))
+                .load(conn)?;
+            Ok(records)
+        })
+    }
+
     pub fn list_excluded_mods(
         &self,
         channel_id: ChannelId,
@@ -337,36 +583,83 @@ impl Subscriptions {
         &self,
         game_id: GameId,
         channel_id: ChannelId,
-        sub_tags: Tags,
         guild_id: GuildId,
-        evts: Events,
-        allow_explicit: bool,
-    ) -> Result<()> {
+        change: SubscriptionChange,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
         use diesel::result::Error;
         use operators::BitwiseExtensions;
         use schema::subscriptions::dsl::*;
 
+        let SubscriptionChange {
+            tags: sub_tags,
+            events: evts,
+            explicit: allow_explicit,
+            crosspost: publish,
+            style: sub_style,
+        } = change;
+
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
-            conn.transaction::<_, Error, _>(|conn| {
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let pred = game
+                    .eq(game_id)
+                    .and(channel.eq(channel_id))
+                    .and(tags.eq(&sub_tags));
+                let old = subscriptions
+                    .select((events, explicit, crosspost, style))
+                    .filter(pred)
+                    .first::<(Events, bool, bool, Style)>(conn)
+                    .optional()?;
+
+                let (new_evts, new_explicit, new_publish, new_style) = old.map_or(
+                    (
+                        evts,
+                        allow_explicit,
+                        publish.unwrap_or_default(),
+                        sub_style.unwrap_or_default(),
+                    ),
+                    |(e, x, p, s)| (e | evts, x, publish.unwrap_or(p), sub_style.unwrap_or(s)),
+                );
+
                 let values = (
                     game.eq(game_id),
                     channel.eq(channel_id),
-                    tags.eq(sub_tags),
+                    tags.eq(&sub_tags),
                     guild.eq(guild_id),
                     events.eq(evts),
                     explicit.eq(allow_explicit),
+                    crosspost.eq(new_publish),
+                    style.eq(new_style),
                 );
                 diesel::insert_into(subscriptions)
                     .values(values)
                     .on_conflict((game, channel, tags))
                     .do_update()
-                    .set(events.eq(events.bit_or(evts)))
-                    .execute(conn)
+                    .set((
+                        events.eq(events.bit_or(evts)),
+                        crosspost.eq(new_publish),
+                        style.eq(new_style),
+                    ))
+                    .execute(conn)?;
+
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::Add);
+                entry.before = old.map(|(e, x, p, s)| {
+                    let desc = audit::describe_crosspost(&audit::describe(&sub_tags, e, x), p);
+                    audit::describe_style(&desc, s)
+                });
+                let desc = audit::describe(&sub_tags, new_evts, new_explicit);
+                let desc = audit::describe_crosspost(&desc, new_publish);
+                entry.after = Some(audit::describe_style(&desc, new_style));
+                if entry.before == entry.after {
+                    return Ok(None);
+                }
+                entry.insert(conn).map(Some)
             })?;
 
-            Ok(())
+            Ok(entry)
         })
     }
 
@@ -377,25 +670,38 @@ impl Subscriptions {
         channel_id: ChannelId,
         sub_tags: Tags,
         evts: Events,
-    ) -> Result<()> {
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
         use diesel::result::Error;
         use schema::subscriptions::dsl::*;
 
         type Record = (GameId, ChannelId, Tags, GuildId, Events, bool);
 
-        let pk = (game_id, channel_id, sub_tags.to_string());
-
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
-            conn.transaction::<_, Error, _>(|conn| {
-                let first = subscriptions.find(pk).first::<Record>(conn);
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let pred = game
+                    .eq(game_id)
+                    .and(channel.eq(channel_id))
+                    .and(tags.eq(&sub_tags));
+                let first = subscriptions
+                    .select((game, channel, tags, guild, events, explicit))
+                    .filter(pred)
+                    .first::<Record>(conn);
 
                 if let Ok((game_id, channel_id, sub_tags, guild_id, old_evts, old_explicit)) = first
                 {
                     let mut new_evts = old_evts;
                     new_evts.remove(evts);
 
+                    let mut entry =
+                        AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::Remove);
+                    entry.before = Some(audit::describe(&sub_tags, old_evts, old_explicit));
+                    if !new_evts.is_empty() {
+                        entry.after = Some(audit::describe(&sub_tags, new_evts, old_explicit));
+                    }
+
                     if new_evts.is_empty() {
                         let pred = game
                             .eq(game_id)
@@ -424,22 +730,85 @@ impl Subscriptions {
                             }
                         }
                     } else {
-                        let values = (
-                            game.eq(game_id),
-                            channel.eq(channel_id),
-                            tags.eq(sub_tags),
-                            guild.eq(guild_id),
-                            events.eq(new_evts),
-                            explicit.eq(old_explicit),
-                        );
-                        diesel::replace_into(subscriptions)
-                            .values(values)
+                        let pred = game
+                            .eq(game_id)
+                            .and(channel.eq(channel_id))
+                            .and(tags.eq(sub_tags));
+                        diesel::update(subscriptions.filter(pred))
+                            .set(events.eq(new_evts))
                             .execute(conn)?;
                     }
+                    return entry.insert(conn).map(Some);
                 }
-                Ok(())
+                Ok(None)
             })?;
-            Ok(())
+            Ok(entry)
+        })
+    }
+
+    /// Changes the tags, events, explicit and crosspost flag and the style of the subscription.
+    pub fn edit(
+        &self,
+        sub_id: i32,
+        channel_id: ChannelId,
+        change: SubscriptionChange,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
+        use schema::subscriptions::dsl::*;
+
+        let SubscriptionChange {
+            tags: sub_tags,
+            events: evts,
+            explicit: allow_explicit,
+            crosspost: publish,
+            style: sub_style,
+        } = change;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let filter = subscriptions.filter(id.eq(sub_id).and(channel.eq(channel_id)));
+                let old = filter
+                    .select((guild, game, (tags, events, explicit, crosspost, style)))
+                    .first::<(GuildId, GameId, (Tags, Events, bool, bool, Style))>(conn)
+                    .optional()?;
+                let Some((
+                    guild_id,
+                    game_id,
+                    (old_tags, old_evts, old_explicit, old_publish, old_style),
+                )) = old
+                else {
+                    return Ok(None);
+                };
+                let publish = publish.unwrap_or(old_publish);
+                let sub_style = sub_style.unwrap_or(old_style);
+
+                diesel::update(filter)
+                    .set((
+                        tags.eq(&sub_tags),
+                        events.eq(evts),
+                        explicit.eq(allow_explicit),
+                        crosspost.eq(publish),
+                        style.eq(sub_style),
+                    ))
+                    .execute(conn)?;
+
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::Edit);
+                let before = audit::describe(&old_tags, old_evts, old_explicit);
+                let before = audit::describe_crosspost(&before, old_publish);
+                let after = audit::describe(&sub_tags, evts, allow_explicit);
+                let after = audit::describe_crosspost(&after, publish);
+                entry.before = Some(audit::describe_style(&before, old_style));
+                entry.after = Some(audit::describe_style(&after, sub_style));
+                if entry.before == entry.after {
+                    return Ok(None);
+                }
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
         })
     }
 
@@ -449,38 +818,76 @@ impl Subscriptions {
         channel_id: ChannelId,
         guild_id: GuildId,
         id: ModId,
-    ) -> Result<()> {
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
         use schema::subscriptions_exclude_mods::dsl::*;
 
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
-            diesel::insert_into(subscriptions_exclude_mods)
-                .values((
-                    game.eq(game_id),
-                    channel.eq(channel_id),
-                    guild.eq(guild_id),
-                    mod_id.eq(id),
-                ))
-                .on_conflict_do_nothing()
-                .execute(conn)?;
-            Ok(())
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let after = format!("Mod: {id}");
+                let num = diesel::insert_into(subscriptions_exclude_mods)
+                    .values((
+                        game.eq(game_id),
+                        channel.eq(channel_id),
+                        guild.eq(guild_id),
+                        mod_id.eq(id),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                if num == 0 {
+                    return Ok(None);
+                }
+
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::MuteMod);
+                entry.after = Some(after);
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
         })
     }
 
-    pub fn unmute_mod(&self, game_id: GameId, channel_id: ChannelId, id: ModId) -> Result<()> {
+    pub fn unmute_mod(
+        &self,
+        game_id: GameId,
+        channel_id: ChannelId,
+        id: ModId,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
         use schema::subscriptions_exclude_mods::dsl::*;
 
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
-            let filter = subscriptions_exclude_mods.filter(
-                game.eq(game_id)
-                    .and(channel.eq(channel_id))
-                    .and(mod_id.eq(id)),
-            );
-            diesel::delete(filter).execute(conn)?;
-            Ok(())
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let before = format!("Mod: {id}");
+                let pred = game.eq(game_id).and(channel.eq(channel_id));
+                let guild_id = subscriptions_exclude_mods
+                    .select(guild)
+                    .filter(pred.and(mod_id.eq(&id)))
+                    .first(conn)
+                    .optional()?;
+                let Some(guild_id) = guild_id else {
+                    return Ok(None);
+                };
+                let filter = subscriptions_exclude_mods.filter(pred.and(mod_id.eq(id)));
+                diesel::delete(filter).execute(conn)?;
+
+                let mut entry = AuditEntry::new(
+                    guild_id,
+                    channel_id,
+                    game_id,
+                    Some(actor),
+                    Action::UnmuteMod,
+                );
+                entry.before = Some(before);
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
         })
     }
 
@@ -490,40 +897,749 @@ impl Subscriptions {
         channel_id: ChannelId,
         guild_id: GuildId,
         name: &str,
-    ) -> Result<()> {
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
         use schema::subscriptions_exclude_users::dsl::*;
 
         block_in_place(|| {
             let conn = &mut self.pool.get()?;
 
-            diesel::insert_into(subscriptions_exclude_users)
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let num = diesel::insert_into(subscriptions_exclude_users)
+                    .values((
+                        game.eq(game_id),
+                        channel.eq(channel_id),
+                        guild.eq(guild_id),
+                        user.eq(name),
+                    ))
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                if num == 0 {
+                    return Ok(None);
+                }
+
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::MuteUser);
+                entry.after = Some(format!("User: {name}"));
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
+        })
+    }
+
+    pub fn unmute_user(
+        &self,
+        game_id: GameId,
+        channel_id: ChannelId,
+        name: &str,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
+        use schema::subscriptions_exclude_users::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let pred = game
+                    .eq(game_id)
+                    .and(channel.eq(channel_id))
+                    .and(user.eq(name));
+                let guild_id = subscriptions_exclude_users
+                    .select(guild)
+                    .filter(pred)
+                    .first(conn)
+                    .optional()?;
+                let Some(guild_id) = guild_id else {
+                    return Ok(None);
+                };
+                diesel::delete(subscriptions_exclude_users.filter(pred)).execute(conn)?;
+
+                let mut entry = AuditEntry::new(
+                    guild_id,
+                    channel_id,
+                    game_id,
+                    Some(actor),
+                    Action::UnmuteUser,
+                );
+                entry.before = Some(format!("User: {name}"));
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
+        })
+    }
+
+    /// Adds or replaces the subscriptions, mod subscriptions, mutes and templates of the guild in
+    /// a single transaction.
+    pub fn import(
+        &self,
+        guild_id: GuildId,
+        actor: UserId,
+        records: ImportRecords,
+    ) -> Result<Vec<AuditEntry>> {
+        use diesel::result::Error;
+
+        let ImportRecords {
+            subscriptions,
+            mods,
+            excluded_mods,
+            excluded_users,
+            template,
+        } = records;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entries = conn.transaction::<_, Error, _>(|conn| {
+                let mut entries = import_subscriptions(conn, guild_id, actor, subscriptions)?;
+                entries.extend(import_mods(conn, guild_id, actor, mods)?);
+                entries.extend(import_mutes(
+                    conn,
+                    guild_id,
+                    actor,
+                    excluded_mods,
+                    excluded_users,
+                )?);
+                if let Some(template) = template {
+                    templates::save_template(conn, guild_id, None, &template)?;
+                }
+                Ok(entries)
+            })?;
+
+            Ok(entries)
+        })
+    }
+
+    /// Pauses the subscriptions and mod subscriptions of the channel, optionally only of a game,
+    /// until the given time.
+    ///
+    /// Without a time the subscriptions stay paused until they are resumed.
+    pub fn pause(
+        &self,
+        channel_id: ChannelId,
+        game_id: Option<GameId>,
+        until: Option<i64>,
+        actor: UserId,
+    ) -> Result<Vec<AuditEntry>> {
+        let until = until.unwrap_or(PAUSED_INDEFINITELY);
+        self.set_paused(channel_id, game_id, Some(until), actor)
+    }
+
+    /// Resumes the paused subscriptions and mod subscriptions of the channel, optionally only of
+    /// a game.
+    pub fn resume(
+        &self,
+        channel_id: ChannelId,
+        game_id: Option<GameId>,
+        actor: UserId,
+    ) -> Result<Vec<AuditEntry>> {
+        self.set_paused(channel_id, game_id, None, actor)
+    }
+
+    fn set_paused(
+        &self,
+        channel_id: ChannelId,
+        game_id: Option<GameId>,
+        until: Option<i64>,
+        actor: UserId,
+    ) -> Result<Vec<AuditEntry>> {
+        use diesel::result::Error;
+        use schema::subscriptions::dsl::*;
+
+        type Record = (i32, GuildId, GameId, Tags, Events, bool, Option<i64>);
+
+        #[allow(clippy::cast_possible_wrap)]
+        let now = crate::util::current_timestamp() as i64;
+        let action = if until.is_some() {
+            Action::Pause
+        } else {
+            Action::Resume
+        };
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entries = conn.transaction::<_, Error, _>(|conn| {
+                let mut query = subscriptions
+                    .select((id, guild, game, tags, events, explicit, paused_until))
+                    .filter(channel.eq(channel_id))
+                    .into_boxed();
+                if let Some(game_id) = game_id {
+                    query = query.filter(game.eq(game_id));
+                }
+                let records = query.load::<Record>(conn)?;
+
+                let mut entries = Vec::new();
+                for (sub_id, guild_id, sub_game, sub_tags, evts, allow_explicit, old) in records {
+                    // Expired pauses are treated as not paused.
+                    let old = old.filter(|t| *t > now);
+                    if old == until {
+                        continue;
+                    }
+                    diesel::update(subscriptions.filter(id.eq(sub_id)))
+                        .set(paused_until.eq(until))
+                        .execute(conn)?;
+
+                    let desc = audit::describe(&sub_tags, evts, allow_explicit);
+                    let mut entry =
+                        AuditEntry::new(guild_id, channel_id, sub_game, Some(actor), action);
+                    entry.before = Some(audit::describe_paused(&desc, old));
+                    entry.after = Some(audit::describe_paused(&desc, until));
+                    entries.push(entry.insert(conn)?);
+                }
+
+                let records = {
+                    use schema::subscriptions_mods::dsl::*;
+
+                    let mut query = subscriptions_mods
+                        .select((guild, game, mod_id, paused_until))
+                        .filter(channel.eq(channel_id))
+                        .into_boxed();
+                    if let Some(game_id) = game_id {
+                        query = query.filter(game.eq(game_id));
+                    }
+                    query.load::<(GuildId, GameId, ModId, Option<i64>)>(conn)?
+                };
+                for (guild_id, sub_game, sub_mod, old) in records {
+                    use schema::subscriptions_mods::dsl::*;
+
+                    let old = old.filter(|t| *t > now);
+                    if old == until {
+                        continue;
+                    }
+                    let pred = game
+                        .eq(sub_game)
+                        .and(channel.eq(channel_id))
+                        .and(mod_id.eq(&sub_mod));
+                    diesel::update(subscriptions_mods.filter(pred))
+                        .set(paused_until.eq(until))
+                        .execute(conn)?;
+
+                    let desc = format!("Mod: {sub_mod}");
+                    let mut entry =
+                        AuditEntry::new(guild_id, channel_id, sub_game, Some(actor), action);
+                    entry.before = Some(audit::describe_paused(&desc, old));
+                    entry.after = Some(audit::describe_paused(&desc, until));
+                    entries.push(entry.insert(conn)?);
+                }
+                Ok(entries)
+            })?;
+            Ok(entries)
+        })
+    }
+
+    /// Copies the subscriptions and mutes of a channel to another channel of the guild.
+    pub fn copy(
+        &self,
+        guild_id: GuildId,
+        from: ChannelId,
+        to: ChannelId,
+        actor: UserId,
+    ) -> Result<Transfer> {
+        self.transfer(guild_id, from, to, actor, Action::Copy)
+    }
+
+    /// Moves the subscriptions and mutes of a channel to another channel of the guild.
+    pub fn move_to(
+        &self,
+        guild_id: GuildId,
+        from: ChannelId,
+        to: ChannelId,
+        actor: UserId,
+    ) -> Result<Transfer> {
+        self.transfer(guild_id, from, to, actor, Action::Move)
+    }
+
+    fn transfer(
+        &self,
+        guild_id: GuildId,
+        from: ChannelId,
+        to: ChannelId,
+        actor: UserId,
+        action: Action,
+    ) -> Result<Transfer> {
+        use diesel::result::Error;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let transfer = conn.transaction::<_, Error, _>(|conn| {
+                let (mut entries, subscriptions) =
+                    transfer_subscriptions(conn, guild_id, from, to, actor, action)?;
+                let (mod_entries, mods) = transfer_mods(conn, guild_id, from, to, actor, action)?;
+                let (mute_entries, mutes) =
+                    transfer_mutes(conn, guild_id, from, to, actor, action)?;
+                entries.extend(mod_entries);
+                entries.extend(mute_entries);
+                Ok(Transfer {
+                    entries,
+                    subscriptions,
+                    mods,
+                    mutes,
+                })
+            })?;
+
+            Ok(transfer)
+        })
+    }
+
+    /// Returns the latest audit log entries of the guild, optionally limited to a channel.
+    pub fn history(
+        &self,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        use schema::subscriptions_audit::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let mut query = subscriptions_audit
+                .select(AuditEntry::as_select())
+                .filter(guild.eq(guild_id))
+                .order_by(id.desc())
+                .limit(limit)
+                .into_boxed();
+            if let Some(channel_id) = channel_id {
+                query = query.filter(channel.eq(channel_id));
+            }
+
+            Ok(query.load(conn)?)
+        })
+    }
+}
+
+/// Copies or moves the subscriptions of a channel with their templates to another channel.
+fn transfer_subscriptions(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    from: ChannelId,
+    to: ChannelId,
+    actor: UserId,
+    action: Action,
+) -> QueryResult<(Vec<AuditEntry>, usize)> {
+    use schema::subscriptions::dsl::*;
+
+    let remove = action == Action::Move;
+    let mut entries = Vec::new();
+
+    let filter = subscriptions.filter(guild.eq(guild_id).and(channel.eq(from)));
+    let records = filter
+        .select((
+            id,
+            (game, tags, events, explicit, paused_until),
+            crosspost,
+            style,
+        ))
+        .load::<(i32, PausableSubscription, bool, Style)>(conn)?;
+    let num = records.len();
+
+    for (sub_id, sub, publish, sub_style) in records {
+        let PausableSubscription {
+            game: game_id,
+            tags: sub_tags,
+            events: evts,
+            explicit: allow_explicit,
+            paused_until: paused,
+        } = sub;
+        // Moved subscriptions stay paused.
+        let paused = paused.filter(|_| remove);
+        let pred = game.eq(game_id).and(channel.eq(to)).and(tags.eq(&sub_tags));
+        let old = subscriptions
+            .select((events, explicit))
+            .filter(pred)
+            .first::<(Events, bool)>(conn)
+            .optional()?;
+
+        diesel::insert_into(subscriptions)
+            .values((
+                game.eq(game_id),
+                channel.eq(to),
+                tags.eq(&sub_tags),
+                guild.eq(guild_id),
+                events.eq(evts),
+                explicit.eq(allow_explicit),
+                paused_until.eq(paused),
+                crosspost.eq(publish),
+                style.eq(sub_style),
+            ))
+            .on_conflict((game, channel, tags))
+            .do_update()
+            .set((
+                events.eq(evts),
+                explicit.eq(allow_explicit),
+                paused_until.eq(paused),
+                crosspost.eq(publish),
+                style.eq(sub_style),
+            ))
+            .execute(conn)?;
+
+        // The subscription in the target channel has a new id.
+        let new_id = subscriptions.select(id).filter(pred).first::<i32>(conn)?;
+        templates::transfer_template(conn, guild_id, sub_id, new_id, remove)?;
+
+        let desc = audit::describe(&sub_tags, evts, allow_explicit);
+        if remove {
+            let mut entry = AuditEntry::new(guild_id, from, game_id, Some(actor), action);
+            entry.before = Some(desc.clone());
+            entries.push(entry.insert(conn)?);
+        }
+
+        let mut entry = AuditEntry::new(guild_id, to, game_id, Some(actor), action);
+        entry.before = old.map(|(e, x)| audit::describe(&sub_tags, e, x));
+        entry.after = Some(desc);
+        if entry.before != entry.after {
+            entries.push(entry.insert(conn)?);
+        }
+    }
+
+    if remove {
+        diesel::delete(filter).execute(conn)?;
+    }
+    Ok((entries, num))
+}
+
+/// Copies or moves the mod subscriptions of a channel to another channel.
+fn transfer_mods(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    from: ChannelId,
+    to: ChannelId,
+    actor: UserId,
+    action: Action,
+) -> QueryResult<(Vec<AuditEntry>, usize)> {
+    use schema::subscriptions_mods::dsl::*;
+
+    let remove = action == Action::Move;
+    let mut entries = Vec::new();
+
+    let filter = subscriptions_mods.filter(guild.eq(guild_id).and(channel.eq(from)));
+    let records = filter
+        .select((game, mod_id, crosspost, paused_until))
+        .load::<ModSubscription>(conn)?;
+    let count = records.len();
+
+    for sub in records {
+        // Moved subscriptions stay paused.
+        let paused = sub.paused_until.filter(|_| remove);
+        let desc = audit::describe_crosspost(&format!("Mod: {}", sub.mod_id), sub.crosspost);
+        if remove {
+            let mut entry = AuditEntry::new(guild_id, from, sub.game, Some(actor), action);
+            entry.before = Some(audit::describe_paused(&desc, sub.paused_until));
+            entries.push(entry.insert(conn)?);
+        }
+        let num = diesel::insert_into(subscriptions_mods)
+            .values((
+                game.eq(sub.game),
+                channel.eq(to),
+                guild.eq(guild_id),
+                mod_id.eq(&sub.mod_id),
+                crosspost.eq(sub.crosspost),
+                paused_until.eq(paused),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+        if num > 0 {
+            let mut entry = AuditEntry::new(guild_id, to, sub.game, Some(actor), action);
+            entry.after = Some(audit::describe_paused(&desc, paused));
+            entries.push(entry.insert(conn)?);
+        }
+    }
+
+    if remove {
+        diesel::delete(filter).execute(conn)?;
+    }
+    Ok((entries, count))
+}
+
+/// Copies or moves the muted mods and users of a channel to another channel.
+fn transfer_mutes(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    from: ChannelId,
+    to: ChannelId,
+    actor: UserId,
+    action: Action,
+) -> QueryResult<(Vec<AuditEntry>, usize)> {
+    let remove = action == Action::Move;
+    let mut entries = Vec::new();
+    let mut count = 0;
+
+    {
+        use schema::subscriptions_exclude_mods::dsl::*;
+
+        let filter = subscriptions_exclude_mods.filter(guild.eq(guild_id).and(channel.eq(from)));
+        let records = filter
+            .select((game, mod_id))
+            .load::<(GameId, ModId)>(conn)?;
+        count += records.len();
+
+        for (game_id, id) in records {
+            let desc = format!("Muted mod: {id}");
+            if remove {
+                let mut entry = AuditEntry::new(guild_id, from, game_id, Some(actor), action);
+                entry.before = Some(desc.clone());
+                entries.push(entry.insert(conn)?);
+            }
+            let num = diesel::insert_into(subscriptions_exclude_mods)
                 .values((
                     game.eq(game_id),
-                    channel.eq(channel_id),
+                    channel.eq(to),
                     guild.eq(guild_id),
-                    user.eq(name),
+                    mod_id.eq(id),
                 ))
                 .on_conflict_do_nothing()
                 .execute(conn)?;
-            Ok(())
-        })
+            if num > 0 {
+                let mut entry = AuditEntry::new(guild_id, to, game_id, Some(actor), action);
+                entry.after = Some(desc);
+                entries.push(entry.insert(conn)?);
+            }
+        }
+
+        if remove {
+            diesel::delete(filter).execute(conn)?;
+        }
     }
 
-    pub fn unmute_user(&self, game_id: GameId, channel_id: ChannelId, name: &str) -> Result<()> {
+    {
         use schema::subscriptions_exclude_users::dsl::*;
 
-        block_in_place(|| {
-            let conn = &mut self.pool.get()?;
+        let filter = subscriptions_exclude_users.filter(guild.eq(guild_id).and(channel.eq(from)));
+        let records = filter.select((game, user)).load::<(GameId, String)>(conn)?;
+        count += records.len();
 
-            let filter = subscriptions_exclude_users.filter(
-                game.eq(game_id)
-                    .and(channel.eq(channel_id))
-                    .and(user.eq(name)),
-            );
+        for (game_id, name) in records {
+            let desc = format!("Muted user: {name}");
+            if remove {
+                let mut entry = AuditEntry::new(guild_id, from, game_id, Some(actor), action);
+                entry.before = Some(desc.clone());
+                entries.push(entry.insert(conn)?);
+            }
+            let num = diesel::insert_into(subscriptions_exclude_users)
+                .values((
+                    game.eq(game_id),
+                    channel.eq(to),
+                    guild.eq(guild_id),
+                    user.eq(&name),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if num > 0 {
+                let mut entry = AuditEntry::new(guild_id, to, game_id, Some(actor), action);
+                entry.after = Some(desc);
+                entries.push(entry.insert(conn)?);
+            }
+        }
+
+        if remove {
             diesel::delete(filter).execute(conn)?;
-            Ok(())
-        })
+        }
+    }
+    Ok((entries, count))
+}
+
+/// Records the subscriptions as automatically removed.
+fn record_cleanup(
+    conn: &mut SqliteConnection,
+    records: Vec<(GuildId, ChannelId, GameId, Tags, Events, bool)>,
+) -> QueryResult<Vec<AuditEntry>> {
+    records
+        .into_iter()
+        .map(
+            |(guild_id, channel_id, game_id, sub_tags, evts, allow_explicit)| {
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, None, Action::Cleanup);
+                entry.before = Some(audit::describe(&sub_tags, evts, allow_explicit));
+                entry.insert(conn)
+            },
+        )
+        .collect()
+}
+
+/// Adds or replaces the imported subscriptions.
+fn import_subscriptions(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    actor: UserId,
+    subs: GroupedSubscriptions,
+) -> QueryResult<Vec<AuditEntry>> {
+    let mut entries = Vec::new();
+
+    for (channel_id, subs) in subs {
+        for sub in subs {
+            use schema::subscriptions::dsl::*;
+
+            let pred = game
+                .eq(sub.game)
+                .and(channel.eq(channel_id))
+                .and(tags.eq(&sub.tags));
+            let old = subscriptions
+                .select((events, explicit, crosspost, style, paused_until))
+                .filter(pred)
+                .first::<(Events, bool, bool, Style, Option<i64>)>(conn)
+                .optional()?;
+
+            let mut entry =
+                AuditEntry::new(guild_id, channel_id, sub.game, Some(actor), Action::Import);
+            entry.before = old.map(|(e, x, p, s, paused)| {
+                audit::describe_settings(&sub.tags, e, x, p, s, paused)
+            });
+            entry.after = Some(audit::describe_settings(
+                &sub.tags,
+                sub.events,
+                sub.explicit,
+                sub.crosspost,
+                sub.style,
+                sub.paused_until,
+            ));
+
+            let values = (
+                events.eq(sub.events),
+                explicit.eq(sub.explicit),
+                crosspost.eq(sub.crosspost),
+                style.eq(sub.style),
+                paused_until.eq(sub.paused_until),
+            );
+            diesel::insert_into(subscriptions)
+                .values((
+                    game.eq(sub.game),
+                    channel.eq(channel_id),
+                    tags.eq(&sub.tags),
+                    guild.eq(guild_id),
+                    values,
+                ))
+                .on_conflict((game, channel, tags))
+                .do_update()
+                .set(values)
+                .execute(conn)?;
+
+            if let Some(template) = &sub.template {
+                let sub_id = subscriptions.select(id).filter(pred).first::<i32>(conn)?;
+                templates::save_template(conn, guild_id, Some(sub_id), template)?;
+            }
+
+            if entry.before != entry.after {
+                entries.push(entry.insert(conn)?);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Adds or replaces the imported mod subscriptions.
+fn import_mods(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    actor: UserId,
+    mods: ChannelModsMap,
+) -> QueryResult<Vec<AuditEntry>> {
+    let mut entries = Vec::new();
+
+    for (channel_id, mods) in mods {
+        use schema::subscriptions_mods::dsl::*;
+
+        for sub in mods {
+            let pred = game
+                .eq(sub.game)
+                .and(channel.eq(channel_id))
+                .and(mod_id.eq(&sub.mod_id));
+            let old = subscriptions_mods
+                .select((crosspost, paused_until))
+                .filter(pred)
+                .first::<(bool, Option<i64>)>(conn)
+                .optional()?;
+
+            let desc = format!("Mod: {}", sub.mod_id);
+            let describe = |publish, paused| {
+                audit::describe_paused(&audit::describe_crosspost(&desc, publish), paused)
+            };
+            let mut entry =
+                AuditEntry::new(guild_id, channel_id, sub.game, Some(actor), Action::AddMod);
+            entry.before = old.map(|(p, paused)| describe(p, paused));
+            entry.after = Some(describe(sub.crosspost, sub.paused_until));
+
+            let values = (
+                crosspost.eq(sub.crosspost),
+                paused_until.eq(sub.paused_until),
+            );
+            diesel::insert_into(subscriptions_mods)
+                .values((
+                    game.eq(sub.game),
+                    channel.eq(channel_id),
+                    guild.eq(guild_id),
+                    mod_id.eq(&sub.mod_id),
+                    values,
+                ))
+                .on_conflict((game, channel, mod_id))
+                .do_update()
+                .set(values)
+                .execute(conn)?;
+
+            if entry.before != entry.after {
+                entries.push(entry.insert(conn)?);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Adds the imported muted mods and users.
+fn import_mutes(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    actor: UserId,
+    excluded_mods: ExcludedModsMap,
+    excluded_users: ExcludedUsersMap,
+) -> QueryResult<Vec<AuditEntry>> {
+    let mut entries = Vec::new();
+
+    for ((game_id, channel_id), mods) in excluded_mods {
+        use schema::subscriptions_exclude_mods::dsl::*;
+
+        for id in mods {
+            let after = format!("Mod: {id}");
+            let num = diesel::insert_into(subscriptions_exclude_mods)
+                .values((
+                    game.eq(game_id),
+                    channel.eq(channel_id),
+                    guild.eq(guild_id),
+                    mod_id.eq(id),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if num > 0 {
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::MuteMod);
+                entry.after = Some(after);
+                entries.push(entry.insert(conn)?);
+            }
+        }
+    }
+
+    for ((game_id, channel_id), users) in excluded_users {
+        use schema::subscriptions_exclude_users::dsl::*;
+
+        for name in users {
+            let num = diesel::insert_into(subscriptions_exclude_users)
+                .values((
+                    game.eq(game_id),
+                    channel.eq(channel_id),
+                    guild.eq(guild_id),
+                    user.eq(&name),
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if num > 0 {
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::MuteUser);
+                entry.after = Some(format!("User: {name}"));
+                entries.push(entry.insert(conn)?);
+            }
+        }
     }
+    Ok(entries)
 }
 
 mod operators {
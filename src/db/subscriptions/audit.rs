@@ -0,0 +1,226 @@
+use std::fmt;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::prelude::*;
+use diesel::serialize::{self, ToSql};
+use diesel::sql_types::Text;
+use diesel::sqlite::Sqlite;
+
+use super::{Events, Style, Tags};
+use crate::db::schema;
+use crate::db::types::{ChannelId, GameId, GuildId, UserId};
+use crate::util::{self, TimestampStyle};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+pub enum Action {
+    Add,
+    Remove,
+    Edit,
+    Pause,
+    Resume,
+    AddMod,
+    RemoveMod,
+    MuteMod,
+    UnmuteMod,
+    MuteUser,
+    UnmuteUser,
+    Import,
+    Copy,
+    Move,
+    /// Subscription removed automatically for an unknown channel or game.
+    Cleanup,
+}
+
+impl Action {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Remove => "rm",
+            Self::Edit => "edit",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::AddMod => "add-mod",
+            Self::RemoveMod => "rm-mod",
+            Self::MuteMod => "mute-mod",
+            Self::UnmuteMod => "unmute-mod",
+            Self::MuteUser => "mute-user",
+            Self::UnmuteUser => "unmute-user",
+            Self::Import => "import",
+            Self::Copy => "copy",
+            Self::Move => "move",
+            Self::Cleanup => "cleanup",
+        }
+    }
+
+    /// The English description of the action shown in the audit log.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Add => "Subscription added",
+            Self::Remove => "Subscription removed",
+            Self::Edit => "Subscription edited",
+            Self::Pause => "Subscription paused",
+            Self::Resume => "Subscription resumed",
+            Self::AddMod => "Mod subscription added",
+            Self::RemoveMod => "Mod subscription removed",
+            Self::MuteMod => "Mod muted",
+            Self::UnmuteMod => "Mod unmuted",
+            Self::MuteUser => "User muted",
+            Self::UnmuteUser => "User unmuted",
+            Self::Import => "Subscription imported",
+            Self::Copy => "Subscription copied",
+            Self::Move => "Subscription moved",
+            Self::Cleanup => "Subscription removed automatically",
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromSql<Text, Sqlite> for Action {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Sqlite>>::from_sql(bytes)?;
+        match value.as_str() {
+            "add" => Ok(Self::Add),
+            "rm" => Ok(Self::Remove),
+            "edit" => Ok(Self::Edit),
+            "pause" => Ok(Self::Pause),
+            "resume" => Ok(Self::Resume),
+            "add-mod" => Ok(Self::AddMod),
+            "rm-mod" => Ok(Self::RemoveMod),
+            "mute-mod" => Ok(Self::MuteMod),
+            "unmute-mod" => Ok(Self::UnmuteMod),
+            "mute-user" => Ok(Self::MuteUser),
+            "unmute-user" => Ok(Self::UnmuteUser),
+            "import" => Ok(Self::Import),
+            "copy" => Ok(Self::Copy),
+            "move" => Ok(Self::Move),
+            "cleanup" => Ok(Self::Cleanup),
+            _ => Err(format!("unknown audit action: {value}").into()),
+        }
+    }
+}
+
+impl ToSql<Text, Sqlite> for Action {
+    fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.as_str());
+        Ok(serialize::IsNull::No)
+    }
+}
+
+#[derive(Debug, Insertable, Queryable, Selectable)]
+#[diesel(table_name = schema::subscriptions_audit)]
+pub struct AuditEntry {
+    #[diesel(skip_insertion)]
+    pub id: i32,
+    pub timestamp: i64,
+    pub guild: GuildId,
+    pub channel: ChannelId,
+    pub game: GameId,
+    /// The user who made the change, `None` for automatic changes.
+    pub actor: Option<UserId>,
+    pub action: Action,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl AuditEntry {
+    pub(super) fn new(
+        guild: GuildId,
+        channel: ChannelId,
+        game: GameId,
+        actor: Option<UserId>,
+        action: Action,
+    ) -> Self {
+        #[allow(clippy::cast_possible_wrap)]
+        let timestamp = util::current_timestamp() as i64;
+        Self {
+            id: 0,
+            timestamp,
+            guild,
+            channel,
+            game,
+            actor,
+            action,
+            before: None,
+            after: None,
+        }
+    }
+
+    pub(super) fn insert(self, conn: &mut SqliteConnection) -> QueryResult<Self> {
+        use schema::subscriptions_audit::dsl::*;
+
+        diesel::insert_into(subscriptions_audit)
+            .values(&self)
+            .execute(conn)?;
+        Ok(self)
+    }
+}
+
+/// Describes the settings of a subscription for the audit log.
+pub(super) fn describe(tags: &Tags, events: Events, explicit: bool) -> String {
+    let events = match (events.contains(Events::NEW), events.contains(Events::UPD)) {
+        (true, false) => "new mods",
+        (false, true) => "updated mods",
+        _ => "new & updated mods",
+    };
+    let mut s = format!("Events: {events}");
+    if !tags.is_empty() {
+        let tags = tags.iter().map(String::as_str).collect::<Vec<_>>();
+        s.push_str(" | Tags: ");
+        s.push_str(&tags.join(", "));
+    }
+    if !explicit {
+        s.push_str(" | No explicit content");
+    }
+    s
+}
+
+/// Describes all settings of a subscription for the audit log.
+pub(super) fn describe_settings(
+    tags: &Tags,
+    events: Events,
+    explicit: bool,
+    crosspost: bool,
+    style: Style,
+    paused_until: Option<i64>,
+) -> String {
+    let desc = describe_crosspost(&describe(tags, events, explicit), crosspost);
+    describe_paused(&describe_style(&desc, style), paused_until)
+}
+
+/// Appends the crosspost flag to the description of a subscription.
+pub(super) fn describe_crosspost(desc: &str, crosspost: bool) -> String {
+    if crosspost {
+        format!("{desc} | Crosspost")
+    } else {
+        desc.to_owned()
+    }
+}
+
+/// Appends the notification style to the description of a subscription.
+pub(super) fn describe_style(desc: &str, style: Style) -> String {
+    if style == Style::Full {
+        desc.to_owned()
+    } else {
+        format!("{desc} | Style: {style}")
+    }
+}
+
+/// Appends the pause state to the description of a subscription.
+pub(super) fn describe_paused(desc: &str, paused_until: Option<i64>) -> String {
+    match paused_until {
+        Some(super::PAUSED_INDEFINITELY) => format!("{desc} | Paused"),
+        Some(until) => {
+            let until = util::discord_timestamp(until, TimestampStyle::DateTime);
+            format!("{desc} | Paused until {until}")
+        }
+        None => desc.to_owned(),
+    }
+}
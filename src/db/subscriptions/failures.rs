@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::Subscriptions;
+use crate::db::types::{ChannelId, GuildId};
+use crate::db::{schema, Result};
+use crate::util;
+
+/// Number of consecutive permission failures after which a channel is paused.
+pub const MAX_PERMISSION_FAILURES: i32 = 5;
+
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = schema::delivery_failures)]
+pub struct DeliveryFailure {
+    pub channel: ChannelId,
+    /// Number of consecutive failed deliveries.
+    pub failures: i32,
+    pub last_error: String,
+    pub last_failure: i64,
+    pub paused: bool,
+}
+
+impl Subscriptions {
+    /// Records a failed delivery to the channel.
+    ///
+    /// Returns the guild of the channel if the channel has been paused after too many
+    /// permission failures.
+    pub fn record_failure(
+        &self,
+        channel_id: ChannelId,
+        error: &str,
+        permission: bool,
+    ) -> Result<Option<GuildId>> {
+        use diesel::result::Error;
+        use schema::delivery_failures::dsl::*;
+
+        #[allow(clippy::cast_possible_wrap)]
+        let now = util::current_timestamp() as i64;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let paused_guild = conn.transaction::<_, Error, _>(|conn| {
+                let guild_id = {
+                    use schema::subscriptions::dsl::*;
+                    subscriptions
+                        .select(guild)
+                        .filter(channel.eq(channel_id))
+                        .first::<GuildId>(conn)
+                        .optional()?
+                };
+                let guild_id = if guild_id.is_some() {
+                    guild_id
+                } else {
+                    use schema::subscriptions_mods::dsl::*;
+                    subscriptions_mods
+                        .select(guild)
+                        .filter(channel.eq(channel_id))
+                        .first::<GuildId>(conn)
+                        .optional()?
+                };
+                let Some(guild_id) = guild_id else {
+                    return Ok(None);
+                };
+
+                let old = delivery_failures
+                    .select((permission_failures, paused))
+                    .filter(channel.eq(channel_id))
+                    .first::<(i32, bool)>(conn)
+                    .optional()?;
+                let (old_permission_failures, was_paused) = old.unwrap_or_default();
+
+                let new_permission_failures = if permission {
+                    old_permission_failures + 1
+                } else {
+                    0
+                };
+                let pause = was_paused || new_permission_failures >= MAX_PERMISSION_FAILURES;
+
+                diesel::insert_into(delivery_failures)
+                    .values((
+                        channel.eq(channel_id),
+                        guild.eq(guild_id),
+                        failures.eq(1),
+                        permission_failures.eq(new_permission_failures),
+                        last_error.eq(error),
+                        last_failure.eq(now),
+                        paused.eq(pause),
+                    ))
+                    .on_conflict(channel)
+                    .do_update()
+                    .set((
+                        failures.eq(failures + 1),
+                        permission_failures.eq(new_permission_failures),
+                        last_error.eq(error),
+                        last_failure.eq(now),
+                        paused.eq(pause),
+                    ))
+                    .execute(conn)?;
+
+                Ok((pause && !was_paused).then_some(guild_id))
+            })?;
+            Ok(paused_guild)
+        })
+    }
+
+    /// Resets the failures of the channel after a successful delivery.
+    pub fn reset_failures(&self, channel_id: ChannelId) -> Result<()> {
+        use schema::delivery_failures::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::delete(delivery_failures.filter(channel.eq(channel_id))).execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the channels with recorded delivery failures.
+    pub fn failing_channels(&self) -> Result<HashSet<ChannelId>> {
+        use schema::delivery_failures::dsl::*;
+
+        let list = block_in_place::<_, Result<_>>(|| {
+            let conn = &mut self.pool.get()?;
+            Ok(delivery_failures.select(channel).load::<ChannelId>(conn)?)
+        })?;
+
+        Ok(list.into_iter().collect())
+    }
+
+    /// Returns the delivery failures of the channels of the guild.
+    pub fn failures(&self, guild_id: GuildId) -> Result<HashMap<ChannelId, DeliveryFailure>> {
+        use schema::delivery_failures::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let list = delivery_failures
+                .select(DeliveryFailure::as_select())
+                .filter(guild.eq(guild_id))
+                .load::<DeliveryFailure>(conn)?;
+
+            Ok(list.into_iter().map(|f| (f.channel, f)).collect())
+        })
+    }
+}
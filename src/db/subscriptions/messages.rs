@@ -0,0 +1,81 @@
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::Subscriptions;
+use crate::db::types::{ChannelId, MessageId, ModId};
+use crate::db::{schema, Result, MAX_EDIT_WINDOW};
+
+/// A notification message of a mod update that can be edited.
+pub struct NotificationMessage {
+    pub message: MessageId,
+    /// The versions listed in the notification.
+    pub versions: Vec<String>,
+    pub created: i64,
+}
+
+impl Subscriptions {
+    /// Returns the last notification of the mod in the channel created after `since`.
+    pub fn last_notification(
+        &self,
+        channel_id: ChannelId,
+        id: &ModId,
+        since: i64,
+    ) -> Result<Option<NotificationMessage>> {
+        use schema::notification_messages::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let record = notification_messages
+                .select((message, versions, created))
+                .filter(channel.eq(channel_id).and(mod_id.eq(id)))
+                .filter(created.gt(since))
+                .first::<(MessageId, String, i64)>(conn)
+                .optional()?;
+
+            Ok(record.map(|(msg, list, time)| NotificationMessage {
+                message: msg,
+                versions: list.lines().map(String::from).collect(),
+                created: time,
+            }))
+        })
+    }
+
+    /// Remembers the notification of the mod in the channel and forgets expired notifications.
+    pub fn save_notification(
+        &self,
+        channel_id: ChannelId,
+        id: &ModId,
+        notification: &NotificationMessage,
+    ) -> Result<()> {
+        use schema::notification_messages::dsl::*;
+
+        #[allow(clippy::cast_possible_wrap)]
+        let now = crate::util::current_timestamp() as i64;
+        let list = notification.versions.join("\n");
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            diesel::insert_into(notification_messages)
+                .values((
+                    channel.eq(channel_id),
+                    mod_id.eq(id),
+                    message.eq(notification.message),
+                    versions.eq(&list),
+                    created.eq(notification.created),
+                ))
+                .on_conflict((channel, mod_id))
+                .do_update()
+                .set((
+                    message.eq(notification.message),
+                    versions.eq(&list),
+                    created.eq(notification.created),
+                ))
+                .execute(conn)?;
+
+            let expired = notification_messages.filter(created.lt(now - MAX_EDIT_WINDOW));
+            diesel::delete(expired).execute(conn)?;
+            Ok(())
+        })
+    }
+}
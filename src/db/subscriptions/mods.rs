@@ -0,0 +1,211 @@
+use std::collections::{BTreeMap, HashMap};
+
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::{audit, Action, AuditEntry, Subscriptions};
+use crate::db::types::{ChannelId, GameId, GuildId, ModId, UserId};
+use crate::db::{schema, Result};
+
+/// The channels subscribed to single mods grouped by game with their crosspost flag.
+pub type SubscribedModsMap = HashMap<GameId, Vec<(ChannelId, GuildId, ModId, bool)>>;
+/// The subscribed mods grouped by channel.
+pub type ChannelModsMap = BTreeMap<ChannelId, Vec<ModSubscription>>;
+
+/// A subscription of a channel to the updates of a single mod.
+#[derive(Debug, Queryable)]
+pub struct ModSubscription {
+    pub game: GameId,
+    pub mod_id: ModId,
+    /// Publish the notifications in announcement channels.
+    pub crosspost: bool,
+    pub paused_until: Option<i64>,
+}
+
+impl Subscriptions {
+    /// Subscribes the channel to the updates of a single mod or changes the crosspost flag of
+    /// an existing subscription.
+    pub fn add_mod(
+        &self,
+        game_id: GameId,
+        channel_id: ChannelId,
+        guild_id: GuildId,
+        id: &ModId,
+        publish: Option<bool>,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
+        use schema::subscriptions_mods::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let pred = game
+                    .eq(game_id)
+                    .and(channel.eq(channel_id))
+                    .and(mod_id.eq(id));
+                let old = subscriptions_mods
+                    .select(crosspost)
+                    .filter(pred)
+                    .first::<bool>(conn)
+                    .optional()?;
+                let new_publish = publish.or(old).unwrap_or_default();
+
+                diesel::insert_into(subscriptions_mods)
+                    .values((
+                        game.eq(game_id),
+                        channel.eq(channel_id),
+                        guild.eq(guild_id),
+                        mod_id.eq(id),
+                        crosspost.eq(new_publish),
+                    ))
+                    .on_conflict((game, channel, mod_id))
+                    .do_update()
+                    .set(crosspost.eq(new_publish))
+                    .execute(conn)?;
+
+                let desc = format!("Mod: {id}");
+                let mut entry =
+                    AuditEntry::new(guild_id, channel_id, game_id, Some(actor), Action::AddMod);
+                entry.before = old.map(|p| audit::describe_crosspost(&desc, p));
+                entry.after = Some(audit::describe_crosspost(&desc, new_publish));
+                if entry.before == entry.after {
+                    return Ok(None);
+                }
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
+        })
+    }
+
+    /// Unsubscribes the channel from the updates of a single mod.
+    pub fn remove_mod(
+        &self,
+        game_id: GameId,
+        channel_id: ChannelId,
+        id: &ModId,
+        actor: UserId,
+    ) -> Result<Option<AuditEntry>> {
+        use diesel::result::Error;
+        use schema::subscriptions_mods::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let entry = conn.transaction::<_, Error, _>(|conn| {
+                let pred = game
+                    .eq(game_id)
+                    .and(channel.eq(channel_id))
+                    .and(mod_id.eq(id));
+                let old_guild = subscriptions_mods
+                    .select(guild)
+                    .filter(pred)
+                    .first::<GuildId>(conn)
+                    .optional()?;
+                let Some(guild_id) = old_guild else {
+                    return Ok(None);
+                };
+                diesel::delete(subscriptions_mods.filter(pred)).execute(conn)?;
+
+                let mut entry = AuditEntry::new(
+                    guild_id,
+                    channel_id,
+                    game_id,
+                    Some(actor),
+                    Action::RemoveMod,
+                );
+                entry.before = Some(format!("Mod: {id}"));
+                entry.insert(conn).map(Some)
+            })?;
+            Ok(entry)
+        })
+    }
+
+    /// Returns the mods the channel is subscribed to.
+    pub fn list_mods(&self, channel_id: ChannelId) -> Result<Vec<ModSubscription>> {
+        use schema::subscriptions_mods::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let list = subscriptions_mods
+                .select((game, mod_id, crosspost, paused_until))
+                .filter(channel.eq(channel_id))
+                .order((game, mod_id))
+                .load(conn)?;
+            Ok(list)
+        })
+    }
+
+    /// Returns the mod subscriptions of the guild grouped by channel.
+    pub fn list_mods_for_guild(&self, guild_id: GuildId) -> Result<ChannelModsMap> {
+        use schema::subscriptions_mods::dsl::*;
+
+        let list = block_in_place::<_, Result<_>>(|| {
+            let conn = &mut self.pool.get()?;
+
+            let list = subscriptions_mods
+                .select((channel, (game, mod_id, crosspost, paused_until)))
+                .filter(guild.eq(guild_id))
+                .order((channel, game, mod_id))
+                .load::<(ChannelId, ModSubscription)>(conn)?;
+            Ok(list)
+        })?;
+
+        Ok(list
+            .into_iter()
+            .fold(ChannelModsMap::new(), |mut map, (channel_id, sub)| {
+                map.entry(channel_id).or_default().push(sub);
+                map
+            }))
+    }
+
+    /// Returns the subscribed mods of all channels.
+    pub fn get_mods(&self) -> Result<Vec<(GameId, ModId)>> {
+        use schema::subscriptions_mods::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            let list = subscriptions_mods
+                .select((game, mod_id))
+                .distinct()
+                .load(conn)?;
+            Ok(list)
+        })
+    }
+
+    /// Loads the mod subscriptions that are not paused.
+    pub fn load_mods(&self) -> Result<SubscribedModsMap> {
+        use schema::subscriptions_mods::dsl::*;
+
+        #[allow(clippy::cast_possible_wrap)]
+        let now = crate::util::current_timestamp() as i64;
+
+        let list = block_in_place::<_, Result<_>>(|| {
+            let conn = &mut self.pool.get()?;
+
+            let paused_channels = {
+                use schema::delivery_failures::dsl::*;
+                delivery_failures.select(channel).filter(paused.eq(true))
+            };
+            let list = subscriptions_mods
+                .select((game, channel, guild, mod_id, crosspost))
+                .filter(channel.ne_all(paused_channels))
+                .filter(paused_until.is_null().or(paused_until.le(now)))
+                .load::<(GameId, ChannelId, GuildId, ModId, bool)>(conn)?;
+            Ok(list)
+        })?;
+
+        Ok(list.into_iter().fold(
+            SubscribedModsMap::new(),
+            |mut map, (game_id, channel_id, guild_id, id, publish)| {
+                map.entry(game_id)
+                    .or_default()
+                    .push((channel_id, guild_id, id, publish));
+                map
+            },
+        ))
+    }
+}
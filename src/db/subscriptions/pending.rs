@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use modio::types::id::{EventId, ModId};
+use tokio::task::block_in_place;
+
+use super::Subscriptions;
+use crate::db::types::GameId;
+use crate::db::{schema, Error, Result};
+
+/// File changes held back by the debouncing with the latest event and the upload time.
+pub type PendingChanges = BTreeMap<ModId, (EventId, i64)>;
+
+impl Subscriptions {
+    /// Returns the held back file changes of the game.
+    pub fn pending_changes(&self, game_id: GameId) -> Result<PendingChanges> {
+        use schema::pending_file_changes::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let list = pending_file_changes
+                .select((mod_id, event, uploaded))
+                .filter(game.eq(game_id))
+                .load::<(i64, i64, i64)>(conn)?;
+
+            #[allow(clippy::cast_sign_loss)]
+            Ok(list
+                .into_iter()
+                .map(|(id, evt, time)| (ModId::new(id as u64), (EventId::new(evt as u64), time)))
+                .collect())
+        })
+    }
+
+    /// Replaces the held back file changes of the game.
+    pub fn save_pending_changes(&self, game_id: GameId, changes: &PendingChanges) -> Result<()> {
+        use schema::pending_file_changes::dsl::*;
+
+        #[allow(clippy::cast_possible_wrap)]
+        let values = changes
+            .iter()
+            .map(|(id, (evt, time))| {
+                (
+                    game.eq(game_id),
+                    mod_id.eq(id.get() as i64),
+                    event.eq(evt.get() as i64),
+                    uploaded.eq(*time),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            conn.transaction::<_, Error, _>(|conn| {
+                diesel::delete(pending_file_changes.filter(game.eq(game_id))).execute(conn)?;
+                diesel::insert_into(pending_file_changes)
+                    .values(values)
+                    .execute(conn)?;
+                Ok(())
+            })
+        })
+    }
+
+    /// Forgets the held back file changes of games that are no longer polled.
+    pub fn cleanup_pending_changes(&self, games: &[GameId]) -> Result<()> {
+        use schema::pending_file_changes::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            diesel::delete(pending_file_changes.filter(game.ne_all(games))).execute(conn)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+use std::str::FromStr;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::serialize::{self, ToSql};
+use diesel::sql_types::Integer;
+use diesel::sqlite::Sqlite;
+
+/// The layout of the notifications of a subscription.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Integer)]
+pub enum Style {
+    /// Embed with a big image and all the details of the mod.
+    #[default]
+    Full = 0,
+    /// Single-line embed with a small thumbnail.
+    Compact = 1,
+    /// Plain text message without an embed.
+    Text = 2,
+}
+
+impl Style {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Compact => "compact",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Style {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "compact" => Ok(Self::Compact),
+            "text" => Ok(Self::Text),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FromSql<Integer, Sqlite> for Style {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        match i32::from_sql(bytes)? {
+            1 => Ok(Self::Compact),
+            2 => Ok(Self::Text),
+            _ => Ok(Self::Full),
+        }
+    }
+}
+
+impl ToSql<Integer, Sqlite> for Style {
+    fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(*self as i32);
+        Ok(serialize::IsNull::No)
+    }
+}
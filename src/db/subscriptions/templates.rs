@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use tokio::task::block_in_place;
+
+use super::Subscriptions;
+use crate::db::types::GuildId;
+use crate::db::{schema, Result};
+
+/// Value of the `subscription` column for the default template of a guild.
+const GUILD_TEMPLATE: i32 = 0;
+
+/// A template for the content, title and description of the notifications.
+///
+/// Fields without a template keep the default text.
+#[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Queryable)]
+pub struct Template {
+    pub content: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Template {
+    pub fn is_empty(&self) -> bool {
+        self.content.is_none() && self.title.is_none() && self.description.is_none()
+    }
+
+    /// Fills the fields without a template with the fields of the other template.
+    pub fn or(self, other: &Template) -> Template {
+        Template {
+            content: self.content.or_else(|| other.content.clone()),
+            title: self.title.or_else(|| other.title.clone()),
+            description: self.description.or_else(|| other.description.clone()),
+        }
+    }
+}
+
+/// The default templates of the guilds and the templates of the subscriptions.
+#[derive(Default)]
+pub struct Templates {
+    guilds: HashMap<GuildId, Template>,
+    subscriptions: HashMap<i32, Template>,
+}
+
+impl Templates {
+    /// Returns the template of the subscription completed by the default template of the guild.
+    pub fn get(&self, sub_id: i32, guild_id: GuildId) -> Option<Template> {
+        match (self.subscriptions.get(&sub_id), self.guilds.get(&guild_id)) {
+            (Some(template), Some(default)) => Some(template.clone().or(default)),
+            (Some(template), None) | (None, Some(template)) => Some(template.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the default template of the guild.
+    pub fn guild(&self, guild_id: GuildId) -> Option<Template> {
+        self.guilds.get(&guild_id).cloned()
+    }
+}
+
+impl Subscriptions {
+    /// Loads the templates of all guilds and subscriptions.
+    pub fn load_templates(&self) -> Result<Templates> {
+        use schema::notification_templates::dsl::*;
+
+        let list = block_in_place::<_, Result<_>>(|| {
+            let conn = &mut self.pool.get()?;
+            let list = notification_templates
+                .select((guild, subscription, (content, title, description)))
+                .load::<(GuildId, i32, Template)>(conn)?;
+            Ok(list)
+        })?;
+
+        let mut templates = Templates::default();
+        for (guild_id, sub_id, template) in list {
+            if sub_id == GUILD_TEMPLATE {
+                templates.guilds.insert(guild_id, template);
+            } else {
+                templates.subscriptions.insert(sub_id, template);
+            }
+        }
+        Ok(templates)
+    }
+
+    /// Returns the template of the subscription or the default template of the guild.
+    pub fn template(&self, guild_id: GuildId, sub_id: Option<i32>) -> Result<Option<Template>> {
+        use schema::notification_templates::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+            let template = notification_templates
+                .select((content, title, description))
+                .filter(guild.eq(guild_id))
+                .filter(subscription.eq(sub_id.unwrap_or(GUILD_TEMPLATE)))
+                .first::<Template>(conn)
+                .optional()?;
+
+            Ok(template)
+        })
+    }
+
+    /// Returns the template of the subscription completed by the default template of the guild.
+    pub fn subscription_template(
+        &self,
+        guild_id: GuildId,
+        sub_id: i32,
+    ) -> Result<Option<Template>> {
+        let default = self.template(guild_id, None)?;
+        let template = self.template(guild_id, Some(sub_id))?;
+        Ok(match (template, default) {
+            (Some(template), Some(default)) => Some(template.or(&default)),
+            (template, default) => template.or(default),
+        })
+    }
+
+    /// Saves the template of the subscription or the default template of the guild.
+    ///
+    /// An empty template is removed.
+    pub fn set_template(
+        &self,
+        guild_id: GuildId,
+        sub_id: Option<i32>,
+        template: &Template,
+    ) -> Result<()> {
+        use schema::notification_templates::dsl::*;
+
+        block_in_place(|| {
+            let conn = &mut self.pool.get()?;
+
+            if template.is_empty() {
+                let pred = subscription.eq(sub_id.unwrap_or(GUILD_TEMPLATE));
+                let filter = notification_templates.filter(guild.eq(guild_id).and(pred));
+                diesel::delete(filter).execute(conn)?;
+                return Ok(());
+            }
+
+            save_template(conn, guild_id, sub_id, template)?;
+            Ok(())
+        })
+    }
+}
+
+/// Returns the templates of the subscriptions of the guild by subscription id.
+pub(super) fn subscription_templates(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+) -> QueryResult<HashMap<i32, Template>> {
+    use schema::notification_templates::dsl::*;
+
+    let list = notification_templates
+        .select((subscription, (content, title, description)))
+        .filter(guild.eq(guild_id).and(subscription.ne(GUILD_TEMPLATE)))
+        .load::<(i32, Template)>(conn)?;
+    Ok(list.into_iter().collect())
+}
+
+/// Inserts or replaces the template of the subscription or the default template of the guild.
+pub(super) fn save_template(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    sub_id: Option<i32>,
+    template: &Template,
+) -> QueryResult<()> {
+    use schema::notification_templates::dsl::*;
+
+    let values = (
+        content.eq(&template.content),
+        title.eq(&template.title),
+        description.eq(&template.description),
+    );
+    diesel::insert_into(notification_templates)
+        .values((
+            guild.eq(guild_id),
+            subscription.eq(sub_id.unwrap_or(GUILD_TEMPLATE)),
+            values,
+        ))
+        .on_conflict((guild, subscription))
+        .do_update()
+        .set(values)
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Copies the template of a subscription to another subscription of the guild and removes the
+/// template of the source subscription if `remove` is set.
+pub(super) fn transfer_template(
+    conn: &mut SqliteConnection,
+    guild_id: GuildId,
+    from: i32,
+    to: i32,
+    remove: bool,
+) -> QueryResult<()> {
+    use schema::notification_templates::dsl::*;
+
+    let source = notification_templates.filter(guild.eq(guild_id).and(subscription.eq(from)));
+    let template = source
+        .select((content, title, description))
+        .first::<Template>(conn)
+        .optional()?;
+    let Some(template) = template else {
+        return Ok(());
+    };
+    save_template(conn, guild_id, Some(to), &template)?;
+
+    if remove {
+        diesel::delete(source).execute(conn)?;
+    }
+    Ok(())
+}
@@ -7,7 +7,9 @@ use diesel::expression::AsExpression;
 use diesel::serialize::{self, ToSql};
 use diesel::sql_types::{BigInt, Integer};
 use diesel::sqlite::Sqlite;
-use twilight_model::id::marker::{ChannelMarker, GuildMarker, UserMarker};
+use twilight_model::id::marker::{
+    ChannelMarker, GuildMarker, MessageMarker, RoleMarker, UserMarker,
+};
 use twilight_model::id::Id;
 
 #[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, AsExpression, FromSqlRow)]
@@ -22,6 +24,10 @@ pub struct ModId(pub modio::types::id::ModId);
 #[diesel(sql_type = BigInt)]
 pub struct ChannelId(pub Id<ChannelMarker>);
 
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, AsExpression, FromSqlRow)]
+#[diesel(sql_type = BigInt)]
+pub struct MessageId(pub Id<MessageMarker>);
+
 #[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, AsExpression, FromSqlRow)]
 #[diesel(sql_type = BigInt)]
 pub struct GuildId(pub Id<GuildMarker>);
@@ -30,6 +36,10 @@ pub struct GuildId(pub Id<GuildMarker>);
 #[diesel(sql_type = BigInt)]
 pub struct UserId(pub Id<UserMarker>);
 
+#[derive(Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd, AsExpression, FromSqlRow)]
+#[diesel(sql_type = BigInt)]
+pub struct RoleId(pub Id<RoleMarker>);
+
 #[derive(Copy, Clone, Eq, PartialEq, AsExpression, FromSqlRow)]
 #[diesel(sql_type = Integer)]
 pub struct ApiAccessOptions(pub modio::types::games::ApiAccessOptions);
@@ -94,6 +104,18 @@ impl fmt::Debug for ChannelId {
     }
 }
 
+impl fmt::Display for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.get(), f)
+    }
+}
+
 impl fmt::Display for GuildId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.0, f)
@@ -106,6 +128,12 @@ impl fmt::Display for UserId {
     }
 }
 
+impl fmt::Display for RoleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
 impl fmt::Debug for GuildId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.0.get(), f)
@@ -118,6 +146,12 @@ impl fmt::Debug for UserId {
     }
 }
 
+impl fmt::Debug for RoleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0.get(), f)
+    }
+}
+
 impl fmt::Debug for ApiAccessOptions {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.0, f)
@@ -172,6 +206,20 @@ impl ToSql<BigInt, Sqlite> for ChannelId {
     }
 }
 
+impl FromSql<BigInt, Sqlite> for MessageId {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let id = i64::from_sql(bytes)?;
+        Ok(Self(Id::try_from(id)?))
+    }
+}
+
+impl ToSql<BigInt, Sqlite> for MessageId {
+    fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(i64::try_from(self.0.get())?);
+        Ok(serialize::IsNull::No)
+    }
+}
+
 impl FromSql<BigInt, Sqlite> for GuildId {
     fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
         let id = i64::from_sql(bytes)?;
@@ -200,6 +248,20 @@ impl ToSql<BigInt, Sqlite> for UserId {
     }
 }
 
+impl FromSql<BigInt, Sqlite> for RoleId {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let id = i64::from_sql(bytes)?;
+        Ok(Self(Id::try_from(id)?))
+    }
+}
+
+impl ToSql<BigInt, Sqlite> for RoleId {
+    fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(i64::try_from(self.0.get())?);
+        Ok(serialize::IsNull::No)
+    }
+}
+
 impl ToSql<Integer, Sqlite> for ApiAccessOptions {
     fn to_sql<'b>(&'b self, out: &mut serialize::Output<'b, '_, Sqlite>) -> serialize::Result {
         out.set_value(i32::from(self.0.bits()));
@@ -15,6 +15,7 @@ pub enum Error {
     Message(String),
     Io(IoError),
     Modio(ModioError),
+    Request(reqwest::Error),
     Database(DatabaseErrorInner),
     Tokio(TokioError),
     Twilight(TwilightError),
@@ -55,6 +56,7 @@ impl fmt::Display for Error {
             Error::Database(DatabaseErrorInner::Init(e)) => e.fmt(fmt),
             Error::Database(DatabaseErrorInner::Query(e)) => e.fmt(fmt),
             Error::Modio(e) => e.fmt(fmt),
+            Error::Request(e) => e.fmt(fmt),
             Error::Config(e) => e.fmt(fmt),
             Error::Metrics(e) => e.fmt(fmt),
         }
@@ -97,6 +99,12 @@ impl From<ModioError> for Error {
     }
 }
 
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::Request(e)
+    }
+}
+
 impl From<PrometheusError> for Error {
     fn from(e: PrometheusError) -> Error {
         Error::Metrics(e)
@@ -0,0 +1,162 @@
+//! Translations of the command replies, the registered commands and the notifications.
+//!
+//! The English text is the key of the translations and the fallback for missing entries.
+
+use std::collections::HashMap;
+
+use twilight_model::application::command::{Command, CommandOption};
+use twilight_model::application::interaction::Interaction;
+
+use crate::util;
+
+mod de;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+/// The bundled languages.
+pub const LOCALES: [Locale; 2] = [Locale::En, Locale::De];
+
+impl Locale {
+    /// Parses a language code like `de` or a Discord locale like `en-US`.
+    pub fn from_code(code: &str) -> Option<Locale> {
+        let lang = code.split(['-', '_']).next().unwrap_or(code);
+        match lang.to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            _ => None,
+        }
+    }
+
+    /// Returns the locale of the user, the locale of the guild or English.
+    pub fn from_interaction(interaction: &Interaction) -> Locale {
+        [&interaction.locale, &interaction.guild_locale]
+            .into_iter()
+            .flatten()
+            .find_map(|code| Locale::from_code(code))
+            .unwrap_or_default()
+    }
+
+    /// The language code stored in the settings.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+        }
+    }
+
+    /// The Discord locales of the language.
+    fn discord_codes(self) -> &'static [&'static str] {
+        match self {
+            Locale::En => &["en-US", "en-GB"],
+            Locale::De => &["de"],
+        }
+    }
+
+    fn lookup(self, text: &str) -> Option<&'static str> {
+        match self {
+            Locale::En => None,
+            Locale::De => de::translate(text),
+        }
+    }
+
+    /// The name of the language in the language itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    /// Returns the translation of the English text.
+    pub fn tr(self, text: &'static str) -> &'static str {
+        self.lookup(text).unwrap_or(text)
+    }
+
+    /// Returns the translation of the English text with its `{name}` placeholders replaced.
+    pub fn format(self, text: &'static str, args: &[(&str, &str)]) -> String {
+        let lookup = |name: &str| args.iter().find(|(n, _)| *n == name).map(|(_, v)| *v);
+        util::expand_placeholders(self.tr(text), lookup)
+            .or_else(|_| util::expand_placeholders(text, lookup))
+            .unwrap_or_else(|_| text.to_owned())
+    }
+}
+
+/// Returns the translations of the English text keyed by the Discord locales.
+fn localizations(text: &str) -> Option<HashMap<String, String>> {
+    let map = LOCALES
+        .into_iter()
+        .filter_map(|locale| Some((locale, locale.lookup(text)?)))
+        .flat_map(|(locale, translated)| {
+            let codes = locale.discord_codes().iter();
+            codes.map(move |code| ((*code).to_owned(), translated.to_owned()))
+        })
+        .collect::<HashMap<_, _>>();
+
+    (!map.is_empty()).then_some(map)
+}
+
+/// Adds the translations of the descriptions and the choice names to the commands.
+///
+/// The names of the commands and options stay in English so that the help topics and the
+/// documentation apply to every language.
+pub fn localize_commands(commands: &mut [Command]) {
+    for cmd in commands {
+        cmd.description_localizations = localizations(&cmd.description);
+        localize_options(&mut cmd.options);
+    }
+}
+
+fn localize_options(options: &mut [CommandOption]) {
+    for opt in options {
+        opt.description_localizations = localizations(&opt.description);
+        for choice in opt.choices.iter_mut().flatten() {
+            choice.name_localizations = localizations(&choice.name);
+        }
+        if let Some(options) = &mut opt.options {
+            localize_options(options);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::{BTreeSet, HashSet};
+
+    use super::de;
+    use crate::util::expand_placeholders;
+
+    fn placeholders(text: &str) -> BTreeSet<String> {
+        let names = RefCell::new(BTreeSet::new());
+        let lookup = |name: &str| {
+            names.borrow_mut().insert(name.to_owned());
+            Some("")
+        };
+        expand_placeholders(text, lookup).unwrap();
+        names.into_inner()
+    }
+
+    #[test]
+    fn translations_keep_placeholders() {
+        for (text, translated) in de::ENTRIES {
+            assert_eq!(
+                placeholders(text),
+                placeholders(translated),
+                "placeholders of {text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn translations_are_unique() {
+        let mut seen = HashSet::new();
+        for (text, _) in de::ENTRIES {
+            assert!(seen.insert(text), "duplicate translation of {text:?}");
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! German translations.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// The English texts and their translations.
+pub const ENTRIES: &[(&str, &str)] = &[
+    ("Game is set to '{game}'.", "Das Spiel ist auf '{game}' gesetzt."),
+    ("Game not found.", "Spiel nicht gefunden."),
+    ("The channel uses the default game of the server.", "Der Kanal verwendet das Standardspiel des Servers."),
+    ("Game of the channel is set to '{game}'.", "Das Spiel des Kanals ist auf '{game}' gesetzt."),
+    ("No games added.", "Keine Spiele hinzugefügt."),
+    ("Unknown game", "Unbekanntes Spiel"),
+    ("Games", "Spiele"),
+    ("Invalid alias '{alias}'.", "Ungültiger Alias '{alias}'."),
+    ("The alias '{alias}' is already used for another game.", "Der Alias '{alias}' wird bereits für ein anderes Spiel verwendet."),
+    ("Added '{game}' with the alias '{alias}'.", "'{game}' mit dem Alias '{alias}' hinzugefügt."),
+    ("Added '{game}'.", "'{game}' hinzugefügt."),
+    ("Removed '{game}'.", "'{game}' entfernt."),
+    ("'{game}' is not a known game of this server.", "'{game}' ist kein bekanntes Spiel dieses Servers."),
+    ("Changes of subscriptions are announced in {channel}.", "Änderungen an Abonnements werden in {channel} angekündigt."),
+    ("Changes of subscriptions are no longer announced.", "Änderungen an Abonnements werden nicht mehr angekündigt."),
+    ("Every mod update is sent as a new notification.", "Jedes Mod-Update wird als neue Benachrichtigung gesendet."),
+    ("Invalid duration `{duration}`, use e.g. `30m`, `2h` or `1d` up to one week.", "Ungültige Dauer `{duration}`, verwende z. B. `30m`, `2h` oder `1d` bis zu einer Woche."),
+    ("Mod updates within `{duration}` edit the previous notification of the mod instead of sending a new one.", "Mod-Updates innerhalb von `{duration}` bearbeiten die vorherige Benachrichtigung der Mod, statt eine neue zu senden."),
+    ("Notifications are sent in {language}.", "Benachrichtigungen werden auf {language} gesendet."),
+    ("Only members with the `Administrator` or `Manage Channels` permission can manage subscriptions.", "Nur Mitglieder mit der Berechtigung `Administrator` oder `Manage Channels` können Abonnements verwalten."),
+    ("Roles allowed to manage subscriptions:", "Rollen, die Abonnements verwalten dürfen:"),
+    ("{role} is allowed to manage subscriptions.", "{role} darf Abonnements verwalten."),
+    ("{role} is no longer allowed to manage subscriptions.", "{role} darf Abonnements nicht mehr verwalten."),
+    ("{role} was not allowed to manage subscriptions.", "{role} durfte keine Abonnements verwalten."),
+    (":no_entry: Third party API access is disabled for '{game}' but is required for the commands.", ":no_entry: Der API-Zugriff für Drittanbieter ist für '{game}' deaktiviert, wird aber für die Befehle benötigt."),
+    (":no_entry: Third party API access is disabled for '{game}' but is required for notifications.", ":no_entry: Der API-Zugriff für Drittanbieter ist für '{game}' deaktiviert, wird aber für Benachrichtigungen benötigt."),
+    (":no_entry: Third party API access is disabled for '{game}' but is required for subscriptions.", ":no_entry: Der API-Zugriff für Drittanbieter ist für '{game}' deaktiviert, wird aber für Abonnements benötigt."),
+    ("You don't follow any games, mods or users.", "Du folgst keinen Spielen, Mods oder Benutzern."),
+    (" (disabled)", " (deaktiviert)"),
+    ("New and updated mods", "Neue und aktualisierte Mods"),
+    ("User", "Benutzer"),
+    ("Direct messages to you failed, follow something again to enable the notifications.", "Direktnachrichten an dich sind fehlgeschlagen, folge erneut, um die Benachrichtigungen zu aktivieren."),
+    ("Mod not found.", "Mod nicht gefunden."),
+    ("the mods of '{user}' for '{game}'", "den Mods von '{user}' für '{game}'"),
+    ("You are now following {target}. Notifications are sent as direct messages.", "Du folgst jetzt {target}. Benachrichtigungen werden als Direktnachrichten gesendet."),
+    ("You are already following {target}.", "Du folgst bereits {target}."),
+    ("You are no longer following {target}.", "Du folgst {target} nicht mehr."),
+    ("You are not following {target}.", "Du folgst {target} nicht."),
+    ("Unknown command.", "Unbekannter Befehl."),
+    ("Command:", "Befehl:"),
+    ("Default game is not set.", "Es ist kein Standardspiel festgelegt."),
+    ("No mods found.", "Keine Mods gefunden."),
+    ("Rating", "Bewertung"),
+    ("Rank: {rank}/{total}\nDownloads: {downloads}\nSubscribers: {subscribers}\nVotes: +{positive}/-{negative}", "Rang: {rank}/{total}\nDownloads: {downloads}\nAbonnenten: {subscribers}\nStimmen: +{positive}/-{negative}"),
+    ("Dates", "Daten"),
+    ("Created: {added}\nUpdated: {updated}", "Erstellt: {added}\nAktualisiert: {updated}"),
+    ("Homepage", "Webseite"),
+    ("Size", "Größe"),
+    (":warning: The bot is missing the permission(s) {permissions} in {channel}.", ":warning: Dem Bot fehlen die Berechtigung(en) {permissions} in {channel}."),
+    (":warning: Notifications are only crossposted in announcement channels.", ":warning: Benachrichtigungen werden nur in Ankündigungskanälen veröffentlicht."),
+    ("Invalid tag(s): ", "Ungültige Tags: "),
+    ("Available tags: ", "Verfügbare Tags: "),
+    (":no_entry: You are not allowed to manage the subscriptions.", ":no_entry: Du darfst die Abonnements nicht verwalten."),
+    ("No subscriptions found.", "Keine Abonnements gefunden."),
+    ("Failed to subscribe to '{game}'.", "'{game}' konnte nicht abonniert werden."),
+    ("Subscribed to '{game}'.", "'{game}' abonniert."),
+    ("Failed to add subscription.", "Das Abonnement konnte nicht hinzugefügt werden."),
+    ("Failed to unsubscribe from '{game}'.", "Das Abonnement von '{game}' konnte nicht beendet werden."),
+    ("Unsubscribed from '{game}'.", "Abonnement von '{game}' beendet."),
+    ("Failed to remove subscription.", "Das Abonnement konnte nicht entfernt werden."),
+    ("Subscribed to the mod '{mod}'.", "Die Mod '{mod}' abonniert."),
+    ("Unsubscribed from the mod '{mod}'.", "Abonnement der Mod '{mod}' beendet."),
+    ("The mod '{mod}' is already subscribed.", "Die Mod '{mod}' ist bereits abonniert."),
+    ("The mod '{mod}' is not subscribed.", "Die Mod '{mod}' ist nicht abonniert."),
+    ("Failed to subscribe to the mod '{mod}'.", "Die Mod '{mod}' konnte nicht abonniert werden."),
+    ("Failed to unsubscribe from the mod '{mod}'.", "Das Abonnement der Mod '{mod}' konnte nicht beendet werden."),
+    ("Invalid duration `{duration}`, use e.g. `12h`, `3d` or `1w2d`.", "Ungültige Dauer `{duration}`, verwende z. B. `12h`, `3d` oder `1w2d`."),
+    ("this channel", "diesem Kanal"),
+    ("No subscriptions of {target} changed.", "Keine Abonnements von {target} wurden geändert."),
+    ("Paused {count} subscription(s) of {target} until {until}.", "{count} Abonnement(s) von {target} bis {until} pausiert."),
+    ("Paused {count} subscription(s) of {target}.", "{count} Abonnement(s) von {target} pausiert."),
+    ("Resumed {count} subscription(s) of {target}.", "{count} Abonnement(s) von {target} fortgesetzt."),
+    ("This channel is not subscribed to '{game}'.", "Dieser Kanal hat '{game}' nicht abonniert."),
+    ("None of the latest {limit} mods of '{game}' matches the subscriptions of this channel.", "Keine der neuesten {limit} Mods von '{game}' passt zu den Abonnements dieses Kanals."),
+    ("*This is a test notification.*", "*Dies ist eine Testbenachrichtigung.*"),
+    ("Sent a test notification for '{mod}'.", "Testbenachrichtigung für '{mod}' gesendet."),
+    (":no_entry: The notification could not be sent to {channel}. The bot is missing the `Send Messages` or `Embed Links` permission.", ":no_entry: Die Benachrichtigung konnte nicht an {channel} gesendet werden. Dem Bot fehlt die Berechtigung `Send Messages` oder `Embed Links`."),
+    (":no_entry: The notification could not be sent to {channel}: {error}", ":no_entry: Die Benachrichtigung konnte nicht an {channel} gesendet werden: {error}"),
+    ("Subscription not found.", "Abonnement nicht gefunden."),
+    ("Failed to edit the subscription of '{game}'.", "Das Abonnement von '{game}' konnte nicht bearbeitet werden."),
+    ("A subscription to '{game}' with the same tags already exists.", "Ein Abonnement von '{game}' mit denselben Tags existiert bereits."),
+    ("Updated the subscription to '{game}'.", "Das Abonnement von '{game}' wurde aktualisiert."),
+    ("The subscription to '{game}' is unchanged.", "Das Abonnement von '{game}' ist unverändert."),
+    ("The mod has been edited.", "Die Mod wurde bearbeitet."),
+    ("A new mod is available.", "Eine neue Mod ist verfügbar."),
+    ("The mod is now unavailable.", "Die Mod ist jetzt nicht mehr verfügbar."),
+    ("A new version is available.", "Eine neue Version ist verfügbar."),
+    ("The mod has been permanently deleted.", "Die Mod wurde endgültig gelöscht."),
+    ("Versions", "Versionen"),
+    ("Changelog", "Änderungen"),
+    ("Uploaded {date}", "Hochgeladen {date}"),
+    ("Get bot info", "Informationen über den Bot"),
+    ("Guild specific settings", "Einstellungen des Servers"),
+    ("Set the default game for `/mods` command", "Standardspiel für den Befehl `/mods` festlegen"),
+    ("ID or search", "ID oder Suche"),
+    ("Set the default game of this channel. Omit the game to use the server default.", "Standardspiel dieses Kanals festlegen. Ohne Spiel wird das Standardspiel des Servers verwendet."),
+    ("Manage the known games of the server.", "Bekannte Spiele des Servers verwalten."),
+    ("List the known games and their aliases.", "Bekannte Spiele und ihre Aliase auflisten."),
+    ("Add a known game with an optional alias.", "Bekanntes Spiel mit optionalem Alias hinzufügen."),
+    ("Short name for the game", "Kurzname für das Spiel"),
+    ("Remove a known game.", "Bekanntes Spiel entfernen."),
+    ("ID, alias or search", "ID, Alias oder Suche"),
+    ("Set the channel where changes of subscriptions are announced.", "Kanal festlegen, in dem Änderungen an Abonnements angekündigt werden."),
+    ("Omit the channel to disable the announcements.", "Ohne Kanal werden die Ankündigungen deaktiviert."),
+    ("Edit the previous notification of a mod for updates within the time window.", "Vorherige Benachrichtigung einer Mod bei Updates innerhalb des Zeitfensters bearbeiten."),
+    ("e.g. `30m`, `2h` or `1d`. Omit the duration to always send new notifications.", "z. B. `30m`, `2h` oder `1d`. Ohne Dauer werden immer neue Benachrichtigungen gesendet."),
+    ("Set the language of the notifications.", "Sprache der Benachrichtigungen festlegen."),
+    ("Omit the language to use English.", "Ohne Sprache wird Englisch verwendet."),
+    ("Manage the roles allowed to manage subscriptions.", "Rollen verwalten, die Abonnements verwalten dürfen."),
+    ("List the roles allowed to manage subscriptions.", "Rollen auflisten, die Abonnements verwalten dürfen."),
+    ("Allow a role to manage subscriptions.", "Einer Rolle erlauben, Abonnements zu verwalten."),
+    ("Role", "Rolle"),
+    ("Disallow a role to manage subscriptions.", "Einer Rolle verbieten, Abonnements zu verwalten."),
+    ("Username", "Benutzername"),
+    ("username", "Benutzername"),
+    ("Get notified by direct message about mod updates.", "Per Direktnachricht über Mod-Updates benachrichtigt werden."),
+    ("List the games, mods and users you follow.", "Spiele, Mods und Benutzer auflisten, denen du folgst."),
+    ("Stop the direct message notifications about mod updates.", "Benachrichtigungen per Direktnachricht über Mod-Updates beenden."),
+    ("Follow new and updated mods of a game.", "Neuen und aktualisierten Mods eines Spiels folgen."),
+    ("Follow the updates of a mod.", "Den Updates einer Mod folgen."),
+    ("Follow the mods of a user.", "Den Mods eines Benutzers folgen."),
+    ("Unfollow new and updated mods of a game.", "Neuen und aktualisierten Mods eines Spiels nicht mehr folgen."),
+    ("Unfollow the updates of a mod.", "Den Updates einer Mod nicht mehr folgen."),
+    ("Unfollow the mods of a user.", "Den Mods eines Benutzers nicht mehr folgen."),
+    ("List all games on <https://mod.io>", "Alle Spiele auf <https://mod.io> auflisten"),
+    ("Display the default game or the statistics of a game.", "Standardspiel oder Statistiken eines Spiels anzeigen."),
+    ("Display the default game.", "Standardspiel anzeigen."),
+    ("Show the growth of a game over time.", "Entwicklung eines Spiels im Zeitverlauf anzeigen."),
+    ("ID or search game instead of the default game.", "ID oder Suche eines Spiels statt des Standardspiels."),
+    ("Time period of the statistics.", "Zeitraum der Statistiken."),
+    ("7 days", "7 Tage"),
+    ("30 days", "30 Tage"),
+    ("90 days", "90 Tage"),
+    ("Show help info and commands", "Hilfe und Befehle anzeigen"),
+    ("Command to get help for.", "Befehl, zu dem Hilfe angezeigt wird."),
+    ("List mods or show the details for a single mod.", "Mods auflisten oder die Details einer einzelnen Mod anzeigen."),
+    ("List popular mods.", "Beliebte Mods auflisten."),
+    ("Show a random mod.", "Eine zufällige Mod anzeigen."),
+    ("Show details of a single mod.", "Details einer einzelnen Mod anzeigen."),
+    ("Show the statistics of a mod over time.", "Statistiken einer Mod im Zeitverlauf anzeigen."),
+    ("Search mods across all games.", "Mods in allen Spielen suchen."),
+    ("Search term", "Suchbegriff"),
+    ("Manage subscriptions in the current channel to mod updates of a game.", "Abonnements des aktuellen Kanals für Mod-Updates eines Spiels verwalten."),
+    ("Show an overview of the current setup of this server.", "Übersicht der aktuellen Einrichtung dieses Servers anzeigen."),
+    ("List subscriptions", "Abonnements auflisten"),
+    ("Subscribe the current channel to mod update of a game.", "Mod-Updates eines Spiels im aktuellen Kanal abonnieren."),
+    ("Comma-separated list of tags", "Kommagetrennte Liste von Tags"),
+    ("Type of the mod updates.", "Art der Mod-Updates."),
+    ("Allow explicit content", "Explizite Inhalte erlauben"),
+    ("Publish the notifications in announcement channels", "Benachrichtigungen in Ankündigungskanälen veröffentlichen"),
+    ("Subscribe the current channel to a game by choosing the tags and options.", "Ein Spiel im aktuellen Kanal durch Auswahl der Tags und Optionen abonnieren."),
+    ("Unsubscribe the current channel from mod update of a game.", "Abonnement der Mod-Updates eines Spiels im aktuellen Kanal beenden."),
+    ("Edit a subscription of the current channel.", "Ein Abonnement des aktuellen Kanals bearbeiten."),
+    ("The subscription to edit", "Das zu bearbeitende Abonnement"),
+    ("Comma-separated list of tags, `-` to remove all tags", "Kommagetrennte Liste von Tags, `-` entfernt alle Tags"),
+    ("Subscribe to the updates of single mods.", "Die Updates einzelner Mods abonnieren."),
+    ("List the subscribed mods", "Abonnierte Mods auflisten"),
+    ("Subscribe the current channel to the updates of a mod.", "Die Updates einer Mod im aktuellen Kanal abonnieren."),
+    ("Unsubscribe the current channel from the updates of a mod.", "Abonnement der Updates einer Mod im aktuellen Kanal beenden."),
+    ("Mute update notifications for a mod.", "Update-Benachrichtigungen einer Mod stummschalten."),
+    ("List muted mods", "Stummgeschaltete Mods auflisten"),
+    ("Unmute update notifications for a mod.", "Stummschaltung der Update-Benachrichtigungen einer Mod aufheben."),
+    ("Mute update notifications for mods of a user.", "Update-Benachrichtigungen für Mods eines Benutzers stummschalten."),
+    ("List muted user", "Stummgeschaltete Benutzer auflisten"),
+    ("Unmute update notifications for mods of a user.", "Stummschaltung der Update-Benachrichtigungen für Mods eines Benutzers aufheben."),
+    ("Export the subscriptions of this server to a file.", "Abonnements dieses Servers in eine Datei exportieren."),
+    ("Import subscriptions from an exported file.", "Abonnements aus einer exportierten Datei importieren."),
+    ("Exported file", "Exportierte Datei"),
+    ("Map the channels of the file to channels of this server, e.g. `123=#mods`", "Kanäle der Datei Kanälen dieses Servers zuordnen, z. B. `123=#mods`"),
+    ("Pause the notifications of the current channel without removing the subscriptions.", "Benachrichtigungen des aktuellen Kanals pausieren, ohne die Abonnements zu entfernen."),
+    ("Only pause the subscriptions of this game", "Nur die Abonnements dieses Spiels pausieren"),
+    ("Resume the notifications automatically after e.g. `12h`, `3d` or `1w2d`", "Benachrichtigungen automatisch fortsetzen nach z. B. `12h`, `3d` oder `1w2d`"),
+    ("Resume the paused notifications of the current channel.", "Pausierte Benachrichtigungen des aktuellen Kanals fortsetzen."),
+    ("Only resume the subscriptions of this game", "Nur die Abonnements dieses Spiels fortsetzen"),
+    ("Send a test notification for a game to the current channel.", "Testbenachrichtigung für ein Spiel in den aktuellen Kanal senden."),
+    ("Copy the subscriptions of a channel to another channel.", "Abonnements eines Kanals in einen anderen Kanal kopieren."),
+    ("Channel to copy the subscriptions from.", "Kanal, aus dem die Abonnements kopiert werden."),
+    ("Channel to copy the subscriptions to.", "Kanal, in den die Abonnements kopiert werden."),
+    ("Move the subscriptions of a channel to another channel.", "Abonnements eines Kanals in einen anderen Kanal verschieben."),
+    ("Channel to move the subscriptions from.", "Kanal, aus dem die Abonnements verschoben werden."),
+    ("Channel to move the subscriptions to.", "Kanal, in den die Abonnements verschoben werden."),
+    ("Show the latest changes of the subscriptions.", "Letzte Änderungen der Abonnements anzeigen."),
+    ("Only show the changes of this channel.", "Nur die Änderungen dieses Kanals anzeigen."),
+    ("Customize the notifications.", "Benachrichtigungen anpassen."),
+    ("Show the notification template and the available placeholders.", "Benachrichtigungsvorlage und verfügbare Platzhalter anzeigen."),
+    ("Template of the subscription", "Vorlage des Abonnements"),
+    ("Set the notification template and show a preview.", "Benachrichtigungsvorlage festlegen und eine Vorschau anzeigen."),
+    ("Only change the subscription", "Nur das Abonnement ändern"),
+    ("Message text, `-` for the default", "Nachrichtentext, `-` für den Standard"),
+    ("Title of the embed, `-` for the default", "Titel des Embeds, `-` für den Standard"),
+    ("Description of the embed, `-` for the default", "Beschreibung des Embeds, `-` für den Standard"),
+    ("Remove the notification template.", "Benachrichtigungsvorlage entfernen."),
+    ("Layout of the notifications", "Layout der Benachrichtigungen"),
+    ("New mods", "Neue Mods"),
+    ("Updated mods", "Aktualisierte Mods"),
+    ("All", "Alle"),
+    ("Full", "Vollständig"),
+    ("Compact", "Kompakt"),
+    ("Text only", "Nur Text"),
+    ("Use `/subs import` to apply the file to a server.", "Verwende `/subs import`, um die Datei auf einen Server anzuwenden."),
+    ("Invalid channel mapping. Use `old=new` pairs, e.g. `123=#mods`.", "Ungültige Kanalzuordnung. Verwende `alt=neu`-Paare, z. B. `123=#mods`."),
+    ("File not found.", "Datei nicht gefunden."),
+    ("The file is too large.", "Die Datei ist zu groß."),
+    ("Unsupported file version: {version}", "Nicht unterstützte Dateiversion: {version}"),
+    ("Invalid file: {error}", "Ungültige Datei: {error}"),
+    ("Nothing was imported.", "Es wurde nichts importiert."),
+    ("Imported {subs} subscription(s) into {channels} channel(s).", "{subs} Abonnement(s) in {channels} Kanal/Kanäle importiert."),
+    ("Unknown channel: `{id}`", "Unbekannter Kanal: `{id}`"),
+    ("Unknown game: `{id}`", "Unbekanntes Spiel: `{id}`"),
+    ("Invalid tag(s) for '{game}': {tags}", "Ungültige Tags für '{game}': {tags}"),
+    ("Invalid mod: `{id}`", "Ungültige Mod: `{id}`"),
+    ("the subscription to '{game}'", "das Abonnement von '{game}'"),
+    ("the subscription to the game `{id}`", "das Abonnement des Spiels `{id}`"),
+    ("this server", "diesen Server"),
+    ("Notification template of {target}.", "Benachrichtigungsvorlage für {target}."),
+    ("Saved the notification template of {target}.", "Die Benachrichtigungsvorlage für {target} wurde gespeichert."),
+    ("Removed the notification template of {target}.", "Die Benachrichtigungsvorlage für {target} wurde entfernt."),
+    ("Content", "Inhalt"),
+    ("Title", "Titel"),
+    ("Description", "Beschreibung"),
+    ("default", "Standard"),
+    ("Fields without a template use the template of the server.", "Felder ohne Vorlage verwenden die Vorlage des Servers."),
+    ("Placeholders", "Platzhalter"),
+    ("Template", "Vorlage"),
+    ("Preview", "Vorschau"),
+    (":no_entry: Invalid {field} template: {error}.", ":no_entry: Ungültige Vorlage für {field}: {error}."),
+    ("Set a default game with `/settings default-game` to see a preview.", "Lege mit `/settings default-game` ein Standardspiel fest, um eine Vorschau zu sehen."),
+    ("Name of the mod", "Name der Mod"),
+    ("Version of the mod's primary file", "Version der primären Datei der Mod"),
+    ("Username of the mod's author", "Benutzername des Autors der Mod"),
+    ("Tags of the mod", "Tags der Mod"),
+    ("Name of the game", "Name des Spiels"),
+    ("Summary of the mod", "Zusammenfassung der Mod"),
+    ("Changelog of the mod's primary file", "Änderungsprotokoll der primären Datei der Mod"),
+    ("Upload date of the mod's primary file", "Hochladedatum der primären Datei der Mod"),
+    ("Default text of the notification, e.g. `A new version is available.`", "Standardtext der Benachrichtigung, z. B. `Eine neue Version ist verfügbar.`"),
+    ("Link to the mod's profile", "Link zum Profil der Mod"),
+    ("Download link of the mod's primary file", "Downloadlink der primären Datei der Mod"),
+    ("Explicit content", "Explizite Inhalte"),
+    ("Save", "Speichern"),
+    ("Cancel", "Abbrechen"),
+    ("Setup cancelled.", "Einrichtung abgebrochen."),
+    ("Choose the tags and the type of mod updates for '{game}' and save the subscription.", "Wähle die Tags und die Art der Mod-Updates für '{game}' und speichere das Abonnement."),
+    ("Not all tags of the game can be shown, use `/subs add` for the other tags.", "Nicht alle Tags des Spiels können angezeigt werden, verwende `/subs add` für die anderen Tags."),
+    ("Mods matching: '{query}'", "Mods zu '{query}'"),
+    ("and {count} more", "und {count} weitere"),
+    ("No mods found in the searched games.", "Keine Mods in den durchsuchten Spielen gefunden."),
+    ("Alias", "Alias"),
+    ("Follows", "Gefolgt"),
+    ("Download", "Download"),
+    ("Links: ", "Links: "),
+    ("Tags", "Tags"),
+    ("Version", "Version"),
+    ("Version {version}", "Version {version}"),
+    ("No games found.", "Keine Spiele gefunden."),
+    ("Found 1 game.", "1 Spiel gefunden."),
+    ("Statistics", "Statistiken"),
+    ("Not enough statistics recorded yet.", "Es wurden noch nicht genug Statistiken aufgezeichnet."),
+    ("Growth over the last {days} days.", "Entwicklung der letzten {days} Tage."),
+    ("Mods", "Mods"),
+    ("Subscribers", "Abonnenten"),
+    ("Downloads", "Downloads"),
+    ("Info", "Info"),
+    ("Stats", "Statistiken"),
+    ("reroll", "neu würfeln"),
+    ("No statistics recorded yet. Statistics are only recorded for subscribed and followed mods.", "Es wurden noch keine Statistiken aufgezeichnet. Statistiken werden nur für abonnierte und gefolgte Mods aufgezeichnet."),
+    ("Statistics over the last {days} days.", "Statistiken der letzten {days} Tage."),
+    ("Votes: {votes} ({delta})\nRank: {rank} ({change})", "Stimmen: {votes} ({delta})\nRang: {rank} ({change})"),
+    (":pause_button: Paused after {count} failed deliveries", ":pause_button: Pausiert nach {count} fehlgeschlagenen Zustellungen"),
+    (":warning: {count} failed deliveries", ":warning: {count} fehlgeschlagene Zustellungen"),
+    ("Last error {time}: `{error}`", "Letzter Fehler {time}: `{error}`"),
+    ("Paused", "Pausiert"),
+    ("Paused until {until}", "Pausiert bis {until}"),
+    ("Subscriptions", "Abonnements"),
+    ("Channel", "Kanal"),
+    ("Muted mods", "Stummgeschaltete Mods"),
+    ("Muted users", "Stummgeschaltete Benutzer"),
+    ("No mod is subscribed.", "Es ist keine Mod abonniert."),
+    ("Subscribed Mods", "Abonnierte Mods"),
+    ("No mod is muted.", "Es ist keine Mod stummgeschaltet."),
+    ("Muted Mods", "Stummgeschaltete Mods"),
+    ("No user is muted.", "Es ist kein Benutzer stummgeschaltet."),
+    ("Muted Users", "Stummgeschaltete Benutzer"),
+    ("The mod '{mod}' is now muted.", "Die Mod '{mod}' ist jetzt stummgeschaltet."),
+    ("Failed to mute '{mod}'.", "'{mod}' konnte nicht stummgeschaltet werden."),
+    ("The mod '{mod}' is now unmuted.", "Die Stummschaltung der Mod '{mod}' wurde aufgehoben."),
+    ("Failed to unmute '{mod}'.", "Die Stummschaltung von '{mod}' konnte nicht aufgehoben werden."),
+    ("The user '{user}' is now muted for '{game}'.", "Der Benutzer '{user}' ist jetzt für '{game}' stummgeschaltet."),
+    ("Failed to mute '{user}'.", "'{user}' konnte nicht stummgeschaltet werden."),
+    ("The user '{user}' is now unmuted for '{game}'.", "Die Stummschaltung des Benutzers '{user}' für '{game}' wurde aufgehoben."),
+    ("Failed to unmute '{user}'.", "Die Stummschaltung von '{user}' konnte nicht aufgehoben werden."),
+    ("The channels must be different.", "Die Kanäle müssen verschieden sein."),
+    ("No subscriptions found in {channel}.", "Keine Abonnements in {channel} gefunden."),
+    ("Moved {subs} subscription(s), {mods} mod subscription(s) and {mutes} mute(s) from {from} to {to}.", "{subs} Abonnement(s), {mods} Mod-Abonnement(s) und {mutes} Stummschaltung(en) von {from} nach {to} verschoben."),
+    ("Copied {subs} subscription(s), {mods} mod subscription(s) and {mutes} mute(s) from {from} to {to}.", "{subs} Abonnement(s), {mods} Mod-Abonnement(s) und {mutes} Stummschaltung(en) von {from} nach {to} kopiert."),
+    ("No changes recorded.", "Keine Änderungen aufgezeichnet."),
+    ("Subscription History", "Verlauf der Abonnements"),
+    ("Subscription Changes", "Änderungen der Abonnements"),
+    ("{time} **{action}** in {channel} by {actor}", "{time} **{action}** in {channel} von {actor}"),
+    ("{time} **{action}** in {channel} by the bot", "{time} **{action}** in {channel} vom Bot"),
+    ("Game", "Spiel"),
+    ("Before", "Vorher"),
+    ("After", "Nachher"),
+    ("Subscription added", "Abonnement hinzugefügt"),
+    ("Subscription removed", "Abonnement entfernt"),
+    ("Subscription edited", "Abonnement bearbeitet"),
+    ("Subscription paused", "Abonnement pausiert"),
+    ("Subscription resumed", "Abonnement fortgesetzt"),
+    ("Mod subscription added", "Mod-Abonnement hinzugefügt"),
+    ("Mod subscription removed", "Mod-Abonnement entfernt"),
+    ("Mod muted", "Mod stummgeschaltet"),
+    ("Mod unmuted", "Stummschaltung der Mod aufgehoben"),
+    ("User muted", "Benutzer stummgeschaltet"),
+    ("User unmuted", "Stummschaltung des Benutzers aufgehoben"),
+    ("Subscription imported", "Abonnement importiert"),
+    ("Subscription copied", "Abonnement kopiert"),
+    ("Subscription moved", "Abonnement verschoben"),
+    ("Subscription removed automatically", "Abonnement automatisch entfernt"),
+    ("Notifications for {channel} have been paused after {count} failed deliveries because the bot is missing permissions in the channel.\nLast error: `{error}`\nGrant the bot the `Send Messages` and `Embed Links` permissions and use `/subs test` in the channel to resume the notifications.", "Die Benachrichtigungen für {channel} wurden nach {count} fehlgeschlagenen Zustellungen pausiert, weil dem Bot Berechtigungen im Kanal fehlen.\nLetzter Fehler: `{error}`\nGib dem Bot die Berechtigungen `Send Messages` und `Embed Links` und verwende `/subs test` im Kanal, um die Benachrichtigungen fortzusetzen."),
+];
+
+static TRANSLATIONS: LazyLock<HashMap<&str, &str>> =
+    LazyLock::new(|| ENTRIES.iter().copied().collect());
+
+pub fn translate(text: &str) -> Option<&'static str> {
+    TRANSLATIONS.get(text).copied()
+}
@@ -21,6 +21,7 @@ mod commands;
 mod config;
 mod db;
 mod error;
+mod i18n;
 mod metrics;
 mod tasks;
 mod util;
@@ -92,8 +93,9 @@ async fn try_main() -> CliResult {
     }
 
     tokio::spawn(metrics::serve(config.metrics, metrics));
-    tokio::spawn(tasks::events::task(context.clone()));
+    tokio::spawn(tasks::events::task(context.clone(), &config.events));
     tokio::spawn(tasks::games::task(context.clone()));
+    tokio::spawn(tasks::stats::task(context.clone()));
 
     let mut senders = Vec::with_capacity(shards.len());
     let mut tasks = Vec::with_capacity(shards.len());
@@ -14,36 +14,80 @@ use tokio::sync::mpsc;
 use tokio::time::{self, Instant};
 use tokio_stream::{self as stream, StreamExt};
 use tracing::{debug, error, trace};
-use twilight_model::channel::message::embed::Embed;
+use twilight_model::channel::message::embed::{Embed, EmbedField};
+use twilight_model::channel::ChannelType;
 use twilight_util::builder::embed::{
     EmbedAuthorBuilder, EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder, ImageSource,
 };
 
 use crate::bot::Context;
 use crate::commands::mods::create_fields;
-use crate::db::types::{ChannelId, ModId};
-use crate::db::Subscription;
-use crate::util;
+use crate::commands::subs::{announce_changes, announce_paused};
+use crate::config::EventsConfig;
+use crate::db::types::{ChannelId, GameId, GuildId, MessageId, ModId, UserId};
+use crate::db::{
+    ExcludedModsMap, ExcludedUsersMap, Follow, NotificationMessage, PendingChanges, Style,
+    Subscription, Template, Templates,
+};
+use crate::error::{Error, TwilightError};
+use crate::i18n::Locale;
+use crate::util::{self, TimestampStyle};
 
 const MIN: Duration = Duration::from_secs(60);
 const INTERVAL_DURATION: Duration = Duration::from_secs(300);
 const THROTTLE: Duration = Duration::from_millis(30);
+/// Maximum number of attempts to crosspost a notification.
+const CROSSPOST_ATTEMPTS: usize = 3;
+/// Maximum time to wait for a rate limit before a crosspost is given up.
+const MAX_CROSSPOST_DELAY: Duration = Duration::from_secs(3600);
+
+/// A notification for the channels and the direct messages to the users.
+struct Notification {
+    /// The channels with the guild of their subscription.
+    channels: BTreeMap<ChannelId, GuildId>,
+    /// The channels whose notifications are published to the following servers.
+    crosspost: BTreeSet<ChannelId>,
+    users: BTreeSet<UserId>,
+    content: Option<String>,
+    embeds: Vec<Embed>,
+    /// The mod and its new version if a previous notification can be edited instead.
+    update: Option<(ModId, String)>,
+    locale: Locale,
+}
 
 #[allow(clippy::too_many_lines)]
-pub fn task(ctx: Context) -> impl Future<Output = ()> {
-    let (sender, mut receiver) = mpsc::channel::<(BTreeSet<ChannelId>, Option<String>, Embed)>(100);
+pub fn task(ctx: Context, config: &EventsConfig) -> impl Future<Output = ()> {
+    let (sender, mut receiver) = mpsc::channel::<Notification>(100);
 
     let unknown_channels = Arc::new(DashSet::new());
     let unknown_channels2 = unknown_channels.clone();
+    #[allow(clippy::cast_possible_wrap)]
+    let debounce = config.debounce as i64;
     let subscriptions = ctx.subscriptions.clone();
+    let follows = ctx.follows.clone();
+    let ctx2 = ctx.clone();
 
     tokio::spawn(async move {
+        // The channels with recorded failures which are reset after a successful delivery.
+        let mut failing = subscriptions.failing_channels().unwrap_or_else(|e| {
+            error!("{e}");
+            HashSet::new()
+        });
+
         loop {
-            if let Some((channels, content, embed)) = receiver.recv().await {
-                let embeds = [embed];
+            if let Some(notification) = receiver.recv().await {
+                let Notification {
+                    channels,
+                    crosspost,
+                    users,
+                    content,
+                    embeds,
+                    update,
+                    locale,
+                } = notification;
                 let requests = channels
                     .into_iter()
-                    .filter(|id| {
+                    .filter(|(id, _)| {
                         if unknown_channels.contains(id) {
                             tracing::debug!("channel #{id} ignored: unknown channel");
                             false
@@ -51,30 +95,95 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                             true
                         }
                     })
-                    .map(|id| {
-                        let mut msg = ctx.client.create_message(*id).embeds(&embeds);
-                        if let Some(content) = &content {
-                            msg = msg.content(content);
+                    .map(|(id, guild_id)| {
+                        let ctx = &ctx2;
+                        let content = content.as_deref();
+                        let embeds = &embeds;
+                        let update = update.as_ref();
+                        async move {
+                            let res = match update {
+                                Some((mod_id, version)) => {
+                                    let embed = embeds.first();
+                                    let update = (mod_id, version.as_str());
+                                    deliver_update(
+                                        ctx, id, guild_id, update, content, embed, locale,
+                                    )
+                                    .await
+                                }
+                                None => deliver(ctx, id, content, embeds).await,
+                            };
+                            (id, res)
                         }
-                        async move { (id, msg.await) }
                     });
                 let messages = stream::iter(requests).throttle(THROTTLE);
 
                 tokio::pin!(messages);
 
                 while let Some(fut) = messages.next().await {
-                    if let (channel_id, Err(e)) = fut.await {
-                        if util::is_unknown_channel_error(e.kind()) {
+                    let (channel_id, res) = fut.await;
+                    match res {
+                        Err(e) if util::is_unknown_channel_error(e.kind()) => {
                             unknown_channels.insert(channel_id);
 
-                            if let Err(e) = subscriptions.cleanup_unknown_channels(&[channel_id]) {
-                                error!("{e}");
+                            match subscriptions.cleanup_unknown_channels(&[channel_id]) {
+                                Ok(entries) => announce_changes(&ctx2, &entries).await,
+                                Err(e) => error!("{e}"),
                             }
-                        } else {
+                        }
+                        Err(e) => {
                             error!("{e}");
+
+                            let permission = util::is_missing_permissions_error(e.kind());
+                            let error = e.to_string();
+                            failing.insert(channel_id);
+                            match subscriptions.record_failure(channel_id, &error, permission) {
+                                Ok(Some(guild_id)) => {
+                                    announce_paused(&ctx2, guild_id, channel_id, &error).await;
+                                }
+                                Ok(None) => {}
+                                Err(e) => error!("{e}"),
+                            }
+                        }
+                        Ok(message) => {
+                            ctx2.metrics.notifications.inc();
+
+                            if failing.remove(&channel_id) {
+                                if let Err(e) = subscriptions.reset_failures(channel_id) {
+                                    error!("{e}");
+                                }
+                            }
+                            if let Some(message_id) = message {
+                                if crosspost.contains(&channel_id) {
+                                    tokio::spawn(publish(ctx2.clone(), channel_id, message_id));
+                                }
+                            }
                         }
-                    } else {
-                        ctx.metrics.notifications.inc();
+                    }
+                }
+
+                let requests = users.into_iter().map(|id| {
+                    let ctx = &ctx2;
+                    let content = content.as_deref();
+                    let embeds = &embeds;
+                    async move { (id, deliver_dm(ctx, id, content, embeds).await) }
+                });
+                let messages = stream::iter(requests).throttle(THROTTLE);
+
+                tokio::pin!(messages);
+
+                while let Some(fut) = messages.next().await {
+                    let (user_id, res) = fut.await;
+                    match res {
+                        Ok(()) => ctx2.metrics.notifications.inc(),
+                        Err(Error::Twilight(TwilightError::Http(e)))
+                            if util::is_cannot_message_user_error(e.kind()) =>
+                        {
+                            debug!("disabling follows of @{user_id}: {e}");
+                            if let Err(e) = follows.disable(user_id) {
+                                error!("{e}");
+                            }
+                        }
+                        Err(e) => error!("{e}"),
                     }
                 }
             }
@@ -104,18 +213,53 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                 ]))
                 .order_by(Id::asc());
 
-            let (subs, excluded_mods, excluded_users) =
+            let (mut subs, excluded_mods, excluded_users) =
                 ctx.subscriptions.load().unwrap_or_else(|e| {
                     error!("failed to load subscriptions: {e}");
                     (HashMap::default(), HashMap::default(), HashMap::default())
                 });
+            let mut subscribed_mods = ctx.subscriptions.load_mods().unwrap_or_else(|e| {
+                error!("failed to load mod subscriptions: {e}");
+                HashMap::default()
+            });
+            let mut follows = ctx.follows.load().unwrap_or_else(|e| {
+                error!("failed to load follows: {e}");
+                HashMap::default()
+            });
+            let templates = ctx.subscriptions.load_templates().unwrap_or_else(|e| {
+                error!("failed to load templates: {e}");
+                Templates::default()
+            });
             let excluded_mods = Arc::new(excluded_mods);
             let excluded_users = Arc::new(excluded_users);
+            let languages = ctx.settings.languages().unwrap_or_else(|e| {
+                error!("failed to load languages: {e}");
+                HashMap::default()
+            });
+            let templates = Arc::new(templates);
+            let languages = Arc::new(languages);
+
+            let games = subs
+                .keys()
+                .chain(subscribed_mods.keys())
+                .chain(follows.keys())
+                .copied()
+                .collect::<BTreeSet<_>>();
 
-            for (game_id, subs) in subs {
-                if subs.is_empty() {
+            // Forget the held back file changes of games that are no longer polled.
+            let polled = games.iter().copied().collect::<Vec<_>>();
+            if let Err(e) = ctx.subscriptions.cleanup_pending_changes(&polled) {
+                error!("{e}");
+            }
+
+            for game_id in games {
+                let subs = subs.remove(&game_id).unwrap_or_default();
+                let subscribed_mods = subscribed_mods.remove(&game_id).unwrap_or_default();
+                let follows = follows.remove(&game_id).unwrap_or_default();
+                if subs.is_empty() && subscribed_mods.is_empty() && follows.is_empty() {
                     continue;
                 }
+                let ctx = ctx.clone();
                 let sender = sender.clone();
                 let subscriptions = ctx.subscriptions.clone();
                 let unknown_channels = unknown_channels2.clone();
@@ -125,11 +269,13 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                 let events = ctx.modio.game(*game_id).mods().events(filter);
                 let excluded_mods = Arc::clone(&excluded_mods);
                 let excluded_users = Arc::clone(&excluded_users);
+                let templates = Arc::clone(&templates);
+                let languages = Arc::clone(&languages);
 
                 let task = async move {
-                    type Events = BTreeMap<id::ModId, Vec<(id::EventId, EventType)>>;
-
-                    debug!("polling events at {tstamp} for game={game_id} subs: {subs:?}");
+                    debug!(
+                        "polling events at {tstamp} for game={game_id} subs: {subs:?} follows: {follows:?}"
+                    );
 
                     let game = match game.get().await {
                         Ok(game) => game,
@@ -139,7 +285,11 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                             );
 
                             if e.status().map(|s| s.as_u16()) == Some(404) {
-                                if let Err(e) = subscriptions.cleanup_unknown_games(&[game_id]) {
+                                match subscriptions.cleanup_unknown_games(&[game_id]) {
+                                    Ok(entries) => announce_changes(&ctx, &entries).await,
+                                    Err(e) => error!("{e}"),
+                                }
+                                if let Err(e) = ctx.follows.cleanup_unknown_games(&[game_id]) {
                                     error!("{e}");
                                 }
                             }
@@ -149,40 +299,57 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                     };
 
                     // - Group the events by mod
+                    // - Collapse multiple events of the same kind
                     // - Filter `MODFILE_CHANGED` events for new mods
+                    // - Debounce `MODFILE_CHANGED` events across ticks
                     // - Ungroup the events ordered by event id
 
                     let mut st = events.iter().await?;
-                    let mut events = Events::new();
+                    let mut events = GroupedEvents::new();
+                    let mut uploads = HashMap::new();
                     while let Some(event) = st.try_next().await? {
+                        if event.event_type == EventType::MODFILE_CHANGED {
+                            uploads.insert(event.mod_id, event.date_added.as_secs());
+                        }
                         events
                             .entry(event.mod_id)
                             .or_default()
                             .push((event.id, event.event_type));
                     }
 
-                    if events.is_empty() {
+                    let mut pending = subscriptions.pending_changes(game_id).unwrap_or_else(|e| {
+                        error!("{e}");
+                        PendingChanges::new()
+                    });
+                    if events.is_empty() && pending.is_empty() {
                         return Ok(());
                     }
 
-                    // Filter `MODFILE_CHANGED` events for new mods
-                    for evt in &mut events.values_mut() {
-                        if evt.iter().any(|(_, t)| t == &EventType::MOD_AVAILABLE) {
-                            let pos = evt
-                                .iter()
-                                .position(|(_, t)| t == &EventType::MODFILE_CHANGED);
-                            if let Some(pos) = pos {
-                                evt.remove(pos);
-                            }
+                    let held_back = pending.clone();
+                    #[allow(clippy::cast_possible_wrap)]
+                    let now = util::current_timestamp() as i64;
+                    collapse_events(&mut events, &uploads, &mut pending, debounce, now);
+
+                    // The released file changes stay stored until their notifications are queued
+                    // and are released again by the next poll if loading the mods fails.
+                    let mut stored = held_back.clone();
+                    stored.extend(pending.clone());
+                    if stored != held_back {
+                        if let Err(e) = subscriptions.save_pending_changes(game_id, &stored) {
+                            error!("{e}");
                         }
                     }
 
+                    if events.is_empty() {
+                        return Ok(());
+                    }
+
                     // Load the mods for the events
                     let filter = Id::_in(events.keys().collect::<Vec<_>>());
                     let mut st = mods.search(filter).iter().await?;
                     let events = {
                         let mut evts = Vec::new();
-                        while let Some(Ok(mod_)) = st.next().await {
+                        while let Some(mod_) = st.try_next().await? {
                             if let Some(evt) = events.get(&mod_.id) {
                                 evts.push((mod_, evt));
                             }
@@ -199,76 +366,108 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
                     }
 
                     for (_, (m, evt)) in updates {
-                        let mut effected_channels = BTreeSet::new();
-
-                        for Subscription {
-                            channel,
-                            tags,
-                            events: evts,
-                            explicit,
-                        } in &subs
-                        {
-                            if unknown_channels.contains(channel) {
-                                debug!("event ignored #{channel}: unknown channel");
-                                continue;
-                            }
-                            if *evt == EventType::MOD_AVAILABLE
-                                && !evts.contains(crate::db::Events::NEW)
-                                || *evt == EventType::MODFILE_CHANGED
-                                    && !evts.contains(crate::db::Events::UPD)
-                            {
-                                debug!("event ignored #{channel}: {evt} for {:?}", m.name);
+                        let mut effected_channels = BTreeMap::new();
+                        let mut crosspost = BTreeSet::new();
+
+                        for sub in &subs {
+                            if unknown_channels.contains(&sub.channel) {
+                                debug!("event ignored #{}: unknown channel", sub.channel);
                                 continue;
                             }
-                            if let Some(users) = excluded_users.get(&(game_id, *channel)) {
-                                if users.contains(&m.submitted_by.username)
-                                    || users.contains(&m.submitted_by.name_id)
-                                {
-                                    debug!(
-                                        "user ignored #{channel}: {evt} for {:?}/{:?}",
-                                        m.submitted_by.name_id, m.name,
-                                    );
-                                    continue;
-                                }
-                            }
-                            if let Some(mods) = excluded_mods.get(&(game_id, *channel)) {
-                                if mods.contains(&ModId(m.id)) {
-                                    debug!("mod ignored #{channel}: {evt} for {:?}", m.name);
-                                    continue;
+                            if is_wanted(sub, game_id, m, evt, &excluded_mods, &excluded_users) {
+                                effected_channels.entry(sub.channel).or_insert_with(|| {
+                                    let locale = languages.get(&sub.guild).copied();
+                                    let template = templates.get(sub.id, sub.guild);
+                                    let key = (template, sub.style, locale.unwrap_or_default());
+                                    (key, sub.guild)
+                                });
+                                if sub.crosspost {
+                                    crosspost.insert(sub.channel);
                                 }
                             }
-                            if !tags.is_empty() {
-                                let mod_tags = m.tags.iter().map(|t| t.name.as_str()).collect();
-
-                                // Hidden tags are saved with a leading `*`
-                                let tags: HashSet<_> =
-                                    tags.iter().map(|t| t.trim_start_matches('*')).collect();
-                                if !tags.is_subset(&mod_tags) {
-                                    debug!(
-                                        "mod ignored based on tags #{channel}: {evt} for {:?}",
-                                        m.name
-                                    );
-                                    trace!("mod tags: {mod_tags:?}; sub tags: {tags:?}");
-                                    continue;
+                        }
+                        for (channel, guild_id, mod_id, publish) in &subscribed_mods {
+                            if mod_id.0 == m.id && !unknown_channels.contains(channel) {
+                                if *publish {
+                                    crosspost.insert(*channel);
                                 }
+                                effected_channels.entry(*channel).or_insert_with(|| {
+                                    let template = templates.guild(*guild_id);
+                                    let locale = languages.get(guild_id).copied();
+                                    let key = (template, Style::Full, locale.unwrap_or_default());
+                                    (key, *guild_id)
+                                });
                             }
-                            if !explicit && m.maturity_option.contains(MaturityOption::EXPLICIT) {
-                                debug!("mod ignored based on maturiy options #{channel}: {evt} for {:?}", m.name);
-                                continue;
-                            }
-                            effected_channels.insert(*channel);
                         }
-                        if effected_channels.is_empty() {
-                            debug!("no channels left to send to");
+
+                        let mut followers = follows
+                            .iter()
+                            .filter(|(_, follow)| is_followed(follow, m))
+                            .map(|(user_id, _)| *user_id)
+                            .collect::<BTreeSet<_>>();
+
+                        if effected_channels.is_empty() && followers.is_empty() {
+                            debug!("no channels or users left to send to");
                             continue;
                         }
 
                         debug!(
-                            "send message {} for {:?} to {:?}",
-                            evt, m.name, effected_channels
+                            "send message {} for {:?} to {:?} and {:?}",
+                            evt,
+                            m.name,
+                            effected_channels.keys(),
+                            followers
                         );
-                        let (content, embed) = create_mod_message(&game, m, evt);
-                        if let Err(e) = sender.send((effected_channels, content, embed)).await {
+
+                        // Group the channels by their template, style and language, direct messages
+                        // use the default notification.
+                        let mut groups = BTreeMap::<_, BTreeMap<_, _>>::new();
+                        for (channel, (key, guild_id)) in effected_channels {
+                            groups.entry(key).or_default().insert(channel, guild_id);
+                        }
+                        if !followers.is_empty() {
+                            groups
+                                .entry((None, Style::Full, Locale::default()))
+                                .or_default();
+                        }
+
+                        let update = (*evt == EventType::MODFILE_CHANGED)
+                            .then(|| modfile_version(m))
+                            .flatten()
+                            .map(|version| (ModId(m.id), version));
+                        for ((template, style, locale), channels) in groups {
+                            let (content, embed) =
+                                create_mod_message(&game, m, evt, template.as_ref(), style, locale);
+                            let is_default = template.is_none()
+                                && style == Style::Full
+                                && locale == Locale::default();
+                            let users = if is_default {
+                                std::mem::take(&mut followers)
+                            } else {
+                                BTreeSet::new()
+                            };
+                            let notification = Notification {
+                                crosspost: channels
+                                    .keys()
+                                    .filter(|c| crosspost.contains(c))
+                                    .copied()
+                                    .collect(),
+                                channels,
+                                users,
+                                content,
+                                embeds: embed.into_iter().collect(),
+                                update: update.as_ref().map(|(id, v)| (ModId(id.0), v.clone())),
+                                locale,
+                            };
+                            if let Err(e) = sender.send(notification).await {
+                                error!("{e}");
+                                return Ok(());
+                            }
+                        }
+                    }
+
+                    if pending != stored {
+                        if let Err(e) = subscriptions.save_pending_changes(game_id, &pending) {
                             error!("{e}");
                         }
                     }
@@ -285,84 +484,581 @@ pub fn task(ctx: Context) -> impl Future<Output = ()> {
     }
 }
 
-fn create_mod_message(game: &Game, mod_: &Mod, event_type: &EventType) -> (Option<String>, Embed) {
+/// Events of a game grouped by mod.
+type GroupedEvents = BTreeMap<id::ModId, Vec<(id::EventId, EventType)>>;
+
+/// Collapses multiple events of the same kind into the latest event, drops the file changes of
+/// new mods and holds back file changes until the mod hasn't been updated for `debounce`
+/// seconds.
+///
+/// `uploads` are the upload times of the file changes. Held back file changes are kept in
+/// `pending` and are added back to the events once the debounce duration has passed.
+fn collapse_events(
+    events: &mut GroupedEvents,
+    uploads: &HashMap<id::ModId, i64>,
+    pending: &mut PendingChanges,
+    debounce: i64,
+    now: i64,
+) {
+    for evt in events.values_mut() {
+        let mut seen = HashSet::new();
+        evt.reverse();
+        evt.retain(|(_, t)| seen.insert(*t));
+        evt.reverse();
+
+        if evt.iter().any(|(_, t)| t == &EventType::MOD_AVAILABLE) {
+            evt.retain(|(_, t)| t != &EventType::MODFILE_CHANGED);
+        }
+    }
+
+    if debounce > 0 {
+        for (mod_id, evt) in events.iter_mut() {
+            let pos = evt
+                .iter()
+                .position(|(_, t)| t == &EventType::MODFILE_CHANGED);
+            if let Some(pos) = pos {
+                let (event_id, _) = evt.remove(pos);
+                let uploaded = uploads.get(mod_id).copied().unwrap_or(now);
+                pending.insert(*mod_id, (event_id, uploaded));
+            }
+        }
+    }
+    pending.retain(|mod_id, (event_id, uploaded)| {
+        if *uploaded > now - debounce {
+            return true;
+        }
+        events
+            .entry(*mod_id)
+            .or_default()
+            .push((*event_id, EventType::MODFILE_CHANGED));
+        false
+    });
+    events.retain(|_, evt| !evt.is_empty());
+}
+
+/// Returns `true` if the subscription wants to be notified about the event of the mod.
+pub fn is_wanted(
+    sub: &Subscription,
+    game_id: GameId,
+    m: &Mod,
+    evt: &EventType,
+    excluded_mods: &ExcludedModsMap,
+    excluded_users: &ExcludedUsersMap,
+) -> bool {
+    let Subscription {
+        channel,
+        tags,
+        events: evts,
+        explicit,
+        ..
+    } = sub;
+
+    if *evt == EventType::MOD_AVAILABLE && !evts.contains(crate::db::Events::NEW)
+        || *evt == EventType::MODFILE_CHANGED && !evts.contains(crate::db::Events::UPD)
+    {
+        debug!("event ignored #{channel}: {evt} for {:?}", m.name);
+        return false;
+    }
+    if let Some(users) = excluded_users.get(&(game_id, *channel)) {
+        if users.contains(&m.submitted_by.username) || users.contains(&m.submitted_by.name_id) {
+            debug!(
+                "user ignored #{channel}: {evt} for {:?}/{:?}",
+                m.submitted_by.name_id, m.name,
+            );
+            return false;
+        }
+    }
+    if let Some(mods) = excluded_mods.get(&(game_id, *channel)) {
+        if mods.contains(&ModId(m.id)) {
+            debug!("mod ignored #{channel}: {evt} for {:?}", m.name);
+            return false;
+        }
+    }
+    if !tags.is_empty() {
+        let mod_tags = m.tags.iter().map(|t| t.name.as_str()).collect();
+
+        // Hidden tags are saved with a leading `*`
+        let tags: HashSet<_> = tags.iter().map(|t| t.trim_start_matches('*')).collect();
+        if !tags.is_subset(&mod_tags) {
+            debug!(
+                "mod ignored based on tags #{channel}: {evt} for {:?}",
+                m.name
+            );
+            trace!("mod tags: {mod_tags:?}; sub tags: {tags:?}");
+            return false;
+        }
+    }
+    if !explicit && m.maturity_option.contains(MaturityOption::EXPLICIT) {
+        debug!(
+            "mod ignored based on maturiy options #{channel}: {evt} for {:?}",
+            m.name
+        );
+        return false;
+    }
+    true
+}
+
+/// Returns `true` if the follow of a user matches the mod.
+pub fn is_followed(follow: &Follow, m: &Mod) -> bool {
+    match follow {
+        Follow::Game => true,
+        Follow::Mod(mod_id) => mod_id.0 == m.id,
+        Follow::User(name) => {
+            m.submitted_by.username.eq_ignore_ascii_case(name)
+                || m.submitted_by.name_id.eq_ignore_ascii_case(name)
+        }
+    }
+}
+
+/// Sends the notification to the channel.
+///
+/// Returns the id of the new message if the response could be deserialized.
+pub async fn deliver(
+    ctx: &Context,
+    channel_id: ChannelId,
+    content: Option<&str>,
+    embeds: &[Embed],
+) -> Result<Option<MessageId>, twilight_http::Error> {
+    let mut msg = ctx.client.create_message(*channel_id).embeds(embeds);
+    if let Some(content) = content {
+        msg = msg.content(content);
+    }
+    match msg.await?.model().await {
+        Ok(message) => Ok(Some(MessageId(message.id))),
+        Err(e) => {
+            error!("{e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Sends the notification of a mod update to the channel or edits the previous notification
+/// of the mod if it's younger than the edit window of the guild.
+///
+/// Returns the id of the new message, edited notifications return `None`.
+async fn deliver_update(
+    ctx: &Context,
+    channel_id: ChannelId,
+    guild_id: GuildId,
+    (mod_id, version): (&ModId, &str),
+    content: Option<&str>,
+    embed: Option<&Embed>,
+    locale: Locale,
+) -> Result<Option<MessageId>, twilight_http::Error> {
+    let window = ctx.settings.edit_window(guild_id).unwrap_or_else(|e| {
+        error!("{e}");
+        None
+    });
+    let Some(window) = window else {
+        return deliver(
+            ctx,
+            channel_id,
+            content,
+            embed.map_or(&[], std::slice::from_ref),
+        )
+        .await;
+    };
+
+    #[allow(clippy::cast_possible_wrap)]
+    let now = util::current_timestamp() as i64;
+    let previous = ctx
+        .subscriptions
+        .last_notification(channel_id, mod_id, now - window)
+        .unwrap_or_else(|e| {
+            error!("{e}");
+            None
+        });
+
+    if let Some(mut notification) = previous {
+        if !notification.versions.iter().any(|v| v == version) {
+            notification.versions.push(version.to_owned());
+        }
+        let embeds = embed
+            .map(|embed| with_versions(embed, &notification.versions, locale))
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut req = ctx
+            .client
+            .update_message(*channel_id, notification.message.0)
+            .embeds(Some(&embeds));
+        // Text-only notifications are replaced with the latest text.
+        if embed.is_none() {
+            req = req.content(content);
+        }
+        let res = req.await;
+        match res {
+            Ok(_) => {
+                if let Err(e) =
+                    ctx.subscriptions
+                        .save_notification(channel_id, mod_id, &notification)
+                {
+                    error!("{e}");
+                }
+                return Ok(None);
+            }
+            Err(e) if util::is_unknown_message_error(e.kind()) => {
+                debug!("previous notification in #{channel_id} is gone: {e}");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut msg = ctx
+        .client
+        .create_message(*channel_id)
+        .embeds(embed.map_or(&[], std::slice::from_ref));
+    if let Some(content) = content {
+        msg = msg.content(content);
+    }
+    match msg.await?.model().await {
+        Ok(message) => {
+            let notification = NotificationMessage {
+                message: MessageId(message.id),
+                versions: vec![version.to_owned()],
+                created: now,
+            };
+            if let Err(e) = ctx
+                .subscriptions
+                .save_notification(channel_id, mod_id, &notification)
+            {
+                error!("{e}");
+            }
+            Ok(Some(notification.message))
+        }
+        Err(e) => {
+            error!("{e}");
+            Ok(None)
+        }
+    }
+}
+
+/// Publishes the notification to the servers following the announcement channel.
+///
+/// Rate limited requests are retried after the requested delay.
+async fn publish(ctx: Context, channel_id: ChannelId, message_id: MessageId) {
+    let kind = ctx.cache.channel(*channel_id).map(|c| c.kind);
+    if kind != Some(ChannelType::GuildAnnouncement) {
+        debug!("crosspost ignored #{channel_id}: not an announcement channel");
+        return;
+    }
+    for _ in 0..CROSSPOST_ATTEMPTS {
+        let Err(e) = ctx
+            .client
+            .crosspost_message(*channel_id, message_id.0)
+            .await
+        else {
+            return;
+        };
+        match util::ratelimit_retry_after(e.kind()) {
+            Some(delay) if delay <= MAX_CROSSPOST_DELAY => {
+                debug!("crosspost in #{channel_id} rate limited, retrying in {delay:?}");
+                time::sleep(delay).await;
+            }
+            _ => {
+                error!("failed to crosspost in #{channel_id}: {e}");
+                return;
+            }
+        }
+    }
+    error!("failed to crosspost in #{channel_id}: too many attempts");
+}
+
+/// Returns the version of the mod's primary file or the filename without a version.
+fn modfile_version(mod_: &Mod) -> Option<String> {
+    let file = mod_.modfile.as_ref()?;
+    let version = file.version.as_ref().filter(|v| !v.is_empty());
+    Some(version.unwrap_or(&file.filename).clone())
+}
+
+/// Adds the list of versions to the embed of an edited notification.
+fn with_versions(embed: &Embed, versions: &[String], locale: Locale) -> Embed {
+    let mut embed = embed.clone();
+    if versions.len() > 1 {
+        let mut value = String::new();
+        // Keep the latest versions if the list gets too long for the field.
+        for version in versions.iter().rev() {
+            if value.len() + version.len() + 5 > 1024 {
+                break;
+            }
+            value.insert_str(0, &format!("• {version}\n"));
+        }
+        embed.fields.push(EmbedField {
+            inline: false,
+            name: locale.tr("Versions").to_owned(),
+            value,
+        });
+    }
+    embed
+}
+
+/// Sends the notification as direct message to the user.
+pub async fn deliver_dm(
+    ctx: &Context,
+    user_id: UserId,
+    content: Option<&str>,
+    embeds: &[Embed],
+) -> Result<(), Error> {
+    let channel = ctx
+        .client
+        .create_private_channel(user_id.0)
+        .await?
+        .model()
+        .await?;
+
+    let mut msg = ctx.client.create_message(channel.id).embeds(embeds);
+    if let Some(content) = content {
+        msg = msg.content(content);
+    }
+    msg.await?;
+    Ok(())
+}
+
+/// The placeholders of the notification templates with their description.
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("mod", "Name of the mod"),
+    ("version", "Version of the mod's primary file"),
+    ("author", "Username of the mod's author"),
+    ("tags", "Tags of the mod"),
+    ("game", "Name of the game"),
+    ("summary", "Summary of the mod"),
+    ("changelog", "Changelog of the mod's primary file"),
+    ("uploaded", "Upload date of the mod's primary file"),
+    (
+        "event",
+        "Default text of the notification, e.g. `A new version is available.`",
+    ),
+    ("url", "Link to the mod's profile"),
+    ("download", "Download link of the mod's primary file"),
+];
+
+const MAX_CONTENT_LENGTH: usize = 2000;
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Creates the notification for the event in the style of the subscription and applies the
+/// template if there is one.
+///
+/// Text-only notifications are returned without an embed.
+pub fn create_mod_message(
+    game: &Game,
+    mod_: &Mod,
+    event_type: &EventType,
+    template: Option<&Template>,
+    style: Style,
+    locale: Locale,
+) -> (Option<String>, Option<Embed>) {
+    let (mut content, mut embed) = create_default_message(game, mod_, event_type, style, locale);
+    if let Some(template) = template {
+        let values = placeholder_values(game, mod_, event_type, style, locale);
+        (content, embed) = apply_template(template, &values, content, embed);
+    }
+    if style == Style::Text {
+        return (Some(text_message(content, &embed)), None);
+    }
+    (content, Some(embed))
+}
+
+fn apply_template(
+    template: &Template,
+    values: &HashMap<&str, String>,
+    content: Option<String>,
+    mut embed: Embed,
+) -> (Option<String>, Embed) {
+    let render = |text: &str, max: usize| {
+        let text = util::expand_placeholders(text, |name| values.get(name).map(String::as_str))
+            .unwrap_or_else(|_| text.to_owned());
+        let text = text.trim();
+        (!text.is_empty()).then(|| util::truncate(text.to_owned(), max))
+    };
+
+    let content = match &template.content {
+        Some(text) => render(text, MAX_CONTENT_LENGTH),
+        None => content,
+    };
+    if let Some(text) = &template.title {
+        embed.title = render(text, MAX_TITLE_LENGTH);
+    }
+    if let Some(text) = &template.description {
+        embed.description = render(text, MAX_DESCRIPTION_LENGTH);
+    }
+    (content, embed)
+}
+
+/// Flattens the notification into a plain text message.
+fn text_message(content: Option<String>, embed: &Embed) -> String {
+    let mut lines = Vec::new();
+    lines.extend(content);
+    match (&embed.title, &embed.description) {
+        (Some(title), Some(desc)) => lines.push(format!("**{title}**: {desc}")),
+        (Some(title), None) => lines.push(format!("**{title}**")),
+        (None, Some(desc)) => lines.push(desc.clone()),
+        (None, None) => {}
+    }
+    if let Some(url) = &embed.url {
+        lines.push(format!("<{url}>"));
+    }
+    util::truncate(lines.join("\n"), MAX_CONTENT_LENGTH)
+}
+
+/// Returns the values of the template placeholders for the event of the mod.
+///
+/// Dates are formatted as plain text for text-only notifications because bridges of the
+/// channel don't render the Discord timestamps.
+fn placeholder_values(
+    game: &Game,
+    mod_: &Mod,
+    event_type: &EventType,
+    style: Style,
+    locale: Locale,
+) -> HashMap<&'static str, String> {
+    let with_ddl = game
+        .api_access_options
+        .contains(ApiAccessOptions::ALLOW_DIRECT_DOWNLOAD);
+    let file = mod_.modfile.as_ref();
+
+    let tags = mod_
+        .tags
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect::<Vec<_>>();
+    let version = file.and_then(|f| f.version.clone()).unwrap_or_default();
+    let changelog = file
+        .and_then(|f| f.changelog.as_ref())
+        .map(util::strip_html_tags)
+        .unwrap_or_default();
+    let download = file
+        .filter(|_| with_ddl)
+        .map(|f| f.download.binary_url.to_string())
+        .unwrap_or_default();
+    let uploaded = file
+        .map(|f| match style {
+            Style::Text => util::format_timestamp(f.date_added.as_secs()),
+            _ => util::discord_timestamp(f.date_added.as_secs(), TimestampStyle::Relative),
+        })
+        .unwrap_or_default();
+
+    HashMap::from([
+        ("mod", mod_.name.clone()),
+        ("version", version),
+        ("author", mod_.submitted_by.username.clone()),
+        ("tags", tags.join(", ")),
+        ("game", game.name.clone()),
+        ("summary", mod_.summary.clone()),
+        ("changelog", changelog),
+        ("uploaded", uploaded),
+        ("event", locale.tr(event_text(event_type)).to_owned()),
+        ("url", mod_.profile_url.to_string()),
+        ("download", download),
+    ])
+}
+
+/// Returns the default text of the notification for the event.
+fn event_text(event_type: &EventType) -> &'static str {
+    match *event_type {
+        EventType::MOD_EDITED => "The mod has been edited.",
+        EventType::MOD_AVAILABLE => "A new mod is available.",
+        EventType::MOD_UNAVAILABLE => "The mod is now unavailable.",
+        EventType::MODFILE_CHANGED => "A new version is available.",
+        EventType::MOD_DELETED => "The mod has been permanently deleted.",
+        _ => "event ignored",
+    }
+}
+
+fn create_default_message(
+    game: &Game,
+    mod_: &Mod,
+    event_type: &EventType,
+    style: Style,
+    locale: Locale,
+) -> (Option<String>, Embed) {
+    let event = locale.tr(event_text(event_type));
     let with_ddl = game
         .api_access_options
         .contains(ApiAccessOptions::ALLOW_DIRECT_DOWNLOAD);
 
+    if style != Style::Full {
+        let desc = match *event_type {
+            EventType::MODFILE_CHANGED => {
+                format!("{event} {}", download_link(mod_, with_ddl, locale))
+            }
+            _ => event.to_owned(),
+        };
+        let embed = EmbedBuilder::new()
+            .title(mod_.name.clone())
+            .url(mod_.profile_url.to_string())
+            .description(desc.trim_end())
+            .thumbnail(ImageSource::url(mod_.logo.thumb_320x180.to_string()).unwrap());
+        return (None, embed.build());
+    }
+
     let embed = match *event_type {
-        EventType::MOD_EDITED => create_embed(game, mod_, "The mod has been edited.", false),
         EventType::MOD_AVAILABLE => {
-            let content = "A new mod is available. :tada:".to_owned();
+            let content = format!("{event} :tada:");
             let embed = create_embed(game, mod_, &mod_.summary, true);
-            let embed = create_fields(embed, game, mod_, true, with_ddl).build();
+            let embed = create_fields(embed, game, mod_, true, with_ddl, locale).build();
             return (Some(content), embed);
         }
-        EventType::MOD_UNAVAILABLE => {
-            create_embed(game, mod_, "The mod is now unavailable.", false)
-        }
         EventType::MODFILE_CHANGED => {
-            let (download, changelog) = mod_
+            let changelog = mod_
                 .modfile
                 .as_ref()
-                .map(|f| {
-                    let link = &f.download.binary_url;
-                    let no_version = || {
-                        if with_ddl {
-                            format!("[Download]({link})")
-                        } else {
-                            String::new()
-                        }
-                    };
-                    let version = |v| {
-                        if with_ddl {
-                            format!("[Version {v}]({link})")
+                .and_then(|f| f.changelog.as_ref())
+                .map(util::strip_html_tags)
+                .filter(|c| !c.is_empty())
+                .map(|c| {
+                    let it = c.char_indices().rev().scan(c.len(), |state, (pos, _)| {
+                        if *state > 1024 {
+                            *state = pos;
+                            Some(pos)
                         } else {
-                            format!("Version {v}")
+                            None
                         }
-                    };
-                    let download = f
-                        .version
-                        .as_ref()
-                        .filter(|v| !v.is_empty())
-                        .map_or_else(no_version, version);
-                    let changelog = f
-                        .changelog
-                        .as_ref()
-                        .map(util::strip_html_tags)
-                        .filter(|c| !c.is_empty())
-                        .map(|c| {
-                            let it = c.char_indices().rev().scan(c.len(), |state, (pos, _)| {
-                                if *state > 1024 {
-                                    *state = pos;
-                                    Some(pos)
-                                } else {
-                                    None
-                                }
-                            });
-                            let pos = it.last().unwrap_or(c.len());
-                            EmbedFieldBuilder::new("Changelog", c[..pos].to_owned()).inline()
-                        });
-                    (download, changelog)
-                })
-                .unwrap_or_default();
-
-            let desc = format!("A new version is available. {download}");
+                    });
+                    let pos = it.last().unwrap_or(c.len());
+                    EmbedFieldBuilder::new(locale.tr("Changelog"), c[..pos].to_owned()).inline()
+                });
+
+            let download = download_link(mod_, with_ddl, locale);
+            let mut desc = format!("{event} {download}");
+            if let Some(file) = &mod_.modfile {
+                let date =
+                    util::discord_timestamp(file.date_added.as_secs(), TimestampStyle::Relative);
+                desc.push('\n');
+                desc.push_str(&locale.format("Uploaded {date}", &[("date", &date)]));
+            }
             let mut embed = create_embed(game, mod_, &desc, false);
             if let Some(changelog) = changelog {
                 embed = embed.field(changelog);
             }
             embed
         }
-        EventType::MOD_DELETED => {
-            create_embed(game, mod_, "The mod has been permanently deleted.", false)
-        }
-        _ => create_embed(game, mod_, "event ignored", false),
+        _ => create_embed(game, mod_, event, false),
     };
 
     (None, embed.build())
 }
 
+/// Returns the version of the mod's primary file, linked to the download if the game allows
+/// direct downloads.
+fn download_link(mod_: &Mod, with_ddl: bool, locale: Locale) -> String {
+    let Some(file) = &mod_.modfile else {
+        return String::new();
+    };
+    let link = &file.download.binary_url;
+    match file.version.as_ref().filter(|v| !v.is_empty()) {
+        Some(v) => {
+            let text = locale.format("Version {version}", &[("version", v)]);
+            if with_ddl {
+                format!("[{text}]({link})")
+            } else {
+                text
+            }
+        }
+        None if with_ddl => format!("[{}]({link})", locale.tr("Download")),
+        None => String::new(),
+    }
+}
+
 fn create_embed(game: &Game, mod_: &Mod, desc: &str, big_thumbnail: bool) -> EmbedBuilder {
     let mut footer = EmbedFooterBuilder::new(mod_.submitted_by.username.clone());
     if let Some(avatar) = &mod_.submitted_by.avatar {
@@ -386,3 +1082,93 @@ fn create_embed(game: &Game, mod_: &Mod, desc: &str, big_thumbnail: bool) -> Emb
         embed.thumbnail(ImageSource::url(mod_.logo.thumb_320x180.to_string()).unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use modio::types::id::{EventId, ModId};
+    use modio::types::mods::EventType;
+
+    use super::{collapse_events, GroupedEvents, PendingChanges};
+
+    #[test]
+    fn collapse_repeated_events() {
+        let mut events = GroupedEvents::new();
+        events.insert(
+            ModId::new(1),
+            vec![
+                (EventId::new(1), EventType::MOD_UNAVAILABLE),
+                (EventId::new(2), EventType::MOD_AVAILABLE),
+                (EventId::new(3), EventType::MOD_UNAVAILABLE),
+            ],
+        );
+        let mut pending = PendingChanges::new();
+
+        collapse_events(&mut events, &HashMap::new(), &mut pending, 0, 100);
+
+        assert_eq!(
+            events[&ModId::new(1)],
+            [
+                (EventId::new(2), EventType::MOD_AVAILABLE),
+                (EventId::new(3), EventType::MOD_UNAVAILABLE),
+            ]
+        );
+    }
+
+    #[test]
+    fn drop_file_changes_of_new_mods() {
+        let mut events = GroupedEvents::new();
+        events.insert(
+            ModId::new(1),
+            vec![
+                (EventId::new(1), EventType::MOD_AVAILABLE),
+                (EventId::new(2), EventType::MODFILE_CHANGED),
+            ],
+        );
+        let uploads = HashMap::from([(ModId::new(1), 100)]);
+        let mut pending = PendingChanges::new();
+
+        collapse_events(&mut events, &uploads, &mut pending, 60, 100);
+
+        assert_eq!(
+            events[&ModId::new(1)],
+            [(EventId::new(1), EventType::MOD_AVAILABLE)]
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn release_file_changes_after_debounce() {
+        let mut events = GroupedEvents::new();
+        events.insert(
+            ModId::new(1),
+            vec![(EventId::new(1), EventType::MODFILE_CHANGED)],
+        );
+        let uploads = HashMap::from([(ModId::new(1), 100)]);
+        let mut pending = PendingChanges::new();
+
+        collapse_events(&mut events, &uploads, &mut pending, 60, 120);
+        assert!(events.is_empty());
+        assert_eq!(pending[&ModId::new(1)], (EventId::new(1), 100));
+
+        // A newer file change restarts the debounce duration.
+        let mut events = GroupedEvents::new();
+        events.insert(
+            ModId::new(1),
+            vec![(EventId::new(2), EventType::MODFILE_CHANGED)],
+        );
+        let uploads = HashMap::from([(ModId::new(1), 150)]);
+        collapse_events(&mut events, &uploads, &mut pending, 60, 180);
+        assert!(events.is_empty());
+        assert_eq!(pending[&ModId::new(1)], (EventId::new(2), 150));
+
+        let mut events = GroupedEvents::new();
+        collapse_events(&mut events, &HashMap::new(), &mut pending, 60, 210);
+        assert_eq!(
+            events[&ModId::new(1)],
+            [(EventId::new(2), EventType::MODFILE_CHANGED)]
+        );
+        assert!(pending.is_empty());
+    }
+}
@@ -6,7 +6,9 @@ use tracing::error;
 
 use crate::bot::Context;
 use crate::db::autocomplete::{replace_games, Game};
+use crate::db::stats::{day_of, record_game_stats, GameStats};
 use crate::db::types::{ApiAccessOptions, GameId};
+use crate::util;
 
 const MIN: Duration = Duration::from_secs(45);
 const INTERVAL_DURATION: Duration = Duration::from_secs(4500);
@@ -22,7 +24,7 @@ pub async fn task(ctx: Context) {
 
         let task = async move {
             let games = games.collect().await?;
-            let games = games
+            let records = games
                 .iter()
                 .map(|g| Game {
                     id: GameId(g.id),
@@ -32,7 +34,26 @@ pub async fn task(ctx: Context) {
                 })
                 .collect::<Vec<_>>();
 
-            if let Err(e) = replace_games(&pool, &games) {
+            if let Err(e) = replace_games(&pool, &records) {
+                error!("{e}");
+            }
+
+            #[allow(clippy::cast_possible_wrap)]
+            let date = day_of(util::current_timestamp() as i64);
+            let stats = games
+                .iter()
+                .filter_map(|g| {
+                    g.stats.as_ref().map(|s| GameStats {
+                        game: GameId(g.id),
+                        date,
+                        mods: i64::from(s.mods_total),
+                        subscribers: i64::from(s.subscribers_total),
+                        downloads: i64::from(s.downloads.total),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if let Err(e) = record_game_stats(&pool, &stats) {
                 error!("{e}");
             }
 
@@ -1,2 +1,3 @@
 pub mod events;
 pub mod games;
+pub mod stats;
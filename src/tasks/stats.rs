@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use modio::filter::prelude::*;
+use modio::mods::filters::stats;
+use tokio::time::{self, Instant};
+use tracing::error;
+
+use crate::bot::Context;
+use crate::db::stats::{cleanup_mod_stats, day_of, record_mod_stats, ModStats};
+use crate::db::types::{GameId, ModId};
+use crate::db::Result;
+use crate::util;
+
+const MIN: Duration = Duration::from_secs(120);
+const INTERVAL_DURATION: Duration = Duration::from_secs(86400);
+/// Maximum number of mod ids per statistics request to keep the URL short.
+const CHUNK_SIZE: usize = 100;
+
+type TrackedMods = BTreeMap<GameId, BTreeSet<modio::types::id::ModId>>;
+
+/// Records the statistics of the subscribed and followed mods once a day.
+pub async fn task(ctx: Context) {
+    let mut interval = time::interval_at(Instant::now() + MIN, INTERVAL_DURATION);
+
+    loop {
+        interval.tick().await;
+
+        let (games, mods) = match tracked(&ctx) {
+            Ok(tracked) => tracked,
+            Err(e) => {
+                error!("{e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = cleanup_mod_stats(&ctx.pool, &games) {
+            error!("{e}");
+        }
+
+        #[allow(clippy::cast_possible_wrap)]
+        let date = day_of(util::current_timestamp() as i64);
+
+        for (game_id, mod_ids) in mods {
+            let mod_ids = mod_ids.into_iter().collect::<Vec<_>>();
+            let mut records = Vec::with_capacity(mod_ids.len());
+
+            for chunk in mod_ids.chunks(CHUNK_SIZE) {
+                let filter = stats::ModId::_in(chunk.to_vec());
+                let stats = ctx.modio.game(*game_id).mods().statistics(filter);
+
+                let stats = match stats.collect().await {
+                    Ok(stats) => stats,
+                    Err(e) => {
+                        error!("failed to load mod stats for game={game_id}: {e}");
+                        continue;
+                    }
+                };
+
+                records.extend(stats.into_iter().map(|s| ModStats {
+                    game: game_id,
+                    mod_id: ModId(s.mod_id),
+                    date,
+                    downloads: i64::from(s.downloads_total),
+                    subscribers: i64::from(s.subscribers_total),
+                    rank: i64::from(s.popularity.rank_position),
+                    ratings_positive: i64::from(s.ratings.positive),
+                    ratings_negative: i64::from(s.ratings.negative),
+                }));
+            }
+
+            if let Err(e) = record_mod_stats(&ctx.pool, game_id, &records) {
+                error!("{e}");
+            }
+        }
+    }
+}
+
+/// Returns the games with subscriptions or follows and the subscribed and followed mods.
+fn tracked(ctx: &Context) -> Result<(Vec<GameId>, TrackedMods)> {
+    let mut games = ctx.subscriptions.get_games()?;
+    games.extend(ctx.follows.get_games()?);
+    games.sort_unstable();
+    games.dedup();
+
+    let mut mods = TrackedMods::new();
+    let list = ctx.subscriptions.get_mods()?.into_iter();
+    for (game_id, mod_id) in list.chain(ctx.follows.get_mods()?) {
+        mods.entry(game_id).or_default().insert(mod_id.0);
+    }
+    Ok((games, mods))
+}
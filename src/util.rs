@@ -38,6 +38,49 @@ pub fn is_unknown_channel_error(err: &ErrorType) -> bool {
     )
 }
 
+pub fn is_unknown_message_error(err: &ErrorType) -> bool {
+    matches!(err,
+        ErrorType::Response {
+            error: ApiError::General(e),
+            status,
+            ..
+        } if status.get() == 404 && e.code == 10008
+    )
+}
+
+/// Returns `true` for the `Missing Access` and `Missing Permissions` errors.
+pub fn is_missing_permissions_error(err: &ErrorType) -> bool {
+    matches!(err,
+        ErrorType::Response {
+            error: ApiError::General(e),
+            status,
+            ..
+        } if status.get() == 403 && matches!(e.code, 50001 | 50013)
+    )
+}
+
+/// Returns `true` for the `Cannot send messages to this user` error of closed direct messages.
+pub fn is_cannot_message_user_error(err: &ErrorType) -> bool {
+    matches!(err,
+        ErrorType::Response {
+            error: ApiError::General(e),
+            status,
+            ..
+        } if status.get() == 403 && e.code == 50007
+    )
+}
+
+/// Returns the time to wait before retrying a request that was rate limited.
+pub fn ratelimit_retry_after(err: &ErrorType) -> Option<Duration> {
+    match err {
+        ErrorType::Response {
+            error: ApiError::Ratelimited(e),
+            ..
+        } => Duration::try_from_secs_f64(e.retry_after).ok(),
+        _ => None,
+    }
+}
+
 async fn get_unknown_channels(ctx: &Context) -> Result<Vec<ChannelId>> {
     let channels = ctx.subscriptions.get_channels()?;
 
@@ -165,12 +208,81 @@ pub fn current_timestamp() -> u64 {
         .as_secs()
 }
 
+/// Returns a random number in the range `0..upper`.
+pub fn random(upper: usize) -> usize {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    if upper == 0 {
+        return 0;
+    }
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    usize::try_from(hasher.finish() % upper as u64).unwrap_or_default()
+}
+
+/// Parses a duration like `30m`, `12h`, `3d` or `1w2d`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    let mut total = 0u64;
+    let mut value = None::<u64>;
+    for c in s.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            let v = value.unwrap_or_default();
+            value = Some(v.checked_mul(10)?.checked_add(u64::from(digit))?);
+            continue;
+        }
+        let unit = match c {
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            'w' => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+        total = total.checked_add(value.take()?.checked_mul(unit)?)?;
+    }
+    if value.is_some() || total == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(total))
+}
+
+/// Display styles of the Discord timestamp markup.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimestampStyle {
+    /// `18/10/2026`
+    ShortDate,
+    /// `18 October 2026 16:20`
+    DateTime,
+    /// `3 hours ago`
+    Relative,
+}
+
+/// Formats the timestamp as Discord markup which is rendered in the local time of the viewer.
+pub fn discord_timestamp(seconds: i64, style: TimestampStyle) -> String {
+    let style = match style {
+        TimestampStyle::ShortDate => 'd',
+        TimestampStyle::DateTime => 'f',
+        TimestampStyle::Relative => 'R',
+    };
+    format!("<t:{seconds}:{style}>")
+}
+
+/// Formats the timestamp in UTC for plain text where the Discord markup isn't rendered.
 pub fn format_timestamp(seconds: i64) -> String {
     use time::format_description::FormatItem;
     use time::macros::format_description;
     use time::OffsetDateTime;
 
-    const FMT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day] [hour]:[minute]");
+    const FMT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day] [hour]:[minute] UTC");
 
     if let Ok(v) = OffsetDateTime::from_unix_timestamp(seconds) {
         if let Ok(s) = v.format(&FMT) {
@@ -180,6 +292,93 @@ pub fn format_timestamp(seconds: i64) -> String {
     String::new()
 }
 
+/// Truncates the string to `max` characters and marks the cut with an ellipsis.
+pub fn truncate(mut s: String, max: usize) -> String {
+    if s.chars().count() > max {
+        s = s.chars().take(max - 1).collect();
+        s.push('…');
+    }
+    s
+}
+
+/// Error of a template with an invalid placeholder.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PlaceholderError {
+    Unknown(String),
+    Unclosed,
+    Unmatched,
+}
+
+impl fmt::Display for PlaceholderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown placeholder `{{{name}}}`"),
+            Self::Unclosed => f.write_str("unclosed placeholder, `}` is missing"),
+            Self::Unmatched => f.write_str("unmatched `}`, use `}}` for a literal brace"),
+        }
+    }
+}
+
+/// Replaces the `{name}` placeholders of the template with the values returned by `lookup`.
+///
+/// Literal braces are written as `{{` and `}}`.
+pub fn expand_placeholders<'a, F>(
+    template: &str,
+    lookup: F,
+) -> std::result::Result<String, PlaceholderError>
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.next_if_eq(&'{').is_some() => out.push('{'),
+            '}' if chars.next_if_eq(&'}').is_some() => out.push('}'),
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(PlaceholderError::Unclosed),
+                    }
+                }
+                let name = name.trim();
+                match lookup(name) {
+                    Some(value) => out.push_str(value),
+                    None => return Err(PlaceholderError::Unknown(name.to_owned())),
+                }
+            }
+            '}' => return Err(PlaceholderError::Unmatched),
+            c => out.push(c),
+        }
+    }
+    Ok(out)
+}
+
+/// Renders the values as a line of unicode block characters scaled between the min and max value.
+pub fn sparkline(values: &[i64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    #[allow(clippy::cast_possible_wrap)]
+    const STEPS: i64 = BARS.len() as i64 - 1;
+
+    let min = values.iter().copied().min().unwrap_or_default();
+    let max = values.iter().copied().max().unwrap_or_default();
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|v| {
+            if range == 0 {
+                return BARS[0];
+            }
+            let idx = (v - min) * STEPS / range;
+            BARS[usize::try_from(idx).unwrap_or_default()]
+        })
+        .collect()
+}
+
 pub fn strip_html_tags<S: AsRef<str>>(input: S) -> String {
     use html5ever::tendril::TendrilSink;
     use html5ever::{parse_document, ParseOpts};
@@ -315,8 +514,12 @@ mod sink {
 
 #[cfg(test)]
 mod tests {
-    use super::{strip_html_tags, ContentBuilder};
+    use super::{
+        discord_timestamp, expand_placeholders, format_timestamp, parse_duration, sparkline,
+        strip_html_tags, truncate, ContentBuilder, PlaceholderError, TimestampStyle,
+    };
     use std::fmt::Write;
+    use std::time::Duration;
 
     #[test]
     fn content_builder() {
@@ -343,6 +546,80 @@ mod tests {
         assert_eq!(c.content[2], "foobar");
     }
 
+    #[test]
+    fn duration() {
+        assert_eq!(parse_duration("30m"), Some(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("12h"), Some(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_duration("1w2d"), Some(Duration::from_secs(9 * 86400)));
+        assert_eq!(
+            parse_duration(" 1d12h "),
+            Some(Duration::from_secs(36 * 3600))
+        );
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("12"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration("0d"), None);
+        assert_eq!(parse_duration("1y"), None);
+    }
+
+    #[test]
+    fn placeholders() {
+        let lookup = |name: &str| match name {
+            "mod" => Some("Foo"),
+            "version" => Some("1.0"),
+            _ => None,
+        };
+        assert_eq!(
+            expand_placeholders("{mod} {version}", lookup),
+            Ok("Foo 1.0".to_owned())
+        );
+        assert_eq!(
+            expand_placeholders("{{mod}} { mod }!", lookup),
+            Ok("{mod} Foo!".to_owned())
+        );
+        assert_eq!(expand_placeholders("", lookup), Ok(String::new()));
+        assert_eq!(
+            expand_placeholders("{game}", lookup),
+            Err(PlaceholderError::Unknown("game".to_owned()))
+        );
+        assert_eq!(
+            expand_placeholders("{mod", lookup),
+            Err(PlaceholderError::Unclosed)
+        );
+        assert_eq!(
+            expand_placeholders("mod}", lookup),
+            Err(PlaceholderError::Unmatched)
+        );
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let ts = 1_700_000_000;
+        assert_eq!(
+            "<t:1700000000:f>",
+            discord_timestamp(ts, TimestampStyle::DateTime)
+        );
+        assert_eq!(
+            "<t:1700000000:R>",
+            discord_timestamp(ts, TimestampStyle::Relative)
+        );
+        assert_eq!("2023-11-14 22:13 UTC", format_timestamp(ts));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!("foo", truncate("foo".to_owned(), 3));
+        assert_eq!("fo…", truncate("foobar".to_owned(), 3));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!("", sparkline(&[]));
+        assert_eq!("▁▁▁", sparkline(&[5, 5, 5]));
+        assert_eq!("▁▄█", sparkline(&[0, 50, 100]));
+        assert_eq!("█▁▂", sparkline(&[70, 0, 10]));
+    }
+
     #[test]
     fn test_strip_html_tags() {
         let input = "aaa<br/>";